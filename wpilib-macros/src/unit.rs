@@ -0,0 +1,673 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{parenthesized, Expr, Ident, LitStr, Token, Type};
+
+/// `Name("symbol", "alias", ...)` followed by `= scale` or `= (scale, offset)` unless it's the
+/// coherent unit, with an optional `: type` before the scale; the type defaults to `f64`.
+struct UnitDecl {
+    name: Ident,
+    symbols: Vec<LitStr>,
+    r#type: Ident,
+    scale: Option<(Expr, Expr)>,
+}
+
+impl Parse for UnitDecl {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name: Ident = input.parse()?;
+        let content;
+        parenthesized!(content in input);
+        let symbols = Punctuated::<LitStr, Token![,]>::parse_terminated(&content)?
+            .into_iter()
+            .collect::<Vec<_>>();
+        if symbols.is_empty() {
+            return Err(syn::Error::new(name.span(), "a unit needs a symbol"));
+        }
+        let r#type = if input.peek(Token![:]) {
+            input.parse::<Token![:]>()?;
+            input.parse()?
+        } else {
+            Ident::new("f64", name.span())
+        };
+        let scale = if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            match input.parse()? {
+                Expr::Tuple(tuple) if tuple.elems.len() == 2 => {
+                    let mut elems = tuple.elems.into_iter();
+                    Some((
+                        elems.next().expect("tuple has two elements"),
+                        elems.next().expect("tuple has two elements"),
+                    ))
+                }
+                scale => Some((scale, syn::parse_quote!(0.0))),
+            }
+        } else {
+            None
+        };
+        Ok(Self {
+            name,
+            symbols,
+            r#type,
+            scale,
+        })
+    }
+}
+
+/// `Dimension: Unit("symbol"), Unit("symbol") = scale, ...`
+struct UnitGroup {
+    dimension: Type,
+    units: Vec<UnitDecl>,
+}
+
+impl Parse for UnitGroup {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let dimension = input.parse()?;
+        input.parse::<Token![:]>()?;
+        let units = Punctuated::<UnitDecl, Token![,]>::parse_terminated(input)?
+            .into_iter()
+            .collect::<Vec<_>>();
+        for unit in units.iter().skip(1) {
+            if unit.scale.is_none() {
+                return Err(syn::Error::new(
+                    unit.name.span(),
+                    "only the first unit of a group can leave out its scale",
+                ));
+            }
+        }
+        Ok(Self { dimension, units })
+    }
+}
+
+pub fn unit(input: TokenStream) -> TokenStream {
+    let group = match syn::parse2::<UnitGroup>(input) {
+        Ok(group) => group,
+        Err(err) => return err.to_compile_error(),
+    };
+    let dimension = &group.dimension;
+
+    let mut output = TokenStream::new();
+    // a group led by a unit without a scale gives the dimension its coherent SI unit
+    if let Some(first) = group.units.first().filter(|unit| unit.scale.is_none()) {
+        let name = &first.name;
+        output.extend(quote! {
+            impl wpilib::math::units::dimension::SiUnit for #dimension {
+                type Unit = #name;
+            }
+        });
+    }
+    // every symbol of the group with its unit and whether it takes SI prefixes, which only the
+    // coherent unit's do, longest first so that a symbol that ends another, like `s` in `rad/s`,
+    // is only tried once the longer one didn't match
+    let mut symbols = group
+        .units
+        .iter()
+        .flat_map(|unit| {
+            unit.symbols
+                .iter()
+                .map(move |symbol| (symbol, &unit.name, unit.scale.is_none()))
+        })
+        .collect::<Vec<_>>();
+    symbols.sort_by_key(|(symbol, _, _)| std::cmp::Reverse(symbol.value().len()));
+
+    for unit in &group.units {
+        output.extend(unit_struct(dimension, unit));
+        output.extend(unit_from_str(&unit.name, &symbols));
+        for other in group.units.iter().filter(|other| other.name != unit.name) {
+            let struct_name = &unit.name;
+            let other_name = &other.name;
+            output.extend(quote! {
+                impl From<#other_name> for #struct_name {
+                    #[inline(always)]
+                    fn from(value: #other_name) -> Self {
+                        wpilib::math::units::dimension::Unit::to(value)
+                    }
+                }
+                impl From<&#other_name> for #struct_name {
+                    #[inline(always)]
+                    fn from(value: &#other_name) -> Self {
+                        wpilib::math::units::dimension::Unit::to(*value)
+                    }
+                }
+            });
+        }
+    }
+    output
+}
+
+/// Parses a number followed by the symbol of any unit in the group, with an SI prefix if it's the
+/// coherent unit's, converting it into this unit, or a bare number in this unit.
+fn unit_from_str(struct_name: &Ident, symbols: &[(&LitStr, &Ident, bool)]) -> TokenStream {
+    let symbol_names = symbols.iter().map(|(symbol, _, _)| symbol);
+    let units = symbols.iter().map(|(_, unit, _)| unit);
+    let prefixed = symbols.iter().map(|(_, _, prefixed)| prefixed);
+    quote! {
+        impl std::str::FromStr for #struct_name {
+            type Err = wpilib::math::units::parse::ParseUnitError;
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let s = s.trim();
+                #(
+                    if let Some(value) = wpilib::math::units::parse::parse_symbol(s, #symbol_names, #prefixed) {
+                        return Ok(wpilib::math::units::dimension::Unit::to(
+                            <#units as wpilib::math::units::dimension::Unit>::from_value(value?),
+                        ));
+                    }
+                )*
+                s.parse::<f64>()
+                    .map(wpilib::math::units::dimension::Unit::from_value)
+                    .map_err(|_| wpilib::math::units::parse::ParseUnitError::UnknownUnit(s.to_owned()))
+            }
+        }
+    }
+}
+
+#[allow(clippy::too_many_lines)]
+fn unit_struct(dimension: &Type, unit: &UnitDecl) -> TokenStream {
+    let struct_name = &unit.name;
+    let r#type = &unit.r#type;
+    let symbol = &unit.symbols[0];
+    let (scale, offset) = unit
+        .scale
+        .clone()
+        .unwrap_or_else(|| (syn::parse_quote!(1.0), syn::parse_quote!(0.0)));
+
+    let struct_item = quote! {
+        #[forbid(non_camel_case_types)]
+        #[derive(Clone, Copy)]
+        pub struct #struct_name {
+            pub(super) value: #r#type,
+        }
+    };
+
+    //impl debug and display for the struct, display formatting the value then the symbol
+    let impl_basic_block = quote! {
+        impl std::fmt::Debug for #struct_name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}({})", stringify!(#struct_name), self.value)
+            }
+        }
+        impl std::fmt::Display for #struct_name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                std::fmt::Display::fmt(&self.value, f)?;
+                write!(f, " {}", #symbol)
+            }
+        }
+        impl #struct_name {
+            #[inline(always)]
+            pub fn new(value: #r#type) -> Self {
+                Self { value }
+            }
+            #[inline(always)]
+            pub fn value(&self) -> #r#type {
+                self.value
+            }
+            #[inline(always)]
+            pub fn set(&mut self, value: #r#type) {
+                self.value = value;
+            }
+            #[inline(always)]
+            pub fn map(&self, f: impl FnOnce(#r#type) -> #r#type) -> Self {
+                Self {
+                    value: f(self.value),
+                }
+            }
+        }
+    };
+
+    //place the unit in its dimension
+    let impl_unit_block = quote! {
+        impl wpilib::math::units::dimension::Unit for #struct_name {
+            type Dimension = #dimension;
+            const SCALE: f64 = #scale;
+            const OFFSET: f64 = #offset;
+            const SYMBOL: &'static str = #symbol;
+            #[inline(always)]
+            fn from_value(value: f64) -> Self {
+                Self {
+                    value: value as #r#type,
+                }
+            }
+            #[inline(always)]
+            fn into_value(self) -> f64 {
+                self.value as f64
+            }
+        }
+    };
+
+    //add and subtract any unit of the same dimension, converting it into this one
+    let impl_add_sub_block = quote! {
+        impl<R: wpilib::math::units::dimension::Unit<Dimension = #dimension>> std::ops::Add<R> for #struct_name {
+            type Output = Self;
+            #[inline(always)]
+            fn add(self, rhs: R) -> Self::Output {
+                Self {
+                    value: self.value + wpilib::math::units::dimension::Unit::to::<Self>(rhs).value,
+                }
+            }
+        }
+        impl std::ops::Add<&#struct_name> for #struct_name {
+            type Output = Self;
+            #[inline(always)]
+            fn add(self, rhs: &#struct_name) -> Self::Output {
+                Self {
+                    value: self.value + rhs.value,
+                }
+            }
+        }
+        impl std::ops::Add<#struct_name> for &#struct_name {
+            type Output = #struct_name;
+            #[inline(always)]
+            fn add(self, rhs: #struct_name) -> Self::Output {
+                #struct_name {
+                    value: self.value + rhs.value,
+                }
+            }
+        }
+        impl std::ops::Add<&#struct_name> for &#struct_name {
+            type Output = #struct_name;
+            #[inline(always)]
+            fn add(self, rhs: &#struct_name) -> Self::Output {
+                #struct_name {
+                    value: self.value + rhs.value,
+                }
+            }
+        }
+        impl<R: wpilib::math::units::dimension::Unit<Dimension = #dimension>> std::ops::AddAssign<R> for #struct_name {
+            #[inline(always)]
+            fn add_assign(&mut self, rhs: R) {
+                self.value += wpilib::math::units::dimension::Unit::to::<Self>(rhs).value;
+            }
+        }
+        impl<R: wpilib::math::units::dimension::Unit<Dimension = #dimension>> std::ops::Sub<R> for #struct_name {
+            type Output = Self;
+            #[inline(always)]
+            fn sub(self, rhs: R) -> Self::Output {
+                Self {
+                    value: self.value - wpilib::math::units::dimension::Unit::to::<Self>(rhs).value,
+                }
+            }
+        }
+        impl std::ops::Sub<&#struct_name> for #struct_name {
+            type Output = Self;
+            #[inline(always)]
+            fn sub(self, rhs: &#struct_name) -> Self::Output {
+                Self {
+                    value: self.value - rhs.value,
+                }
+            }
+        }
+        impl std::ops::Sub<#struct_name> for &#struct_name {
+            type Output = #struct_name;
+            #[inline(always)]
+            fn sub(self, rhs: #struct_name) -> Self::Output {
+                #struct_name {
+                    value: self.value - rhs.value,
+                }
+            }
+        }
+        impl std::ops::Sub<&#struct_name> for &#struct_name {
+            type Output = #struct_name;
+            #[inline(always)]
+            fn sub(self, rhs: &#struct_name) -> Self::Output {
+                #struct_name {
+                    value: self.value - rhs.value,
+                }
+            }
+        }
+        impl<R: wpilib::math::units::dimension::Unit<Dimension = #dimension>> std::ops::SubAssign<R> for #struct_name {
+            #[inline(always)]
+            fn sub_assign(&mut self, rhs: R) {
+                self.value -= wpilib::math::units::dimension::Unit::to::<Self>(rhs).value;
+            }
+        }
+        impl std::ops::Rem for #struct_name {
+            type Output = Self;
+            #[inline(always)]
+            fn rem(self, rhs: Self) -> Self::Output {
+                Self {
+                    value: self.value % rhs.value,
+                }
+            }
+        }
+        impl std::ops::RemAssign for #struct_name {
+            #[inline(always)]
+            fn rem_assign(&mut self, rhs: Self) {
+                self.value %= rhs.value;
+            }
+        }
+    };
+
+    //multiply and divide by any unit, giving the coherent SI unit of the resulting dimension
+    let impl_mul_div_block = quote! {
+        impl<R: wpilib::math::units::dimension::Unit> std::ops::Mul<R> for #struct_name
+        where
+            #dimension: std::ops::Mul<R::Dimension>,
+            wpilib::re_exports::typenum::Prod<#dimension, R::Dimension>: wpilib::math::units::dimension::SiUnit,
+        {
+            type Output = <wpilib::re_exports::typenum::Prod<#dimension, R::Dimension> as wpilib::math::units::dimension::SiUnit>::Unit;
+            #[inline(always)]
+            fn mul(self, rhs: R) -> Self::Output {
+                wpilib::math::units::dimension::FromSi::from_si(
+                    wpilib::math::units::dimension::Unit::to_si(self)
+                        * wpilib::math::units::dimension::Unit::to_si(rhs),
+                )
+            }
+        }
+        impl<R: wpilib::math::units::dimension::Unit> std::ops::Div<R> for #struct_name
+        where
+            #dimension: std::ops::Div<R::Dimension>,
+            wpilib::re_exports::typenum::Quot<#dimension, R::Dimension>: wpilib::math::units::dimension::SiUnit,
+        {
+            type Output = <wpilib::re_exports::typenum::Quot<#dimension, R::Dimension> as wpilib::math::units::dimension::SiUnit>::Unit;
+            #[inline(always)]
+            fn div(self, rhs: R) -> Self::Output {
+                wpilib::math::units::dimension::FromSi::from_si(
+                    wpilib::math::units::dimension::Unit::to_si(self)
+                        / wpilib::math::units::dimension::Unit::to_si(rhs),
+                )
+            }
+        }
+        impl std::ops::Mul<f64> for #struct_name {
+            type Output = Self;
+            #[inline(always)]
+            fn mul(self, rhs: f64) -> Self::Output {
+                wpilib::math::units::dimension::Unit::from_value(
+                    wpilib::math::units::dimension::Unit::into_value(self) * rhs,
+                )
+            }
+        }
+        impl std::ops::Mul<#struct_name> for f64 {
+            type Output = #struct_name;
+            #[inline(always)]
+            fn mul(self, rhs: #struct_name) -> Self::Output {
+                rhs * self
+            }
+        }
+        impl std::ops::MulAssign<f64> for #struct_name {
+            #[inline(always)]
+            fn mul_assign(&mut self, rhs: f64) {
+                *self = *self * rhs;
+            }
+        }
+        impl std::ops::Div<f64> for #struct_name {
+            type Output = Self;
+            #[inline(always)]
+            fn div(self, rhs: f64) -> Self::Output {
+                wpilib::math::units::dimension::Unit::from_value(
+                    wpilib::math::units::dimension::Unit::into_value(self) / rhs,
+                )
+            }
+        }
+        impl std::ops::DivAssign<f64> for #struct_name {
+            #[inline(always)]
+            fn div_assign(&mut self, rhs: f64) {
+                *self = *self / rhs;
+            }
+        }
+    };
+
+    //implement num traits for the struct
+    let impl_num_traits_block = quote! {
+        impl wpilib::re_exports::num::Zero for #struct_name {
+            fn zero() -> Self {
+                Self {
+                    value: <#r#type as wpilib::re_exports::num::Zero>::zero(),
+                }
+            }
+            fn is_zero(&self) -> bool {
+                wpilib::re_exports::num::Zero::is_zero(&self.value)
+            }
+        }
+        impl wpilib::re_exports::num::ToPrimitive for #struct_name {
+            fn to_i64(&self) -> Option<i64> {
+                wpilib::re_exports::num::ToPrimitive::to_i64(&self.value)
+            }
+            fn to_u64(&self) -> Option<u64> {
+                wpilib::re_exports::num::ToPrimitive::to_u64(&self.value)
+            }
+            fn to_f64(&self) -> Option<f64> {
+                wpilib::re_exports::num::ToPrimitive::to_f64(&self.value)
+            }
+        }
+        impl wpilib::re_exports::num::FromPrimitive for #struct_name {
+            fn from_i64(n: i64) -> Option<Self> {
+                Some(Self {
+                    value: <#r#type as wpilib::re_exports::num::FromPrimitive>::from_i64(n)?,
+                })
+            }
+            fn from_u64(n: u64) -> Option<Self> {
+                Some(Self {
+                    value: <#r#type as wpilib::re_exports::num::FromPrimitive>::from_u64(n)?,
+                })
+            }
+            fn from_f64(n: f64) -> Option<Self> {
+                Some(Self {
+                    value: <#r#type as wpilib::re_exports::num::FromPrimitive>::from_f64(n)?,
+                })
+            }
+        }
+    };
+
+    //implement into and from for its type, the other primitives and prefixed measures of the
+    //dimension
+    let primitive_types = [
+        "f64", "f32", "u64", "u32", "u16", "u8", "i64", "i32", "i16", "i8",
+    ]
+    .map(|primitive| Ident::new(primitive, struct_name.span()));
+    let impl_into_from_block = quote! {
+        impl From<#struct_name> for #r#type {
+            #[inline(always)]
+            fn from(value: #struct_name) -> #r#type {
+                value.value
+            }
+        }
+        impl<U: wpilib::math::units::dimension::Unit<Dimension = #dimension>, const PREFIX: i8> From<wpilib::math::units::measure::Measure<U, PREFIX>> for #struct_name {
+            #[inline(always)]
+            fn from(value: wpilib::math::units::measure::Measure<U, PREFIX>) -> Self {
+                wpilib::math::units::dimension::Unit::to(value)
+            }
+        }
+        impl<U: wpilib::math::units::dimension::Unit<Dimension = #dimension>, const PREFIX: i8> From<#struct_name> for wpilib::math::units::measure::Measure<U, PREFIX> {
+            #[inline(always)]
+            fn from(value: #struct_name) -> Self {
+                wpilib::math::units::dimension::Unit::to(value)
+            }
+        }
+        impl From<&#struct_name> for #r#type {
+            #[inline(always)]
+            fn from(value: &#struct_name) -> #r#type {
+                value.value
+            }
+        }
+        #(
+            impl From<#primitive_types> for #struct_name {
+                #[inline(always)]
+                fn from(value: #primitive_types) -> Self {
+                    Self {
+                        value: value as #r#type,
+                    }
+                }
+            }
+            impl From<&#primitive_types> for #struct_name {
+                #[inline(always)]
+                fn from(value: &#primitive_types) -> Self {
+                    Self {
+                        value: *value as #r#type,
+                    }
+                }
+            }
+        )*
+    };
+
+    //implement serde for the struct, as a bare number or, from human readable formats, also as
+    //a string with a symbol
+    let impl_serde_block = quote! {
+        impl wpilib::re_exports::serde::Serialize for #struct_name {
+            fn serialize<S: wpilib::re_exports::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                wpilib::re_exports::serde::Serialize::serialize(&self.value, serializer)
+            }
+        }
+        impl<'de> wpilib::re_exports::serde::Deserialize<'de> for #struct_name {
+            fn deserialize<D: wpilib::re_exports::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                if deserializer.is_human_readable() {
+                    deserializer.deserialize_any(wpilib::math::units::parse::UnitVisitor::<Self>::new())
+                } else {
+                    <#r#type as wpilib::re_exports::serde::Deserialize>::deserialize(deserializer).map(|value| Self { value })
+                }
+            }
+        }
+    };
+
+    //compare with any unit of the same dimension
+    let impl_partial_eq_block = quote! {
+        impl<R: wpilib::math::units::dimension::Unit<Dimension = #dimension>> std::cmp::PartialEq<R> for #struct_name {
+            fn eq(&self, other: &R) -> bool {
+                self.value == wpilib::math::units::dimension::Unit::to::<Self>(*other).value
+            }
+        }
+        impl<R: wpilib::math::units::dimension::Unit<Dimension = #dimension>> std::cmp::PartialOrd<R> for #struct_name {
+            fn partial_cmp(&self, other: &R) -> Option<std::cmp::Ordering> {
+                self.value.partial_cmp(&wpilib::math::units::dimension::Unit::to::<Self>(*other).value)
+            }
+        }
+    };
+
+    let impl_negative_block = quote! {
+        impl std::ops::Neg for #struct_name {
+            type Output = Self;
+            fn neg(self) -> Self::Output {
+                Self {
+                    value: -self.value,
+                }
+            }
+        }
+    };
+
+    let impl_simd_block = quote! {
+        impl wpilib::re_exports::nalgebra::SimdValue for #struct_name {
+            type Element = #struct_name;
+            type SimdBool = bool;
+
+            #[inline]
+            fn lanes() -> usize {
+                1
+            }
+            #[inline]
+            fn splat(val: Self::Element) -> Self {
+                val
+            }
+            #[inline]
+            fn extract(&self, _: usize) -> Self::Element {
+                *self
+            }
+            #[inline]
+            unsafe fn extract_unchecked(&self, _: usize) -> Self::Element {
+                *self
+            }
+            #[inline]
+            fn replace(&mut self, _: usize, val: Self::Element) {
+                self.value = val.value
+            }
+            #[inline]
+            unsafe fn replace_unchecked(&mut self, _: usize, val: Self::Element) {
+                self.value = val.value
+            }
+            #[inline]
+            fn select(self, cond: Self::SimdBool, other: Self) -> Self {
+                if cond {
+                    self
+                } else {
+                    other
+                }
+            }
+            #[inline]
+            fn map_lanes(self, f: impl Fn(Self::Element) -> Self::Element) -> Self
+                where
+                    Self: Clone, {
+                f(self)
+            }
+            #[inline]
+            fn zip_map_lanes(
+                    self,
+                    b: Self,
+                    f: impl Fn(Self::Element, Self::Element) -> Self::Element,
+                ) -> Self
+                where
+                    Self: Clone, {
+                f(self, b)
+            }
+        }
+    };
+
+    //compare approximately and cast to and from floats, so the unit can be stored in nalgebra
+    //matrices and added within them; it isn't a field, so products and decompositions need the
+    //matrix cast to floats first
+    let impl_float_block = quote! {
+        impl wpilib::re_exports::approx::AbsDiffEq for #struct_name {
+            type Epsilon = #r#type;
+            fn default_epsilon() -> Self::Epsilon {
+                <#r#type as wpilib::re_exports::approx::AbsDiffEq>::default_epsilon()
+            }
+            fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+                wpilib::re_exports::approx::AbsDiffEq::abs_diff_eq(&self.value, &other.value, epsilon)
+            }
+        }
+        impl wpilib::re_exports::approx::RelativeEq for #struct_name {
+            fn default_max_relative() -> Self::Epsilon {
+                <#r#type as wpilib::re_exports::approx::RelativeEq>::default_max_relative()
+            }
+            fn relative_eq(&self, other: &Self, epsilon: Self::Epsilon, max_relative: Self::Epsilon) -> bool {
+                wpilib::re_exports::approx::RelativeEq::relative_eq(&self.value, &other.value, epsilon, max_relative)
+            }
+        }
+        impl wpilib::re_exports::approx::UlpsEq for #struct_name {
+            fn default_max_ulps() -> u32 {
+                <#r#type as wpilib::re_exports::approx::UlpsEq>::default_max_ulps()
+            }
+            fn ulps_eq(&self, other: &Self, epsilon: Self::Epsilon, max_ulps: u32) -> bool {
+                wpilib::re_exports::approx::UlpsEq::ulps_eq(&self.value, &other.value, epsilon, max_ulps)
+            }
+        }
+        impl wpilib::re_exports::simba::scalar::SubsetOf<#r#type> for #struct_name {
+            fn to_superset(&self) -> #r#type {
+                self.value
+            }
+            fn from_superset_unchecked(element: &#r#type) -> Self {
+                Self { value: *element }
+            }
+            fn is_in_subset(_: &#r#type) -> bool {
+                true
+            }
+        }
+        impl wpilib::re_exports::simba::scalar::SubsetOf<#struct_name> for #r#type {
+            fn to_superset(&self) -> #struct_name {
+                #struct_name { value: *self }
+            }
+            fn from_superset_unchecked(element: &#struct_name) -> Self {
+                element.value
+            }
+            fn is_in_subset(_: &#struct_name) -> bool {
+                true
+            }
+        }
+    };
+
+    let mut output = struct_item;
+    output.extend(impl_basic_block);
+    output.extend(impl_unit_block);
+    output.extend(impl_add_sub_block);
+    output.extend(impl_mul_div_block);
+    output.extend(impl_num_traits_block);
+    output.extend(impl_into_from_block);
+    output.extend(impl_serde_block);
+    output.extend(impl_partial_eq_block);
+    let type_str = r#type.to_string();
+    if !type_str.contains('u') {
+        output.extend(impl_negative_block);
+    }
+    if type_str.contains("f64") || type_str.contains("f32") {
+        output.extend(impl_simd_block);
+        output.extend(impl_float_block);
+    }
+    output
+}
@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+use crate::math::geometry::Pose3d;
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AprilTag {
+    #[serde(rename = "ID")]
+    pub id: i32,
+    pub pose: Pose3d,
+}
+
+impl AprilTag {
+    #[must_use]
+    pub const fn new(id: i32, pose: Pose3d) -> Self {
+        Self { id, pose }
+    }
+}
@@ -0,0 +1,148 @@
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::math::geometry::{Pose3d, Rotation3d, Translation3d};
+use crate::math::units::distance::Meter;
+
+use super::{AprilTag, AprilTagFields};
+
+#[derive(Debug, Error)]
+pub enum AprilTagFieldLayoutError {
+    #[error("failed to read field layout: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("failed to parse field layout: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("no bundled field layout named {0:?}")]
+    UnknownField(String),
+}
+
+/// Which corner of the field tag poses are measured from.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum OriginPosition {
+    /// The right corner of the blue alliance wall, as seen from behind it. Layouts are
+    /// published relative to this origin.
+    BlueAllianceWallRightSide,
+    /// The right corner of the red alliance wall, as seen from behind it.
+    RedAllianceWallRightSide,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct FieldDimensions {
+    length: Meter,
+    width: Meter,
+}
+
+/// The poses of the tags on a field, in the JSON format official layouts are published in.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AprilTagFieldLayout {
+    tags: Vec<AprilTag>,
+    field: FieldDimensions,
+    #[serde(skip)]
+    origin: Pose3d,
+}
+
+impl AprilTagFieldLayout {
+    #[must_use]
+    pub fn new(
+        tags: Vec<AprilTag>,
+        field_length: impl Into<Meter>,
+        field_width: impl Into<Meter>,
+    ) -> Self {
+        Self {
+            tags,
+            field: FieldDimensions {
+                length: field_length.into(),
+                width: field_width.into(),
+            },
+            origin: Pose3d::default(),
+        }
+    }
+
+    /// Loads one of the bundled official layouts.
+    ///
+    /// # Panics
+    /// Never for the bundled layouts; each one is parsed by the tests.
+    #[must_use]
+    pub fn load_field(field: AprilTagFields) -> Self {
+        match Self::from_json(field.json()) {
+            Ok(layout) => layout,
+            Err(error) => panic!("bundled field layout {field} is invalid: {error}"),
+        }
+    }
+
+    /// Loads a bundled layout by the name it is published under, e.g. `2024-crescendo`.
+    ///
+    /// # Errors
+    /// Returns [`AprilTagFieldLayoutError::UnknownField`] if no bundled layout has that name.
+    pub fn load_named(name: &str) -> Result<Self, AprilTagFieldLayoutError> {
+        Ok(Self::load_field(name.parse()?))
+    }
+
+    /// # Errors
+    /// Returns an error if the file can't be read or isn't a valid layout.
+    pub fn load_from_path(path: impl AsRef<Path>) -> Result<Self, AprilTagFieldLayoutError> {
+        Self::from_json(&fs::read_to_string(path)?)
+    }
+
+    /// # Errors
+    /// Returns an error if `json` isn't a valid layout.
+    pub fn from_json(json: &str) -> Result<Self, AprilTagFieldLayoutError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Serializes the layout relative to the blue alliance origin, regardless of the current origin.
+    ///
+    /// # Errors
+    /// Returns an error if serialization fails.
+    pub fn to_json(&self) -> Result<String, AprilTagFieldLayoutError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    #[must_use]
+    pub fn get_tags(&self) -> &[AprilTag] {
+        &self.tags
+    }
+
+    #[must_use]
+    pub const fn get_field_length(&self) -> Meter {
+        self.field.length
+    }
+
+    #[must_use]
+    pub const fn get_field_width(&self) -> Meter {
+        self.field.width
+    }
+
+    /// Measures tag poses from one of the alliance wall corners.
+    pub fn set_origin(&mut self, origin: OriginPosition) {
+        self.origin = match origin {
+            OriginPosition::BlueAllianceWallRightSide => Pose3d::default(),
+            OriginPosition::RedAllianceWallRightSide => Pose3d::new_trans_rot(
+                Translation3d::new(self.field.length, self.field.width, 0.0),
+                Rotation3d::new(0.0, 0.0, std::f64::consts::PI),
+            ),
+        };
+    }
+
+    /// Measures tag poses from an arbitrary origin, given relative to the blue alliance origin.
+    pub const fn set_origin_pose(&mut self, origin: Pose3d) {
+        self.origin = origin;
+    }
+
+    #[must_use]
+    pub const fn get_origin(&self) -> Pose3d {
+        self.origin
+    }
+
+    /// Returns the pose of the tag with the given ID relative to the current origin.
+    #[must_use]
+    pub fn get_tag_pose(&self, id: i32) -> Option<Pose3d> {
+        self.tags
+            .iter()
+            .find(|tag| tag.id == id)
+            .map(|tag| tag.pose.relative_to(&self.origin))
+    }
+}
@@ -0,0 +1,67 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::math::util::alliance_flip_util::FieldSymmetry;
+
+use super::AprilTagFieldLayoutError;
+
+/// The official field layouts bundled with the library.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum AprilTagFields {
+    ChargedUp2023,
+    Crescendo2024,
+}
+
+impl AprilTagFields {
+    pub const ALL: [Self; 2] = [Self::ChargedUp2023, Self::Crescendo2024];
+
+    /// Returns the name the layout is published under, e.g. `2024-crescendo`.
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::ChargedUp2023 => "2023-chargedup",
+            Self::Crescendo2024 => "2024-crescendo",
+        }
+    }
+
+    /// Returns how the red half of the field relates to the blue half.
+    #[must_use]
+    pub const fn symmetry(&self) -> FieldSymmetry {
+        match self {
+            Self::ChargedUp2023 | Self::Crescendo2024 => FieldSymmetry::Mirrored,
+        }
+    }
+
+    pub(super) const fn json(self) -> &'static str {
+        match self {
+            Self::ChargedUp2023 => include_str!("layouts/2023-chargedup.json"),
+            Self::Crescendo2024 => include_str!("layouts/2024-crescendo.json"),
+        }
+    }
+}
+
+/// The layout for the most recent season.
+impl Default for AprilTagFields {
+    fn default() -> Self {
+        Self::Crescendo2024
+    }
+}
+
+impl fmt::Display for AprilTagFields {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Parses a layout name, with or without a `.json` extension.
+impl FromStr for AprilTagFields {
+    type Err = AprilTagFieldLayoutError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let stem = name.strip_suffix(".json").unwrap_or(name);
+        Self::ALL
+            .into_iter()
+            .find(|field| field.name().eq_ignore_ascii_case(stem))
+            .ok_or_else(|| AprilTagFieldLayoutError::UnknownField(name.to_owned()))
+    }
+}
@@ -0,0 +1,8 @@
+mod april_tag;
+mod april_tag_field_layout;
+mod april_tag_fields;
+mod test;
+
+pub use april_tag::*;
+pub use april_tag_field_layout::*;
+pub use april_tag_fields::*;
@@ -0,0 +1,98 @@
+use crate::apriltag::{
+    AprilTag, AprilTagFieldLayout, AprilTagFieldLayoutError, AprilTagFields, OriginPosition,
+};
+use crate::math::geometry::{Pose3d, Rotation3d};
+use crate::math::units::angle::Degree;
+use approx::assert_relative_eq;
+
+#[test]
+fn bundled_layouts() {
+    for field in AprilTagFields::ALL {
+        let layout = AprilTagFieldLayout::load_field(field);
+        assert!(!layout.get_tags().is_empty(), "{field} has no tags");
+        assert_eq!(
+            field
+                .name()
+                .parse::<AprilTagFields>()
+                .expect("bundled name parses"),
+            field
+        );
+    }
+
+    let layout = AprilTagFieldLayout::load_named("2024-crescendo.json").expect("layout is bundled");
+    assert_eq!(layout.get_tags().len(), 16);
+    assert_relative_eq!(layout.get_field_length().value(), 16.541);
+    assert_relative_eq!(layout.get_field_width().value(), 8.211);
+
+    let speaker = layout.get_tag_pose(7).expect("tag 7 exists");
+    assert_relative_eq!(speaker.translation.x.value(), -0.0381, epsilon = 1e-9);
+    assert_relative_eq!(speaker.translation.y.value(), 5.547_868, epsilon = 1e-9);
+    assert_eq!(layout.get_tag_pose(17), None);
+
+    assert!(matches!(
+        AprilTagFieldLayout::load_named("1999-unknown"),
+        Err(AprilTagFieldLayoutError::UnknownField(_))
+    ));
+}
+
+#[test]
+fn red_origin() {
+    let mut layout = AprilTagFieldLayout::new(
+        vec![AprilTag::new(
+            1,
+            Pose3d::new_xyz_rot(1.0, 2.0, 0.5, Rotation3d::new(0.0, 0.0, Degree::new(30.0))),
+        )],
+        10.0,
+        5.0,
+    );
+
+    layout.set_origin(OriginPosition::RedAllianceWallRightSide);
+    let pose = layout.get_tag_pose(1).expect("tag 1 exists");
+    assert_relative_eq!(pose.translation.x.value(), 9.0, epsilon = 1e-9);
+    assert_relative_eq!(pose.translation.y.value(), 3.0, epsilon = 1e-9);
+    assert_relative_eq!(pose.translation.z.value(), 0.5, epsilon = 1e-9);
+    assert_relative_eq!(
+        Degree::from(pose.rotation.get_z()).value(),
+        -150.0,
+        epsilon = 1e-9
+    );
+
+    layout.set_origin(OriginPosition::BlueAllianceWallRightSide);
+    assert_eq!(layout.get_tag_pose(1), Some(layout.get_tags()[0].pose));
+}
+
+#[test]
+fn json_round_trip() {
+    let layout = AprilTagFieldLayout::load_field(AprilTagFields::ChargedUp2023);
+    let json = layout.to_json().expect("layout serializes");
+    assert_eq!(
+        AprilTagFieldLayout::from_json(&json).expect("layout deserializes"),
+        layout
+    );
+
+    let layout = AprilTagFieldLayout::from_json(
+        r#"{
+            "tags": [{
+                "ID": 3,
+                "pose": {
+                    "translation": {"x": 1.0, "y": 2.0, "z": 3.0},
+                    "rotation": {"quaternion": {"W": 0.0, "X": 0.0, "Y": 0.0, "Z": 1.0}}
+                }
+            }],
+            "field": {"length": 16.0, "width": 8.0}
+        }"#,
+    )
+    .expect("layout deserializes");
+    let pose = layout.get_tag_pose(3).expect("tag 3 exists");
+    assert_relative_eq!(pose.translation.z.value(), 3.0);
+    assert_relative_eq!(
+        Degree::from(pose.rotation.get_z()).value().abs(),
+        180.0,
+        epsilon = 1e-9
+    );
+
+    assert!(matches!(
+        AprilTagFieldLayout::from_json("{}"),
+        Err(AprilTagFieldLayoutError::Json(_))
+    ));
+}
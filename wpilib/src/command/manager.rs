@@ -8,6 +8,7 @@ use once_cell::sync::Lazy;
 use parking_lot::Mutex;
 
 use super::{commands::CommandTrait, Command};
+use crate::networktables::NetworkTable;
 
 static MANAGER: Mutex<Lazy<CommandManager>> = Mutex::new(Lazy::new(CommandManager::new));
 
@@ -15,6 +16,16 @@ type SubsystemSUID = u8;
 
 pub trait Subsystem {
     fn periodic(&self) {}
+
+    /// The name the subsystem's telemetry is published under, its type's name by default.
+    fn get_name(&self) -> String {
+        let name = std::any::type_name::<Self>();
+        name.rsplit("::").next().unwrap_or(name).to_owned()
+    }
+
+    /// Publishes the subsystem's state to its own table each time the scheduler's telemetry is
+    /// updated.
+    fn telemetry(&self, _table: &NetworkTable) {}
 }
 
 type SubsystemArc = Arc<Mutex<dyn Subsystem + Sync + Send>>;
@@ -261,6 +272,67 @@ impl CommandManager {
         scheduler.orphaned_commands.clear();
     }
 
+    /// Returns the names of the commands that are running, default commands included, sorted.
+    pub fn get_running_names() -> Vec<String> {
+        let scheduler = MANAGER.lock();
+        let running = scheduler
+            .requirements
+            .values()
+            .chain(scheduler.orphaned_commands.iter())
+            .collect::<HashSet<_>>();
+        let mut names = running
+            .into_iter()
+            .filter_map(|index| scheduler.get_command(*index))
+            .map(CommandTrait::get_name)
+            .collect::<Vec<_>>();
+        drop(scheduler);
+        names.sort();
+        names
+    }
+
+    /// Publishes each subsystem's telemetry and the name of the command requiring it, or an
+    /// empty string, to a table named after it under `table`.
+    pub fn publish_subsystems(table: &NetworkTable) {
+        // the subsystems are user code, which may use the manager, so they're only called once
+        // it's unlocked
+        let scheduler = MANAGER.lock();
+        let subsystems = scheduler
+            .subsystem_to_default
+            .iter()
+            .filter_map(|(suid, default)| {
+                let CommandIndex::DefaultCommand(idx) = default else {
+                    return None;
+                };
+                let command = scheduler
+                    .requirements
+                    .get(suid)
+                    .and_then(|index| scheduler.get_command(*index))
+                    .map(CommandTrait::get_name)
+                    .unwrap_or_default();
+                Some((scheduler.periodic_callbacks[*idx].clone(), command))
+            })
+            .collect::<Vec<_>>();
+        drop(scheduler);
+
+        for (subsystem, command) in subsystems {
+            let subsystem = subsystem.lock();
+            let subsystem_table = table.get_sub_table(&subsystem.get_name());
+            if let Err(err) = subsystem_table.put("Command", command) {
+                tracing::warn!("Failed to publish subsystem telemetry: {err}");
+            }
+            subsystem.telemetry(&subsystem_table);
+            drop(subsystem);
+        }
+    }
+
+    fn get_command(&self, index: CommandIndex) -> Option<&Command> {
+        match index {
+            CommandIndex::Command(idx) => self.commands.get(idx),
+            CommandIndex::DefaultCommand(idx) => self.default_commands.get(idx),
+        }
+        .and_then(Option::as_ref)
+    }
+
     pub fn add_cond_scheduler(scheduler: ConditionalScheduler) {
         let mut manager = MANAGER.lock();
         manager.cond_schedulers.push(scheduler);
@@ -13,6 +13,8 @@ use crate::{
     crate_namespace,
 };
 
+use crate::networktables::{NetworkTable, NetworkTableInstance};
+
 use super::{
     commands::CommandBuilder,
     manager::{Condition, ConditionResponse},
@@ -103,6 +105,11 @@ impl Subsystem for TestSubsystem {
     fn periodic(&self) {
         println!("Periodic");
     }
+
+    fn telemetry(&self, table: &NetworkTable) {
+        // reading the manager from here deadlocks if it's still locked while publishing
+        let _ = table.put("Running", CommandManager::get_running_names());
+    }
 }
 
 fn test_command() {
@@ -139,6 +146,19 @@ fn test_subsystem() {
     assert!(instance.0.lock().is_default_running());
 }
 
+fn test_publish_subsystems() {
+    CommandManager::clear_cond_schedulers();
+    CommandManager::cancel_all();
+    let _instance = register_subsystem!(TestSubsystem);
+    CommandManager::run();
+
+    let table = NetworkTableInstance::new().get_table("/Subsystems");
+    CommandManager::publish_subsystems(&table);
+    let subsystem = table.get_sub_table("TestSubsystem");
+    assert!(subsystem.get::<String>("Command").is_some());
+    assert!(subsystem.get::<Vec<String>>("Running").is_some());
+}
+
 struct Immediately {}
 
 impl Condition for Immediately {
@@ -193,6 +213,11 @@ fn subsystem() {
     run_in_clean_state(test_subsystem);
 }
 
+#[test]
+fn publish_subsystems() {
+    run_in_clean_state(test_publish_subsystems);
+}
+
 #[test]
 fn on_true() {
     run_in_clean_state(test_on_true);
@@ -30,22 +30,27 @@ extern crate wpilib_macros;
 
 use robots::UserRobot;
 
+pub mod apriltag;
 #[cfg(feature = "command")]
 pub mod command;
 pub mod math;
+pub mod networktables;
 pub mod robots;
+pub mod sysid;
 #[macro_use]
 pub mod macros;
 
 //re-exports for macros
 pub use parking_lot::Mutex as WpiMutex;
 pub mod re_exports {
+    pub use approx;
     pub use nalgebra;
     pub use num;
     pub use once_cell;
     pub use parking_lot;
     pub use serde;
     pub use simba;
+    pub use typenum;
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -1,50 +1,59 @@
-use crate::math::controllers::controller::Controller;
-use crate::math::units::time::Millisecond;
+use crate::math::controllers::controller::{Controller, ControllerUnit};
+use crate::math::units::time::Second;
 
 #[derive(Debug, Clone, Copy)]
-pub struct BangBangController {
-    pub min_input: f64,
-    pub max_input: f64,
-    pub min_output: f64,
-    pub max_output: f64,
-    pub set_point: f64,
-    pub tolerance: f64,
+pub struct BangBangController<Input: ControllerUnit = f64, Output: ControllerUnit = f64> {
+    pub min_input: Input,
+    pub max_input: Input,
+    pub min_output: Output,
+    pub max_output: Output,
+    pub set_point: Input,
+    pub tolerance: Input,
     pub enabled: bool,
 }
 
-impl BangBangController {
+impl<Input: ControllerUnit, Output: ControllerUnit> BangBangController<Input, Output> {
     #[must_use]
-    pub const fn new() -> Self {
+    pub fn new() -> Self {
         Self {
-            min_input: -1.0,
-            max_input: 1.0,
-            min_output: -1.0,
-            max_output: 1.0,
-            set_point: 0.0,
-            tolerance: 0.0,
+            min_input: Input::from(-1.0),
+            max_input: Input::from(1.0),
+            min_output: Output::from(-1.0),
+            max_output: Output::from(1.0),
+            set_point: Input::from(0.0),
+            tolerance: Input::from(0.0),
             enabled: true,
         }
     }
 
-    pub fn set_tolerance(&mut self, tolerance: f64) {
-        self.tolerance = tolerance;
+    pub fn set_tolerance(&mut self, tolerance: impl Into<Input>) {
+        self.tolerance = tolerance.into();
     }
 }
 
-impl Controller for BangBangController {
-    fn calculate(&mut self, measurement: f64, _period: impl Into<Millisecond>) -> f64 {
+impl<Input: ControllerUnit, Output: ControllerUnit> Default for BangBangController<Input, Output> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Input: ControllerUnit, Output: ControllerUnit> Controller<Input, Output>
+    for BangBangController<Input, Output>
+{
+    fn calculate(&mut self, measurement: impl Into<Input>, _period: impl Into<Second>) -> Output {
         if !self.enabled {
-            return 0.0;
+            return Output::from(0.0);
         }
-        if measurement.clamp(self.min_input, self.max_input) < self.set_point {
+        let measurement: f64 = measurement.into().into();
+        if measurement.clamp(self.min_input.into(), self.max_input.into()) < self.set_point.into() {
             self.max_output
         } else {
             self.min_output
         }
     }
 
-    fn set_set_point(&mut self, set_point: f64) {
-        self.set_point = set_point;
+    fn set_set_point(&mut self, set_point: impl Into<Input>) {
+        self.set_point = set_point.into();
     }
 
     fn set_enabled(&mut self, enabled: bool) {
@@ -55,18 +64,24 @@ impl Controller for BangBangController {
         self.enabled
     }
 
-    fn get_set_point(&self) -> f64 {
+    fn get_set_point(&self) -> Input {
         self.set_point
     }
 
-    fn set_limits(&mut self, min_input: f64, max_input: f64, min_output: f64, max_output: f64) {
-        self.min_input = min_input;
-        self.max_input = max_input;
-        self.min_output = min_output;
-        self.max_output = max_output;
+    fn set_limits(
+        &mut self,
+        min_input: impl Into<Input>,
+        max_input: impl Into<Input>,
+        min_output: impl Into<Output>,
+        max_output: impl Into<Output>,
+    ) {
+        self.min_input = min_input.into();
+        self.max_input = max_input.into();
+        self.min_output = min_output.into();
+        self.max_output = max_output.into();
     }
 
-    fn get_limits(&self) -> (f64, f64, f64, f64) {
+    fn get_limits(&self) -> (Input, Input, Output, Output) {
         (
             self.min_input,
             self.max_input,
@@ -76,6 +91,6 @@ impl Controller for BangBangController {
     }
 
     fn reset(&mut self) {
-        self.set_point = 0.0;
+        self.set_point = Input::from(0.0);
     }
 }
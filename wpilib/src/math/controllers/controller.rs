@@ -1,21 +1,62 @@
-use crate::math::units::time::Millisecond;
+use std::fmt::Debug;
+use std::ops::Div;
 
-pub trait Controller {
+use crate::math::units::time::Second;
+
+/// A quantity a controller measures or outputs, such as a unit from
+/// [`units`](crate::math::units) or a plain `f64` for unitless values.
+pub trait ControllerUnit: Copy + From<f64> + Into<f64> {}
+
+impl<T: Copy + From<f64> + Into<f64>> ControllerUnit for T {}
+
+/// A [`ControllerUnit`] whose rate of change has a unit too.
+///
+/// A [`Radian`](crate::math::units::angle::Radian) changes in
+/// [`RadianPerSecond`](crate::math::units::angular_velocity::RadianPerSecond), and a plain `f64`
+/// in [`Hertz`](crate::math::units::frequency::Hertz).
+pub trait ControllerInput: ControllerUnit {
+    type PerSecond: ControllerUnit + Debug;
+
+    /// Returns the rate of changing by this much every second.
+    fn into_rate(self) -> Self::PerSecond;
+}
+
+impl<T: ControllerUnit + Div<Second>> ControllerInput for T
+where
+    T::Output: ControllerUnit + Debug,
+{
+    type PerSecond = T::Output;
+
+    fn into_rate(self) -> Self::PerSecond {
+        self / Second::new(1.0)
+    }
+}
+
+/// A feedback controller that measures an `Input`, such as a
+/// [`Radian`](crate::math::units::angle::Radian), and produces an `Output`, such as a
+/// [`Volt`](crate::math::units::energy::Volt).
+pub trait Controller<Input: ControllerUnit, Output: ControllerUnit> {
     /// Returns the control output.
-    fn calculate(&mut self, measurement: f64, period: impl Into<Millisecond>) -> f64;
+    fn calculate(&mut self, measurement: impl Into<Input>, period: impl Into<Second>) -> Output;
     /// Sets the set point.
-    fn set_set_point(&mut self, set_point: f64);
+    fn set_set_point(&mut self, set_point: impl Into<Input>);
     /// Enables or disables the controller.
     fn set_enabled(&mut self, enabled: bool);
     /// Returns whether the controller is enabled.
     fn get_enabled(&self) -> bool;
     /// Returns the set point.
-    fn get_set_point(&self) -> f64;
+    fn get_set_point(&self) -> Input;
     /// Sets the input and output limits.
-    fn set_limits(&mut self, min_input: f64, max_input: f64, min_output: f64, max_output: f64);
+    fn set_limits(
+        &mut self,
+        min_input: impl Into<Input>,
+        max_input: impl Into<Input>,
+        min_output: impl Into<Output>,
+        max_output: impl Into<Output>,
+    );
     /// Gets the input and output limits.
     /// in the order min_input, max_input, min_output, max_output
-    fn get_limits(&self) -> (f64, f64, f64, f64);
+    fn get_limits(&self) -> (Input, Input, Output, Output);
     /// Resets the controller.
     fn reset(&mut self);
 }
@@ -1,26 +1,73 @@
+//! Feedforwards for motors driving a velocity, with friction and, for elevators and arms,
+//! gravity.
+//!
+//! Each has a `calculate` that takes the velocity now and the velocity wanted after `dt`, which is
+//! exact for the discretized motor model rather than assuming constant acceleration over `dt`.
+
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+use nalgebra::Vector1;
+
+use crate::math::controllers::controller::ControllerUnit;
+use crate::math::controllers::linear_plant_inversion_feedforward::LinearPlantInversionFeedforward;
+use crate::math::system::LinearSystemId;
 use crate::math::units::angle::Radian;
 use crate::math::units::angular_acceleration::RadianPerSecondSquared;
 use crate::math::units::angular_velocity::RadianPerSecond;
+use crate::math::units::distance::Meter;
 use crate::math::units::energy::Volt;
+use crate::math::units::linear_acceleration::MeterPerSecondSquared;
+use crate::math::units::linear_velocity::MeterPerSecond;
+use crate::math::units::time::Second;
+
+/// The velocity unit a feedforward's gains are identified in.
+///
+/// It's paired with the matching acceleration unit, so `k_v` is in volts per `Self` and `k_a` in
+/// volts per [`Acceleration`](Self::Acceleration), and with the [`Position`](Self::Position)
+/// unit it's the rate of.
+pub trait VelocityUnit: ControllerUnit + Debug + PartialEq {
+    type Acceleration: ControllerUnit;
+    type Position: ControllerUnit + Debug + PartialEq;
+}
 
+impl VelocityUnit for RadianPerSecond {
+    type Acceleration = RadianPerSecondSquared;
+    type Position = Radian;
+}
+
+impl VelocityUnit for MeterPerSecond {
+    type Acceleration = MeterPerSecondSquared;
+    type Position = Meter;
+}
+
+/// A feedforward for a motor with no load beyond friction, such as a flywheel or a drivetrain
+/// side.
 #[derive(Debug, Clone, Copy)]
-pub struct Simple {
+pub struct Simple<Velocity: VelocityUnit = RadianPerSecond> {
     k_s: f64,
     k_v: f64,
     k_a: f64,
+    unit: PhantomData<Velocity>,
 }
+/// A feedforward with no friction or gravity term.
 #[derive(Debug, Clone, Copy)]
-pub struct Static {
+pub struct Static<Velocity: VelocityUnit = RadianPerSecond> {
     k_v: f64,
     k_a: f64,
+    unit: PhantomData<Velocity>,
 }
+/// A feedforward for a mechanism moving against constant gravity, such as an elevator.
 #[derive(Debug, Clone, Copy)]
-pub struct Elevator {
+pub struct Elevator<Velocity: VelocityUnit = MeterPerSecond> {
     k_s: f64,
     k_g: f64,
     k_v: f64,
     k_a: f64,
+    unit: PhantomData<Velocity>,
 }
+/// A feedforward for an arm whose gravity load varies with the cosine of its angle from
+/// horizontal.
 #[derive(Debug, Clone, Copy)]
 pub struct Arm {
     k_s: f64,
@@ -29,178 +76,366 @@ pub struct Arm {
     k_a: f64,
 }
 
-impl Simple {
+/// Returns the direction friction opposes at `velocity`, which is none at rest.
+fn signum(velocity: f64) -> f64 {
+    if velocity == 0.0 {
+        0.0
+    } else {
+        velocity.signum()
+    }
+}
+
+/// Returns the voltage that moves a velocity system with the given gains from `current_velocity`
+/// to `next_velocity` in exactly `dt`, by inverting the discretized plant. Friction opposes the
+/// current velocity; with no `k_a` the velocity changes instantly, so it opposes the next one.
+fn discrete_calculate(
+    k_s: f64,
+    k_v: f64,
+    k_a: f64,
+    current_velocity: f64,
+    next_velocity: f64,
+    dt: Second,
+) -> f64 {
+    if k_a == 0.0 {
+        return k_s.mul_add(signum(next_velocity), k_v * next_velocity);
+    }
+    let plant = LinearSystemId::identify_velocity_system(k_v, k_a);
+    let mut feedforward = LinearPlantInversionFeedforward::new(&plant, dt);
+    let u = feedforward.calculate_with_r(
+        &Vector1::new(current_velocity),
+        &Vector1::new(next_velocity),
+    );
+    k_s.mul_add(signum(current_velocity), u[0])
+}
+
+impl<Velocity: VelocityUnit> Simple<Velocity> {
     #[must_use]
     pub const fn new(k_s: f64, k_v: f64, k_a: f64) -> Self {
-        Self { k_s, k_v, k_a }
+        Self {
+            k_s,
+            k_v,
+            k_a,
+            unit: PhantomData,
+        }
     }
 
+    #[must_use]
+    pub const fn get_k_s(&self) -> f64 {
+        self.k_s
+    }
+
+    #[must_use]
+    pub const fn get_k_v(&self) -> f64 {
+        self.k_v
+    }
+
+    #[must_use]
+    pub const fn get_k_a(&self) -> f64 {
+        self.k_a
+    }
+
+    #[must_use]
     pub fn v_a_calculate(
-        &mut self,
-        velocity: impl Into<RadianPerSecond>,
-        acceleration: impl Into<RadianPerSecondSquared>,
-    ) -> f64 {
-        let velocity: f64 = velocity.into().value();
-        let acceleration: f64 = acceleration.into().value();
-        self.k_a.mul_add(
+        &self,
+        velocity: impl Into<Velocity>,
+        acceleration: impl Into<Velocity::Acceleration>,
+    ) -> Volt {
+        let velocity: f64 = velocity.into().into();
+        let acceleration: f64 = acceleration.into().into();
+        Volt::new(self.k_a.mul_add(
             acceleration,
             self.k_s.mul_add(num::signum(velocity), self.k_v * velocity),
-        )
+        ))
     }
 
-    pub fn v_calculate(&mut self, velocity: impl Into<RadianPerSecond>) -> f64 {
-        self.v_a_calculate(velocity, 0.0)
+    #[must_use]
+    pub fn v_calculate(&self, velocity: impl Into<Velocity>) -> Volt {
+        self.v_a_calculate(velocity, Velocity::Acceleration::from(0.0))
     }
 
+    /// Returns the voltage that moves the motor from `current_velocity` to `next_velocity` over
+    /// `dt`.
+    ///
+    /// # Panics
+    /// Panics if `k_a` is negative, or if `k_a` is nonzero and `k_v` is negative.
+    #[must_use]
+    pub fn calculate(
+        &self,
+        current_velocity: impl Into<Velocity>,
+        next_velocity: impl Into<Velocity>,
+        dt: impl Into<Second>,
+    ) -> Volt {
+        Volt::new(discrete_calculate(
+            self.k_s,
+            self.k_v,
+            self.k_a,
+            current_velocity.into().into(),
+            next_velocity.into().into(),
+            dt.into(),
+        ))
+    }
+
+    #[must_use]
     pub fn max_velocity(
-        &mut self,
-        max_voltage: Volt,
-        acceleration: impl Into<RadianPerSecondSquared>,
-    ) -> f64 {
-        let acceleration: f64 = acceleration.into().value();
-        self.k_a
-            .mul_add(-acceleration, max_voltage.value() - self.k_s)
-            / self.k_v
+        &self,
+        max_voltage: impl Into<Volt>,
+        acceleration: impl Into<Velocity::Acceleration>,
+    ) -> Velocity {
+        let max_voltage = max_voltage.into().value();
+        let acceleration: f64 = acceleration.into().into();
+        Velocity::from(self.k_a.mul_add(-acceleration, max_voltage - self.k_s) / self.k_v)
     }
 
+    #[must_use]
     pub fn max_acceleration(
-        &mut self,
-        max_voltage: Volt,
-        velocity: impl Into<RadianPerSecond>,
-    ) -> f64 {
-        let velocity: f64 = velocity.into().value();
-        velocity.mul_add(
-            -self.k_v,
-            self.k_s
-                .mul_add(-num::signum(velocity), max_voltage.value()),
-        ) / self.k_a
+        &self,
+        max_voltage: impl Into<Volt>,
+        velocity: impl Into<Velocity>,
+    ) -> Velocity::Acceleration {
+        let max_voltage = max_voltage.into().value();
+        let velocity: f64 = velocity.into().into();
+        Velocity::Acceleration::from(
+            velocity.mul_add(
+                -self.k_v,
+                self.k_s.mul_add(-num::signum(velocity), max_voltage),
+            ) / self.k_a,
+        )
     }
 
+    #[must_use]
     pub fn min_acceleration(
-        &mut self,
-        max_voltage: Volt,
-        velocity: impl Into<RadianPerSecond>,
-    ) -> f64 {
-        self.max_acceleration(-max_voltage, velocity)
+        &self,
+        max_voltage: impl Into<Volt>,
+        velocity: impl Into<Velocity>,
+    ) -> Velocity::Acceleration {
+        self.max_acceleration(-max_voltage.into(), velocity)
     }
 }
 
-impl Static {
+impl<Velocity: VelocityUnit> Static<Velocity> {
     #[must_use]
     pub const fn new(k_v: f64, k_a: f64) -> Self {
-        Self { k_v, k_a }
+        Self {
+            k_v,
+            k_a,
+            unit: PhantomData,
+        }
+    }
+
+    #[must_use]
+    pub const fn get_k_v(&self) -> f64 {
+        self.k_v
+    }
+
+    #[must_use]
+    pub const fn get_k_a(&self) -> f64 {
+        self.k_a
     }
 
+    #[must_use]
     pub fn v_a_calculate(
-        &mut self,
-        velocity: impl Into<RadianPerSecond>,
-        acceleration: impl Into<RadianPerSecondSquared>,
-    ) -> f64 {
-        let velocity: f64 = velocity.into().value();
-        let acceleration: f64 = acceleration.into().value();
-        self.k_v.mul_add(velocity, self.k_a * acceleration)
+        &self,
+        velocity: impl Into<Velocity>,
+        acceleration: impl Into<Velocity::Acceleration>,
+    ) -> Volt {
+        let velocity: f64 = velocity.into().into();
+        let acceleration: f64 = acceleration.into().into();
+        Volt::new(self.k_v.mul_add(velocity, self.k_a * acceleration))
     }
 
-    pub fn v_calculate(&mut self, velocity: impl Into<RadianPerSecond>) -> f64 {
-        self.v_a_calculate(velocity, 0.0)
+    #[must_use]
+    pub fn v_calculate(&self, velocity: impl Into<Velocity>) -> Volt {
+        self.v_a_calculate(velocity, Velocity::Acceleration::from(0.0))
+    }
+
+    /// Returns the voltage that moves the motor from `current_velocity` to `next_velocity` over
+    /// `dt`.
+    ///
+    /// # Panics
+    /// Panics if `k_a` is negative, or if `k_a` is nonzero and `k_v` is negative.
+    #[must_use]
+    pub fn calculate(
+        &self,
+        current_velocity: impl Into<Velocity>,
+        next_velocity: impl Into<Velocity>,
+        dt: impl Into<Second>,
+    ) -> Volt {
+        Volt::new(discrete_calculate(
+            0.0,
+            self.k_v,
+            self.k_a,
+            current_velocity.into().into(),
+            next_velocity.into().into(),
+            dt.into(),
+        ))
     }
 
+    #[must_use]
     pub fn max_velocity(
-        &mut self,
-        max_voltage: Volt,
-        acceleration: impl Into<RadianPerSecondSquared>,
-    ) -> f64 {
-        let acceleration: f64 = acceleration.into().value();
-        self.k_a.mul_add(-acceleration, max_voltage.value()) / self.k_v
+        &self,
+        max_voltage: impl Into<Volt>,
+        acceleration: impl Into<Velocity::Acceleration>,
+    ) -> Velocity {
+        let max_voltage = max_voltage.into().value();
+        let acceleration: f64 = acceleration.into().into();
+        Velocity::from(self.k_a.mul_add(-acceleration, max_voltage) / self.k_v)
     }
 
+    #[must_use]
     pub fn max_acceleration(
-        &mut self,
-        max_voltage: Volt,
-        velocity: impl Into<RadianPerSecond>,
-    ) -> f64 {
-        let velocity: f64 = velocity.into().value();
-        max_voltage
-            .value()
-            .mul_add(num::signum(velocity), -velocity * self.k_v)
-            / self.k_a
+        &self,
+        max_voltage: impl Into<Volt>,
+        velocity: impl Into<Velocity>,
+    ) -> Velocity::Acceleration {
+        let max_voltage = max_voltage.into().value();
+        let velocity: f64 = velocity.into().into();
+        Velocity::Acceleration::from(
+            max_voltage.mul_add(num::signum(velocity), -velocity * self.k_v) / self.k_a,
+        )
     }
 
+    #[must_use]
     pub fn min_acceleration(
-        &mut self,
-        max_voltage: Volt,
-        velocity: impl Into<RadianPerSecond>,
-    ) -> f64 {
-        self.max_acceleration(-max_voltage, velocity)
+        &self,
+        max_voltage: impl Into<Volt>,
+        velocity: impl Into<Velocity>,
+    ) -> Velocity::Acceleration {
+        self.max_acceleration(-max_voltage.into(), velocity)
     }
 }
 
-impl Elevator {
+impl<Velocity: VelocityUnit> Elevator<Velocity> {
     #[must_use]
     pub const fn new(k_s: f64, k_g: f64, k_v: f64, k_a: f64) -> Self {
-        Self { k_s, k_g, k_v, k_a }
+        Self {
+            k_s,
+            k_g,
+            k_v,
+            k_a,
+            unit: PhantomData,
+        }
+    }
+
+    #[must_use]
+    pub const fn get_k_s(&self) -> f64 {
+        self.k_s
+    }
+
+    #[must_use]
+    pub const fn get_k_g(&self) -> f64 {
+        self.k_g
+    }
+
+    #[must_use]
+    pub const fn get_k_v(&self) -> f64 {
+        self.k_v
     }
 
+    #[must_use]
+    pub const fn get_k_a(&self) -> f64 {
+        self.k_a
+    }
+
+    #[must_use]
     pub fn v_a_calculate(
-        &mut self,
-        velocity: impl Into<RadianPerSecond>,
-        acceleration: impl Into<RadianPerSecondSquared>,
-    ) -> f64 {
-        let velocity: f64 = velocity.into().value();
-        let acceleration: f64 = acceleration.into().value();
-        self.k_a.mul_add(
-            acceleration,
-            self.k_v
-                .mul_add(velocity, self.k_s.mul_add(num::signum(velocity), self.k_g)),
+        &self,
+        velocity: impl Into<Velocity>,
+        acceleration: impl Into<Velocity::Acceleration>,
+    ) -> Volt {
+        let velocity: f64 = velocity.into().into();
+        let acceleration: f64 = acceleration.into().into();
+        Volt::new(
+            self.k_a.mul_add(
+                acceleration,
+                self.k_v
+                    .mul_add(velocity, self.k_s.mul_add(num::signum(velocity), self.k_g)),
+            ),
         )
     }
 
-    pub fn v_calculate(&mut self, velocity: impl Into<RadianPerSecond>) -> f64 {
-        self.v_a_calculate(velocity, 0)
+    #[must_use]
+    pub fn v_calculate(&self, velocity: impl Into<Velocity>) -> Volt {
+        self.v_a_calculate(velocity, Velocity::Acceleration::from(0.0))
     }
 
+    /// Returns the voltage that moves the elevator from `current_velocity` to `next_velocity`
+    /// over `dt`.
+    ///
+    /// # Panics
+    /// Panics if `k_a` is negative, or if `k_a` is nonzero and `k_v` is negative.
+    #[must_use]
+    pub fn calculate(
+        &self,
+        current_velocity: impl Into<Velocity>,
+        next_velocity: impl Into<Velocity>,
+        dt: impl Into<Second>,
+    ) -> Volt {
+        Volt::new(
+            self.k_g
+                + discrete_calculate(
+                    self.k_s,
+                    self.k_v,
+                    self.k_a,
+                    current_velocity.into().into(),
+                    next_velocity.into().into(),
+                    dt.into(),
+                ),
+        )
+    }
+
+    #[must_use]
     pub fn max_velocity(
-        &mut self,
-        max_voltage: Volt,
-        acceleration: impl Into<RadianPerSecondSquared>,
-    ) -> f64 {
-        let acceleration: f64 = acceleration.into().value();
-        self.k_a
-            .mul_add(-acceleration, max_voltage.value() - self.k_s - self.k_g)
-            / self.k_v
+        &self,
+        max_voltage: impl Into<Volt>,
+        acceleration: impl Into<Velocity::Acceleration>,
+    ) -> Velocity {
+        let max_voltage = max_voltage.into().value();
+        let acceleration: f64 = acceleration.into().into();
+        Velocity::from(
+            self.k_a
+                .mul_add(-acceleration, max_voltage - self.k_s - self.k_g)
+                / self.k_v,
+        )
     }
 
+    #[must_use]
     pub fn min_velocity(
-        &mut self,
-        max_voltage: Volt,
-        acceleration: impl Into<RadianPerSecondSquared>,
-    ) -> f64 {
-        let acceleration: f64 = acceleration.into().value();
-        self.k_a
-            .mul_add(-acceleration, -max_voltage.value() + self.k_s - self.k_g)
-            / self.k_v
+        &self,
+        max_voltage: impl Into<Volt>,
+        acceleration: impl Into<Velocity::Acceleration>,
+    ) -> Velocity {
+        let max_voltage = max_voltage.into().value();
+        let acceleration: f64 = acceleration.into().into();
+        Velocity::from(
+            self.k_a
+                .mul_add(-acceleration, -max_voltage + self.k_s - self.k_g)
+                / self.k_v,
+        )
     }
 
+    #[must_use]
     pub fn max_acceleration(
-        &mut self,
-        max_voltage: Volt,
-        velocity: impl Into<RadianPerSecond>,
-    ) -> f64 {
-        let velocity: f64 = velocity.into().value();
-        velocity.mul_add(
-            -self.k_v,
-            self.k_s
-                .mul_add(-num::signum(velocity), max_voltage.value())
-                - self.k_g,
-        ) / self.k_a
+        &self,
+        max_voltage: impl Into<Volt>,
+        velocity: impl Into<Velocity>,
+    ) -> Velocity::Acceleration {
+        let max_voltage = max_voltage.into().value();
+        let velocity: f64 = velocity.into().into();
+        Velocity::Acceleration::from(
+            velocity.mul_add(
+                -self.k_v,
+                self.k_s.mul_add(-num::signum(velocity), max_voltage) - self.k_g,
+            ) / self.k_a,
+        )
     }
 
+    #[must_use]
     pub fn min_acceleration(
-        &mut self,
-        max_voltage: Volt,
-        velocity: impl Into<RadianPerSecond>,
-    ) -> f64 {
-        self.max_acceleration(-max_voltage, velocity)
+        &self,
+        max_voltage: impl Into<Volt>,
+        velocity: impl Into<Velocity>,
+    ) -> Velocity::Acceleration {
+        self.max_acceleration(-max_voltage.into(), velocity)
     }
 }
 
@@ -210,87 +445,146 @@ impl Arm {
         Self { k_s, k_g, k_v, k_a }
     }
 
+    #[must_use]
+    pub const fn get_k_s(&self) -> f64 {
+        self.k_s
+    }
+
+    #[must_use]
+    pub const fn get_k_g(&self) -> f64 {
+        self.k_g
+    }
+
+    #[must_use]
+    pub const fn get_k_v(&self) -> f64 {
+        self.k_v
+    }
+
+    #[must_use]
+    pub const fn get_k_a(&self) -> f64 {
+        self.k_a
+    }
+
+    #[must_use]
     pub fn p_v_a_calculate(
-        &mut self,
+        &self,
         position: impl Into<Radian>,
         velocity: impl Into<RadianPerSecond>,
         acceleration: impl Into<RadianPerSecondSquared>,
-    ) -> f64 {
+    ) -> Volt {
         let position: f64 = position.into().value();
         let velocity: f64 = velocity.into().value();
         let acceleration: f64 = acceleration.into().value();
         let g_cos: f64 = position.cos() * self.k_g;
-        self.k_a.mul_add(
-            acceleration,
-            self.k_v
-                .mul_add(velocity, self.k_s.mul_add(num::signum(velocity), g_cos)),
+        Volt::new(
+            self.k_a.mul_add(
+                acceleration,
+                self.k_v
+                    .mul_add(velocity, self.k_s.mul_add(num::signum(velocity), g_cos)),
+            ),
         )
     }
 
+    #[must_use]
     pub fn p_v_calculate(
-        &mut self,
+        &self,
         position: impl Into<Radian>,
         velocity: impl Into<RadianPerSecond>,
-    ) -> f64 {
+    ) -> Volt {
         self.p_v_a_calculate(position, velocity, 0)
     }
 
+    /// Returns the voltage that moves the arm from `current_velocity` to `next_velocity` over
+    /// `dt`, with the gravity load held at its value at `position`.
+    ///
+    /// # Panics
+    /// Panics if `k_a` is negative, or if `k_a` is nonzero and `k_v` is negative.
+    #[must_use]
+    pub fn calculate(
+        &self,
+        position: impl Into<Radian>,
+        current_velocity: impl Into<RadianPerSecond>,
+        next_velocity: impl Into<RadianPerSecond>,
+        dt: impl Into<Second>,
+    ) -> Volt {
+        let position: f64 = position.into().value();
+        Volt::new(position.cos().mul_add(
+            self.k_g,
+            discrete_calculate(
+                self.k_s,
+                self.k_v,
+                self.k_a,
+                current_velocity.into().value(),
+                next_velocity.into().value(),
+                dt.into(),
+            ),
+        ))
+    }
+
+    #[must_use]
     pub fn max_velocity(
-        &mut self,
-        max_voltage: Volt,
+        &self,
+        max_voltage: impl Into<Volt>,
         angle: impl Into<Radian>,
         acceleration: impl Into<RadianPerSecondSquared>,
-    ) -> f64 {
+    ) -> RadianPerSecond {
+        let max_voltage = max_voltage.into().value();
         let angle: f64 = angle.into().value();
         let acceleration: f64 = acceleration.into().value();
-        acceleration.mul_add(
-            -self.k_a,
-            angle
-                .cos()
-                .mul_add(-self.k_g, max_voltage.value() - self.k_s),
-        ) / self.k_v
+        RadianPerSecond::new(
+            acceleration.mul_add(
+                -self.k_a,
+                angle.cos().mul_add(-self.k_g, max_voltage - self.k_s),
+            ) / self.k_v,
+        )
     }
 
+    #[must_use]
     pub fn min_velocity(
-        &mut self,
-        max_voltage: Volt,
+        &self,
+        max_voltage: impl Into<Volt>,
         angle: impl Into<Radian>,
         acceleration: impl Into<RadianPerSecondSquared>,
-    ) -> f64 {
+    ) -> RadianPerSecond {
+        let max_voltage = max_voltage.into().value();
         let angle: f64 = angle.into().value();
         let acceleration: f64 = acceleration.into().value();
-        acceleration.mul_add(
-            -self.k_a,
-            angle
-                .cos()
-                .mul_add(-self.k_g, -max_voltage.value() + self.k_s),
-        ) / self.k_v
+        RadianPerSecond::new(
+            acceleration.mul_add(
+                -self.k_a,
+                angle.cos().mul_add(-self.k_g, -max_voltage + self.k_s),
+            ) / self.k_v,
+        )
     }
 
+    #[must_use]
     pub fn max_acceleration(
-        &mut self,
-        max_voltage: Volt,
+        &self,
+        max_voltage: impl Into<Volt>,
         angle: impl Into<Radian>,
         velocity: impl Into<RadianPerSecond>,
-    ) -> f64 {
+    ) -> RadianPerSecondSquared {
+        let max_voltage = max_voltage.into().value();
         let angle: f64 = angle.into().value();
         let velocity: f64 = velocity.into().value();
-        velocity.mul_add(
-            -self.k_v,
-            angle.cos().mul_add(
-                -self.k_g,
-                self.k_s
-                    .mul_add(-num::signum(velocity), max_voltage.value()),
-            ),
-        ) / self.k_a
+        RadianPerSecondSquared::new(
+            velocity.mul_add(
+                -self.k_v,
+                angle.cos().mul_add(
+                    -self.k_g,
+                    self.k_s.mul_add(-num::signum(velocity), max_voltage),
+                ),
+            ) / self.k_a,
+        )
     }
 
+    #[must_use]
     pub fn min_acceleration(
-        &mut self,
-        max_voltage: Volt,
+        &self,
+        max_voltage: impl Into<Volt>,
         angle: impl Into<Radian>,
         velocity: impl Into<RadianPerSecond>,
-    ) -> f64 {
-        self.max_acceleration(-max_voltage, angle, velocity)
+    ) -> RadianPerSecondSquared {
+        self.max_acceleration(-max_voltage.into(), angle, velocity)
     }
 }
@@ -0,0 +1,88 @@
+use nalgebra::{SMatrix, SVector};
+
+use crate::math::system::{Discretization, LinearSystem};
+use crate::math::units::time::Second;
+
+/// Computes the input that moves a linear plant from one reference to the next in one step,
+/// `u_ff = B⁺(r_k+1 − Ar_k)`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct LinearPlantInversionFeedforward<const STATES: usize, const INPUTS: usize> {
+    a: SMatrix<f64, STATES, STATES>,
+    b: SMatrix<f64, STATES, INPUTS>,
+    r: SVector<f64, STATES>,
+    uff: SVector<f64, INPUTS>,
+}
+
+impl<const STATES: usize, const INPUTS: usize> LinearPlantInversionFeedforward<STATES, INPUTS> {
+    #[must_use]
+    pub fn new<const OUTPUTS: usize>(
+        plant: &LinearSystem<STATES, INPUTS, OUTPUTS>,
+        dt: impl Into<Second>,
+    ) -> Self {
+        Self::new_ab(plant.get_a(), plant.get_b(), dt)
+    }
+
+    /// Creates a feedforward for the continuous system `dx/dt = Ax + Bu`.
+    #[must_use]
+    pub fn new_ab(
+        a: &SMatrix<f64, STATES, STATES>,
+        b: &SMatrix<f64, STATES, INPUTS>,
+        dt: impl Into<Second>,
+    ) -> Self {
+        let (a, b) = Discretization::discretize_ab(a, b, dt);
+        Self {
+            a,
+            b,
+            r: SVector::zeros(),
+            uff: SVector::zeros(),
+        }
+    }
+
+    /// Returns the current reference.
+    #[must_use]
+    pub const fn get_r(&self) -> &SVector<f64, STATES> {
+        &self.r
+    }
+
+    /// Returns the most recent feedforward input.
+    #[must_use]
+    pub const fn get_uff(&self) -> &SVector<f64, INPUTS> {
+        &self.uff
+    }
+
+    /// Sets the reference and zeroes the feedforward input.
+    pub fn reset(&mut self, initial_state: &SVector<f64, STATES>) {
+        self.r = *initial_state;
+        self.uff = SVector::zeros();
+    }
+
+    /// Returns the input that moves the plant from the current reference to `next_r`, which
+    /// becomes the reference.
+    ///
+    /// # Panics
+    /// Panics if `B` doesn't have full column rank.
+    pub fn calculate(&mut self, next_r: &SVector<f64, STATES>) -> SVector<f64, INPUTS> {
+        let r = self.r;
+        self.calculate_with_r(&r, next_r)
+    }
+
+    /// Returns the input that moves the plant from `r` to `next_r`, which becomes the reference.
+    ///
+    /// # Panics
+    /// Panics if `B` doesn't have full column rank.
+    pub fn calculate_with_r(
+        &mut self,
+        r: &SVector<f64, STATES>,
+        next_r: &SVector<f64, STATES>,
+    ) -> SVector<f64, INPUTS> {
+        // least squares solution of Bu = r_k+1 − Ar_k
+        let b_transpose = self.b.transpose();
+        self.uff = (b_transpose * self.b)
+            .try_inverse()
+            .expect("B must have full column rank")
+            * b_transpose
+            * (next_r - self.a * r);
+        self.r = *next_r;
+        self.uff
+    }
+}
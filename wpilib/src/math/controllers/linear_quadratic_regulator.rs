@@ -0,0 +1,206 @@
+use nalgebra::{SMatrix, SVector};
+
+use crate::math::system::{Discretization, LinearSystem};
+use crate::math::units::time::Second;
+use crate::math::util::dare::Dare;
+use crate::math::util::state_space_util::StateSpaceUtil;
+
+/// A state feedback controller `u = K(r − x)` with the gain `K` that minimizes the quadratic cost
+/// `Σ xᵀQx + uᵀRu` for a linear plant.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct LinearQuadraticRegulator<const STATES: usize, const INPUTS: usize> {
+    k: SMatrix<f64, INPUTS, STATES>,
+    r: SVector<f64, STATES>,
+    u: SVector<f64, INPUTS>,
+}
+
+impl<const STATES: usize, const INPUTS: usize> LinearQuadraticRegulator<STATES, INPUTS> {
+    /// Creates a regulator from the largest acceptable excursion of each state and input, which
+    /// are turned into cost matrices with Bryson's rule.
+    ///
+    /// # Panics
+    /// Panics if the plant isn't stabilizable or an input tolerance isn't positive.
+    #[must_use]
+    pub fn new<const OUTPUTS: usize>(
+        plant: &LinearSystem<STATES, INPUTS, OUTPUTS>,
+        q_elms: SVector<f64, STATES>,
+        r_elms: SVector<f64, INPUTS>,
+        dt: impl Into<Second>,
+    ) -> Self {
+        Self::new_qr(
+            plant.get_a(),
+            plant.get_b(),
+            &StateSpaceUtil::make_cost_matrix(&q_elms),
+            &StateSpaceUtil::make_cost_matrix(&r_elms),
+            dt,
+        )
+    }
+
+    /// Creates a regulator for the continuous system `dx/dt = Ax + Bu` from its state and input
+    /// cost matrices.
+    ///
+    /// # Panics
+    /// Panics if (A, B) isn't stabilizable or `R` isn't symmetric positive definite.
+    #[must_use]
+    pub fn new_qr(
+        a: &SMatrix<f64, STATES, STATES>,
+        b: &SMatrix<f64, STATES, INPUTS>,
+        q: &SMatrix<f64, STATES, STATES>,
+        r: &SMatrix<f64, INPUTS, INPUTS>,
+        dt: impl Into<Second>,
+    ) -> Self {
+        let (discrete_a, discrete_b) = Discretization::discretize_ab(a, b, dt);
+        let s = Dare::dare(&discrete_a, &discrete_b, q, r);
+
+        // K = (BᵀSB + R)⁻¹BᵀSA
+        let k = (discrete_b.transpose() * s * discrete_b + r)
+            .try_inverse()
+            .expect("BᵀSB + R must be invertible")
+            * discrete_b.transpose()
+            * s
+            * discrete_a;
+        Self::new_k(k)
+    }
+
+    /// Creates a regulator that also penalizes the cross-term `N` between state and input.
+    ///
+    /// # Panics
+    /// Panics if (A, B) isn't stabilizable or `R` isn't symmetric positive definite.
+    #[must_use]
+    pub fn new_qrn(
+        a: &SMatrix<f64, STATES, STATES>,
+        b: &SMatrix<f64, STATES, INPUTS>,
+        q: &SMatrix<f64, STATES, STATES>,
+        r: &SMatrix<f64, INPUTS, INPUTS>,
+        n: &SMatrix<f64, STATES, INPUTS>,
+        dt: impl Into<Second>,
+    ) -> Self {
+        let (discrete_a, discrete_b) = Discretization::discretize_ab(a, b, dt);
+        let s = Dare::dare_with_cross_term(&discrete_a, &discrete_b, q, r, n);
+
+        // K = (BᵀSB + R)⁻¹(BᵀSA + Nᵀ)
+        let k = (discrete_b.transpose() * s * discrete_b + r)
+            .try_inverse()
+            .expect("BᵀSB + R must be invertible")
+            * (discrete_b.transpose() * s * discrete_a + n.transpose());
+        Self::new_k(k)
+    }
+
+    /// Creates a regulator with a precomputed gain.
+    #[must_use]
+    pub fn new_k(k: SMatrix<f64, INPUTS, STATES>) -> Self {
+        Self {
+            k,
+            r: SVector::zeros(),
+            u: SVector::zeros(),
+        }
+    }
+
+    #[must_use]
+    pub const fn get_k(&self) -> &SMatrix<f64, INPUTS, STATES> {
+        &self.k
+    }
+
+    /// Returns the reference being tracked.
+    #[must_use]
+    pub const fn get_r(&self) -> &SVector<f64, STATES> {
+        &self.r
+    }
+
+    /// Returns the most recent control input.
+    #[must_use]
+    pub const fn get_u(&self) -> &SVector<f64, INPUTS> {
+        &self.u
+    }
+
+    /// Zeroes the reference and control input.
+    pub fn reset(&mut self) {
+        self.r = SVector::zeros();
+        self.u = SVector::zeros();
+    }
+
+    /// Returns the control input that drives `x` toward the current reference.
+    pub fn calculate(&mut self, x: &SVector<f64, STATES>) -> SVector<f64, INPUTS> {
+        self.u = self.k * (self.r - x);
+        self.u
+    }
+
+    /// Returns the control input that drives `x` toward `next_r`, which becomes the reference.
+    pub fn calculate_with_r(
+        &mut self,
+        x: &SVector<f64, STATES>,
+        next_r: &SVector<f64, STATES>,
+    ) -> SVector<f64, INPUTS> {
+        self.r = *next_r;
+        self.calculate(x)
+    }
+
+    /// Adjusts the gain for a plant whose inputs take effect `input_delay` after they're applied,
+    /// such as from CAN latency, by controlling toward where the state will be after the delay.
+    ///
+    /// `dt` must be the period the regulator was created with.
+    pub fn latency_compensate<const OUTPUTS: usize>(
+        &mut self,
+        plant: &LinearSystem<STATES, INPUTS, OUTPUTS>,
+        dt: impl Into<Second>,
+        input_delay: impl Into<Second>,
+    ) {
+        let dt: Second = dt.into();
+        let (discrete_a, discrete_b) =
+            Discretization::discretize_ab(plant.get_a(), plant.get_b(), dt);
+        let closed_loop = discrete_a - discrete_b * self.k;
+        self.k *= matrix_pow(&closed_loop, input_delay.into().value() / dt.value());
+    }
+}
+
+/// Raises `m` to a non-negative real power from repeated square roots of `m`.
+///
+/// `m` must have no eigenvalues on the non-positive real axis if the power isn't an integer.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn matrix_pow<const N: usize>(m: &SMatrix<f64, N, N>, exponent: f64) -> SMatrix<f64, N, N> {
+    let mut result = SMatrix::identity();
+    let mut power = *m;
+    let mut whole = exponent.trunc() as u64;
+    while whole > 0 {
+        if whole % 2 == 1 {
+            result *= power;
+        }
+        power *= power;
+        whole /= 2;
+    }
+
+    // each bit of the fraction picks up another square root
+    let mut fraction = exponent.fract();
+    let mut root = *m;
+    for _ in 0..52 {
+        if fraction <= 0.0 {
+            break;
+        }
+        root = matrix_sqrt(&root);
+        fraction *= 2.0;
+        if fraction >= 1.0 {
+            result *= root;
+            fraction -= 1.0;
+        }
+    }
+    result
+}
+
+/// Returns the principal square root of `m` using the Denman-Beavers iteration.
+fn matrix_sqrt<const N: usize>(m: &SMatrix<f64, N, N>) -> SMatrix<f64, N, N> {
+    let mut y = *m;
+    let mut z = SMatrix::<f64, N, N>::identity();
+    for _ in 0..100 {
+        let (Some(y_inverse), Some(z_inverse)) = (y.try_inverse(), z.try_inverse()) else {
+            break;
+        };
+        let next_y = (y + z_inverse) / 2.0;
+        z = (z + y_inverse) / 2.0;
+        let converged = (next_y - y).norm() <= 1e-12 * next_y.norm();
+        y = next_y;
+        if converged {
+            break;
+        }
+    }
+    y
+}
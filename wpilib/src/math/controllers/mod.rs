@@ -1,9 +1,15 @@
 mod bang_bang;
 mod controller;
 pub mod feed_forward;
+mod linear_plant_inversion_feedforward;
+mod linear_quadratic_regulator;
 mod pid;
+mod profiled_pid;
 mod test;
 
 pub use bang_bang::*;
 pub use controller::*;
+pub use linear_plant_inversion_feedforward::*;
+pub use linear_quadratic_regulator::*;
 pub use pid::*;
+pub use profiled_pid::*;
@@ -1,69 +1,245 @@
-use crate::math::controllers::controller::Controller;
-use crate::math::units::time::Millisecond;
+use crate::math::controllers::controller::{Controller, ControllerInput, ControllerUnit};
+use crate::math::units::time::Second;
+use crate::math::util::math_util::MathUtil;
 
+/// A proportional-integral-derivative controller that measures an `Input` and produces an
+/// `Output`.
+///
+/// Gains are in output units per input unit, with time in seconds, so `k_i` multiplies the error
+/// integrated over seconds and `k_d` the error's rate of change per second.
+///
+/// Neither the output nor the integral term is bounded until
+/// [`set_limits`](Controller::set_limits) or [`set_integrator_range`](Self::set_integrator_range)
+/// is called.
 #[derive(Debug, Clone, Copy)]
-pub struct PIDController {
+pub struct PIDController<Input: ControllerInput = f64, Output: ControllerUnit = f64> {
     pub k_p: f64,
     pub k_i: f64,
     pub k_d: f64,
-    pub i_min: f64,
-    pub i_max: f64,
-    pub min_input: f64,
-    pub max_input: f64,
-    pub min_output: f64,
-    pub max_output: f64,
+    /// Lower bound on the integral term's contribution to the output.
+    pub i_min: Output,
+    /// Upper bound on the integral term's contribution to the output.
+    pub i_max: Output,
+    /// Measurements are clamped to the input range, or wrapped within it with continuous input.
+    pub min_input: Input,
+    pub max_input: Input,
+    pub min_output: Output,
+    pub max_output: Output,
+    i_zone: Input,
+    continuous: bool,
+    position_tolerance: Input,
+    velocity_tolerance: Input::PerSecond,
+    derivative_filter: Option<Second>,
+    position_error: f64,
+    velocity_error: f64,
     prev_error: f64,
     total_error: f64,
-    set_point: f64,
+    set_point: Input,
+    measurement: Option<f64>,
+    have_set_point: bool,
     enabled: bool,
 }
 
-impl PIDController {
+impl<Input: ControllerInput, Output: ControllerUnit> PIDController<Input, Output> {
     #[must_use]
-    pub const fn new(k_p: f64, k_i: f64, k_d: f64) -> Self {
+    pub fn new(k_p: f64, k_i: f64, k_d: f64) -> Self {
         Self {
             k_p,
             k_i,
             k_d,
-            i_min: -1.0,
-            i_max: 1.0,
-            min_input: -1.0,
-            max_input: 1.0,
-            min_output: -1.0,
-            max_output: 1.0,
+            i_min: Output::from(f64::NEG_INFINITY),
+            i_max: Output::from(f64::INFINITY),
+            min_input: Input::from(f64::NEG_INFINITY),
+            max_input: Input::from(f64::INFINITY),
+            min_output: Output::from(f64::NEG_INFINITY),
+            max_output: Output::from(f64::INFINITY),
+            i_zone: Input::from(f64::INFINITY),
+            continuous: false,
+            position_tolerance: Input::from(0.05),
+            velocity_tolerance: Input::PerSecond::from(f64::INFINITY),
+            derivative_filter: None,
+            position_error: 0.0,
+            velocity_error: 0.0,
             prev_error: 0.0,
             total_error: 0.0,
-            set_point: 0.0,
+            set_point: Input::from(0.0),
+            measurement: None,
+            have_set_point: false,
             enabled: true,
         }
     }
 
-    pub fn set_i_zone(&mut self, i_min: f64, i_max: f64) {
-        self.i_min = i_min;
-        self.i_max = i_max;
+    /// Sets the bounds on the integral term's contribution to the output.
+    pub fn set_integrator_range(&mut self, i_min: impl Into<Output>, i_max: impl Into<Output>) {
+        self.i_min = i_min.into();
+        self.i_max = i_max.into();
+    }
+
+    /// Sets the largest error the integral term accumulates over; outside it the accumulated
+    /// error is cleared. This stops the integral term winding up during large moves.
+    ///
+    /// # Panics
+    /// Panics if `i_zone` is negative.
+    pub fn set_i_zone(&mut self, i_zone: impl Into<Input>) {
+        let i_zone = i_zone.into();
+        assert!(i_zone.into() >= 0.0, "IZone must be a non-negative number");
+        self.i_zone = i_zone;
+    }
+
+    #[must_use]
+    pub const fn get_i_zone(&self) -> Input {
+        self.i_zone
+    }
+
+    /// Treats `min_input` and `max_input` as the same point, so the controller takes the shorter
+    /// way around, such as for an angle.
+    pub fn enable_continuous_input(
+        &mut self,
+        min_input: impl Into<Input>,
+        max_input: impl Into<Input>,
+    ) {
+        self.continuous = true;
+        self.min_input = min_input.into();
+        self.max_input = max_input.into();
+    }
+
+    pub const fn disable_continuous_input(&mut self) {
+        self.continuous = false;
+    }
+
+    #[must_use]
+    pub const fn is_continuous_input_enabled(&self) -> bool {
+        self.continuous
+    }
+
+    /// Sets how close the measurement must be to the set point for
+    /// [`at_setpoint`](Self::at_setpoint).
+    pub fn set_tolerance(&mut self, position_tolerance: impl Into<Input>) {
+        self.set_tolerance_with_velocity(position_tolerance, Input::PerSecond::from(f64::INFINITY));
+    }
+
+    /// Sets how close the measurement must be to the set point, and how slowly the error must be
+    /// changing, for [`at_setpoint`](Self::at_setpoint).
+    pub fn set_tolerance_with_velocity(
+        &mut self,
+        position_tolerance: impl Into<Input>,
+        velocity_tolerance: impl Into<Input::PerSecond>,
+    ) {
+        self.position_tolerance = position_tolerance.into();
+        self.velocity_tolerance = velocity_tolerance.into();
+    }
+
+    #[must_use]
+    pub const fn get_position_tolerance(&self) -> Input {
+        self.position_tolerance
+    }
+
+    #[must_use]
+    pub const fn get_velocity_tolerance(&self) -> Input::PerSecond {
+        self.velocity_tolerance
+    }
+
+    /// Smooths the derivative term with a single-pole low-pass filter, trading some lag for less
+    /// amplification of measurement noise.
+    pub fn set_derivative_filter(&mut self, time_constant: impl Into<Second>) {
+        self.derivative_filter = Some(time_constant.into());
+    }
+
+    pub const fn disable_derivative_filter(&mut self) {
+        self.derivative_filter = None;
+    }
+
+    /// Returns the difference between the set point and the last measurement.
+    #[must_use]
+    pub fn get_position_error(&self) -> Input {
+        Input::from(self.position_error)
+    }
+
+    /// Returns the rate of change of the error, after the derivative filter if one is set.
+    #[must_use]
+    pub fn get_velocity_error(&self) -> Input::PerSecond {
+        Input::from(self.velocity_error).into_rate()
+    }
+
+    /// Returns whether the error is within the tolerances. This is false until both a set point
+    /// and a measurement have been given.
+    #[must_use]
+    pub fn at_setpoint(&self) -> bool {
+        self.measurement.is_some()
+            && self.have_set_point
+            && self.position_error.abs() < self.position_tolerance.into()
+            && self.get_velocity_error().into().abs() < self.velocity_tolerance.into()
+    }
+
+    fn error_to(&self, measurement: f64) -> f64 {
+        let error = self.set_point.into() - measurement;
+        if self.continuous {
+            let error_bound = (self.max_input.into() - self.min_input.into()) / 2.0;
+            MathUtil::input_modulus(error, -error_bound, error_bound)
+        } else {
+            error
+        }
     }
 }
 
-impl Controller for PIDController {
-    fn calculate(&mut self, measurement: f64, period: impl Into<Millisecond>) -> f64 {
-        let period: f64 = period.into().value();
+impl<Input: ControllerInput, Output: ControllerUnit> Controller<Input, Output>
+    for PIDController<Input, Output>
+{
+    fn calculate(&mut self, measurement: impl Into<Input>, period: impl Into<Second>) -> Output {
+        let period = period.into().value();
         if !self.enabled {
-            return 0.0;
+            return Output::from(0.0);
         }
-        let error = self.set_point - measurement;
-        self.total_error += error * period;
-        self.total_error = self.total_error.clamp(self.i_min, self.i_max);
-        let d_error = (error - self.prev_error) / period;
-        self.prev_error = error;
-        let p = self.k_p * error;
+
+        let measurement: f64 = measurement.into().into();
+        let measurement = if self.continuous {
+            measurement
+        } else {
+            measurement.clamp(self.min_input.into(), self.max_input.into())
+        };
+        self.prev_error = self.position_error;
+        self.position_error = self.error_to(measurement);
+
+        // without a previous measurement there's no rate of change, rather than a kick from
+        // differencing against zero
+        if self.measurement.is_some() {
+            let velocity_error = (self.position_error - self.prev_error) / period;
+            self.velocity_error = match self.derivative_filter {
+                Some(time_constant) => {
+                    let gain = (-period / time_constant.value()).exp();
+                    gain.mul_add(self.velocity_error, (1.0 - gain) * velocity_error)
+                }
+                None => velocity_error,
+            };
+        } else {
+            self.velocity_error = 0.0;
+        }
+        self.measurement = Some(measurement);
+
+        if self.position_error.abs() > self.i_zone.into() {
+            self.total_error = 0.0;
+        } else if self.k_i != 0.0 {
+            let bound_a = self.i_min.into() / self.k_i;
+            let bound_b = self.i_max.into() / self.k_i;
+            self.total_error = self
+                .position_error
+                .mul_add(period, self.total_error)
+                .clamp(bound_a.min(bound_b), bound_a.max(bound_b));
+        }
+
+        let p = self.k_p * self.position_error;
         let i = self.k_i * self.total_error;
-        let d = self.k_d * d_error;
+        let d = self.k_d * self.velocity_error;
         let output = p + i + d;
-        output.clamp(self.min_output, self.max_output)
+        Output::from(output.clamp(self.min_output.into(), self.max_output.into()))
     }
 
-    fn set_set_point(&mut self, set_point: f64) {
-        self.set_point = set_point;
+    fn set_set_point(&mut self, set_point: impl Into<Input>) {
+        self.set_point = set_point.into();
+        self.have_set_point = true;
+        if let Some(measurement) = self.measurement {
+            self.position_error = self.error_to(measurement);
+        }
     }
 
     fn set_enabled(&mut self, enabled: bool) {
@@ -74,18 +250,24 @@ impl Controller for PIDController {
         self.enabled
     }
 
-    fn get_set_point(&self) -> f64 {
+    fn get_set_point(&self) -> Input {
         self.set_point
     }
 
-    fn set_limits(&mut self, min_input: f64, max_input: f64, min_output: f64, max_output: f64) {
-        self.min_input = min_input;
-        self.max_input = max_input;
-        self.min_output = min_output;
-        self.max_output = max_output;
+    fn set_limits(
+        &mut self,
+        min_input: impl Into<Input>,
+        max_input: impl Into<Input>,
+        min_output: impl Into<Output>,
+        max_output: impl Into<Output>,
+    ) {
+        self.min_input = min_input.into();
+        self.max_input = max_input.into();
+        self.min_output = min_output.into();
+        self.max_output = max_output.into();
     }
 
-    fn get_limits(&self) -> (f64, f64, f64, f64) {
+    fn get_limits(&self) -> (Input, Input, Output, Output) {
         (
             self.min_input,
             self.max_input,
@@ -94,8 +276,12 @@ impl Controller for PIDController {
         )
     }
 
+    /// Clears the accumulated and previous errors, so the next measurement starts fresh.
     fn reset(&mut self) {
+        self.position_error = 0.0;
+        self.velocity_error = 0.0;
         self.prev_error = 0.0;
         self.total_error = 0.0;
+        self.measurement = None;
     }
 }
@@ -0,0 +1,194 @@
+use crate::math::controllers::controller::{Controller, ControllerInput, ControllerUnit};
+use crate::math::controllers::pid::PIDController;
+use crate::math::trajectory::{
+    TrapezoidProfile, TrapezoidProfileConstraints, TrapezoidProfileState,
+};
+use crate::math::units::time::Second;
+use crate::math::util::math_util::MathUtil;
+
+/// A [`PIDController`] that moves its set point toward a goal along a [`TrapezoidProfile`]
+/// instead of jumping to it.
+///
+/// The [`Controller`] set point is the goal position; the profiled set point the PID loop is
+/// tracking each loop is [`get_setpoint`](Self::get_setpoint). Profile positions are in input
+/// units and velocities in input units per second.
+#[derive(Debug, Clone, Copy)]
+pub struct ProfiledPIDController<Input: ControllerInput = f64, Output: ControllerUnit = f64> {
+    pub controller: PIDController<Input, Output>,
+    constraints: TrapezoidProfileConstraints,
+    profile: TrapezoidProfile,
+    goal: TrapezoidProfileState,
+    setpoint: TrapezoidProfileState,
+}
+
+impl<Input: ControllerInput, Output: ControllerUnit> ProfiledPIDController<Input, Output> {
+    #[must_use]
+    pub fn new(k_p: f64, k_i: f64, k_d: f64, constraints: TrapezoidProfileConstraints) -> Self {
+        Self {
+            controller: PIDController::new(k_p, k_i, k_d),
+            constraints,
+            profile: TrapezoidProfile::new(constraints),
+            goal: TrapezoidProfileState::new(0.0, 0.0),
+            setpoint: TrapezoidProfileState::new(0.0, 0.0),
+        }
+    }
+
+    pub const fn set_goal(&mut self, goal: TrapezoidProfileState) {
+        self.goal = goal;
+    }
+
+    /// Sets a goal to come to rest at.
+    pub fn set_goal_position(&mut self, goal: impl Into<Input>) {
+        self.goal = TrapezoidProfileState::new(goal.into().into(), 0.0);
+    }
+
+    #[must_use]
+    pub const fn get_goal(&self) -> TrapezoidProfileState {
+        self.goal
+    }
+
+    pub const fn set_constraints(&mut self, constraints: TrapezoidProfileConstraints) {
+        self.constraints = constraints;
+        self.profile = TrapezoidProfile::new(constraints);
+    }
+
+    #[must_use]
+    pub const fn get_constraints(&self) -> TrapezoidProfileConstraints {
+        self.constraints
+    }
+
+    /// Returns the profiled set point from the last [`calculate`](Controller::calculate), whose
+    /// velocity can be passed to a feedforward.
+    #[must_use]
+    pub const fn get_setpoint(&self) -> TrapezoidProfileState {
+        self.setpoint
+    }
+
+    /// Treats `min_input` and `max_input` as the same point, so the profile takes the shorter
+    /// way around, such as for an angle.
+    pub fn enable_continuous_input(
+        &mut self,
+        min_input: impl Into<Input>,
+        max_input: impl Into<Input>,
+    ) {
+        self.controller
+            .enable_continuous_input(min_input, max_input);
+    }
+
+    pub const fn disable_continuous_input(&mut self) {
+        self.controller.disable_continuous_input();
+    }
+
+    #[must_use]
+    pub const fn is_continuous_input_enabled(&self) -> bool {
+        self.controller.is_continuous_input_enabled()
+    }
+
+    /// Sets how close the measurement must be to the profiled set point for
+    /// [`at_setpoint`](Self::at_setpoint).
+    pub fn set_tolerance(&mut self, position_tolerance: impl Into<Input>) {
+        self.controller.set_tolerance(position_tolerance);
+    }
+
+    /// Returns whether the measurement is within tolerance of the profiled set point.
+    #[must_use]
+    pub fn at_setpoint(&self) -> bool {
+        self.controller.at_setpoint()
+    }
+
+    /// Returns whether the profile has finished and the measurement is within tolerance of the
+    /// goal.
+    #[must_use]
+    pub fn at_goal(&self) -> bool {
+        self.at_setpoint() && self.goal == self.setpoint
+    }
+
+    /// Resets the PID loop and restarts the profile from `measurement`.
+    pub fn reset_to(&mut self, measurement: TrapezoidProfileState) {
+        self.controller.reset();
+        self.setpoint = measurement;
+    }
+
+    /// Resets the PID loop and restarts the profile at rest at `measured_position`.
+    pub fn reset_to_position(&mut self, measured_position: impl Into<Input>) {
+        self.reset_to(TrapezoidProfileState::new(
+            measured_position.into().into(),
+            0.0,
+        ));
+    }
+}
+
+impl<Input: ControllerInput, Output: ControllerUnit> Controller<Input, Output>
+    for ProfiledPIDController<Input, Output>
+{
+    fn calculate(&mut self, measurement: impl Into<Input>, period: impl Into<Second>) -> Output {
+        let period: Second = period.into();
+        if !self.controller.get_enabled() {
+            return Output::from(0.0);
+        }
+
+        let measurement: f64 = measurement.into().into();
+
+        if self.controller.is_continuous_input_enabled() {
+            // move the goal and set point to the copies nearest the measurement, so the profile
+            // takes the shorter way around
+            let error_bound =
+                (self.controller.max_input.into() - self.controller.min_input.into()) / 2.0;
+            self.goal.position = measurement
+                + MathUtil::input_modulus(
+                    self.goal.position - measurement,
+                    -error_bound,
+                    error_bound,
+                );
+            self.setpoint.position = measurement
+                + MathUtil::input_modulus(
+                    self.setpoint.position - measurement,
+                    -error_bound,
+                    error_bound,
+                );
+        }
+
+        self.setpoint = self.profile.calculate(period, self.setpoint, self.goal);
+        self.controller.set_set_point(self.setpoint.position);
+        self.controller.calculate(measurement, period)
+    }
+
+    /// Sets a goal position to come to rest at.
+    fn set_set_point(&mut self, set_point: impl Into<Input>) {
+        self.set_goal_position(set_point);
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.controller.set_enabled(enabled);
+    }
+
+    fn get_enabled(&self) -> bool {
+        self.controller.get_enabled()
+    }
+
+    /// Returns the goal position.
+    fn get_set_point(&self) -> Input {
+        Input::from(self.goal.position)
+    }
+
+    fn set_limits(
+        &mut self,
+        min_input: impl Into<Input>,
+        max_input: impl Into<Input>,
+        min_output: impl Into<Output>,
+        max_output: impl Into<Output>,
+    ) {
+        self.controller
+            .set_limits(min_input, max_input, min_output, max_output);
+    }
+
+    fn get_limits(&self) -> (Input, Input, Output, Output) {
+        self.controller.get_limits()
+    }
+
+    /// Resets the PID loop, keeping the profiled set point; use
+    /// [`reset_to`](ProfiledPIDController::reset_to) to restart the profile from a measurement.
+    fn reset(&mut self) {
+        self.controller.reset();
+    }
+}
@@ -1,8 +1,22 @@
-use crate::math::controllers::{BangBangController, Controller, PIDController};
+use crate::math::controllers::feed_forward::{Arm, Elevator, Simple, Static};
+use crate::math::controllers::{
+    BangBangController, Controller, LinearPlantInversionFeedforward, LinearQuadraticRegulator,
+    PIDController, ProfiledPIDController,
+};
+use crate::math::system::{DCMotor, Discretization, LinearSystem, LinearSystemId};
+use crate::math::trajectory::{TrapezoidProfileConstraints, TrapezoidProfileState};
+use crate::math::units::angle::{Degree, Radian};
+use crate::math::units::angular_velocity::{DegreePerSecond, RadianPerSecond, RotationPerMinute};
+use crate::math::units::energy::Volt;
+use crate::math::units::linear_acceleration::MeterPerSecondSquared;
+use crate::math::units::linear_velocity::{FeetPerSecond, MeterPerSecond};
+use crate::math::units::time::Millisecond;
+use approx::assert_relative_eq;
+use nalgebra::{Matrix1, Matrix1x2, Matrix2, Vector1, Vector2};
 
 #[test]
 fn bang_bang() {
-    let mut controller = BangBangController::new();
+    let mut controller: BangBangController = BangBangController::new();
 
     controller.set_set_point(0.3);
 
@@ -11,9 +25,513 @@ fn bang_bang() {
 
 #[test]
 fn pid() {
-    let mut controller = PIDController::new(0.1, 0.2, 0.3);
+    let mut controller: PIDController = PIDController::new(0.1, 0.2, 0.3);
 
     controller.set_set_point(0.3);
 
-    assert_eq!(controller.calculate(0.2, 20), 0.21150000000000002);
+    // the first call has no derivative kick
+    assert_relative_eq!(
+        controller.calculate(0.2, 0.02),
+        0.1 * 0.1 + 0.2 * 0.1 * 0.02,
+        epsilon = 1e-12
+    );
+}
+
+#[test]
+fn pid_units() {
+    let mut controller: PIDController<Radian, Volt> = PIDController::new(6.0, 0.0, 0.0);
+    controller.set_limits(
+        Radian::new(-10.0),
+        Radian::new(10.0),
+        Volt::new(-12.0),
+        Volt::new(12.0),
+    );
+    controller.set_set_point(Radian::new(1.0));
+
+    // a measurement in another angle unit is converted, and the period can be in milliseconds
+    let output = controller.calculate(Degree::new(90.0), Millisecond::new(20.0));
+    assert_relative_eq!(
+        output.value(),
+        6.0 * (1.0 - std::f64::consts::FRAC_PI_2),
+        epsilon = 1e-9
+    );
+    assert_relative_eq!(
+        controller.get_position_error().value(),
+        1.0 - std::f64::consts::FRAC_PI_2,
+        epsilon = 1e-9
+    );
+    assert_relative_eq!(controller.get_limits().3.value(), 12.0);
+
+    // without limits neither the output nor the integral term is clamped to ±1 V, so this is
+    // 6 V/rad × 3 rad plus 1 V/(rad·s) × 3 rad·s
+    let mut controller: PIDController<Radian, Volt> = PIDController::new(6.0, 1.0, 0.0);
+    controller.set_set_point(Radian::new(3.0));
+    let output = controller.calculate(Radian::new(0.0), 1.0);
+    assert_relative_eq!(output.value(), 21.0, epsilon = 1e-9);
+
+    // the velocity tolerance and error are per second of the input unit; 3 rad is about 171.9°
+    controller.set_tolerance_with_velocity(Degree::new(1.0), DegreePerSecond::new(0.5));
+    assert_relative_eq!(
+        controller.get_velocity_tolerance().value(),
+        0.5_f64.to_radians(),
+        epsilon = 1e-9
+    );
+    controller.calculate(Degree::new(170.0), 1.0);
+    let velocity_error: RadianPerSecond = controller.get_velocity_error();
+    assert_relative_eq!(
+        velocity_error.value(),
+        -170.0_f64.to_radians(),
+        epsilon = 1e-9
+    );
+    controller.calculate(Degree::new(171.0), 1.0);
+    assert!(!controller.at_setpoint());
+    controller.calculate(Degree::new(171.0), 1.0);
+    assert!(controller.at_setpoint());
+}
+
+#[test]
+fn pid_proportional_gain_output() {
+    let mut controller: PIDController = PIDController::new(4.0, 0.0, 0.0);
+    controller.set_set_point(0.0);
+    assert_relative_eq!(controller.calculate(0.025, 0.02), -0.1, epsilon = 1e-5);
+}
+
+#[test]
+fn pid_integral_gain_output() {
+    let mut controller: PIDController = PIDController::new(0.0, 4.0, 0.0);
+    controller.set_integrator_range(-180.0, 180.0);
+    controller.set_set_point(0.0);
+
+    let mut out = 0.0;
+    for _ in 0..5 {
+        out = controller.calculate(0.025, 0.02);
+    }
+    assert_relative_eq!(out, -0.5 * 0.02, epsilon = 1e-5);
+}
+
+#[test]
+fn pid_derivative_gain_output() {
+    let mut controller: PIDController = PIDController::new(0.0, 0.0, 4.0);
+    controller.set_set_point(0.0);
+
+    assert_relative_eq!(controller.calculate(0.0, 0.02), 0.0);
+    assert_relative_eq!(
+        controller.calculate(0.0025, 0.02),
+        -0.01 / 0.02,
+        epsilon = 1e-5
+    );
+}
+
+#[test]
+fn pid_derivative_filter() {
+    let mut controller: PIDController = PIDController::new(0.0, 0.0, 1.0);
+    controller.set_limits(-100.0, 100.0, -100.0, 100.0);
+    controller.set_derivative_filter(0.1);
+    controller.set_set_point(0.0);
+
+    // a step in the measurement is spread over several loops instead of one spike
+    controller.calculate(0.0, 0.02);
+    let first = controller.calculate(1.0, 0.02);
+    let raw = -1.0 / 0.02;
+    assert_relative_eq!(first, raw * (1.0 - (-0.2f64).exp()), epsilon = 1e-9);
+    let second = controller.calculate(1.0, 0.02);
+    assert!(second < 0.0 && second > first);
+
+    controller.disable_derivative_filter();
+    controller.calculate(1.0, 0.02);
+    assert_relative_eq!(controller.calculate(2.0, 0.02), raw, epsilon = 1e-9);
+}
+
+#[test]
+fn pid_i_zone() {
+    let mut controller: PIDController = PIDController::new(0.0, 1.0, 0.0);
+    controller.set_i_zone(1.0);
+    controller.set_set_point(0.0);
+
+    // outside the zone the accumulated error is cleared
+    assert_relative_eq!(controller.calculate(2.0, 0.02), 0.0);
+    assert_relative_eq!(controller.calculate(1.0, 0.02), -0.02, epsilon = 1e-9);
+    assert_relative_eq!(controller.calculate(2.0, 0.02), 0.0);
+}
+
+#[test]
+#[should_panic(expected = "IZone must be a non-negative number")]
+fn pid_negative_i_zone() {
+    PIDController::<f64, f64>::new(0.0, 1.0, 0.0).set_i_zone(-1.0);
+}
+
+#[test]
+fn pid_continuous_input() {
+    let mut controller: PIDController = PIDController::new(1.0, 0.0, 0.0);
+    controller.set_limits(-180.0, 180.0, -360.0, 360.0);
+    controller.enable_continuous_input(-180.0, 180.0);
+    assert!(controller.is_continuous_input_enabled());
+
+    controller.set_set_point(179.0);
+    assert!(controller.calculate(-179.0, 0.02) < 0.0);
+    assert_relative_eq!(controller.get_position_error(), -2.0, epsilon = 1e-9);
+
+    // the error never exceeds half the input range
+    controller.enable_continuous_input(0.0, 360.0);
+    controller.set_set_point(359.0);
+    assert!(controller.calculate(1.0, 0.02) < 0.0);
+    controller.set_set_point(1.0);
+    assert!(controller.calculate(359.0, 0.02) > 0.0);
+    assert!(controller.get_position_error().abs() <= 180.0);
+
+    controller.disable_continuous_input();
+    controller.set_set_point(179.0);
+    assert!(controller.calculate(-179.0, 0.02) > 0.0);
+}
+
+#[test]
+fn pid_input_range() {
+    let mut controller: PIDController = PIDController::new(1.0, 0.0, 0.0);
+    controller.set_limits(-1.0, 1.0, -10.0, 10.0);
+    controller.set_set_point(0.0);
+
+    // measurements outside the input range are clamped to it
+    assert_relative_eq!(controller.calculate(5.0, 0.02), -1.0);
+}
+
+#[test]
+fn pid_tolerance() {
+    const SET_POINT: f64 = 50.0;
+    const TOLERANCE: f64 = 10.0;
+    const RANGE: f64 = 200.0;
+
+    let mut controller: PIDController = PIDController::new(0.05, 0.0, 0.0);
+    controller.enable_continuous_input(-RANGE / 2.0, RANGE / 2.0);
+    assert!(!controller.at_setpoint());
+
+    controller.set_tolerance(TOLERANCE);
+    controller.set_set_point(SET_POINT);
+    assert!(!controller.at_setpoint());
+
+    controller.calculate(0.0, 0.02);
+    assert!(!controller.at_setpoint());
+
+    controller.calculate(SET_POINT + TOLERANCE / 2.0, 0.02);
+    assert!(controller.at_setpoint());
+
+    controller.calculate(SET_POINT + 10.0 * TOLERANCE, 0.02);
+    assert!(!controller.at_setpoint());
+
+    // a set point change is reflected without a new measurement
+    controller.set_set_point(SET_POINT + 10.0 * TOLERANCE);
+    assert!(controller.at_setpoint());
+
+    controller.reset();
+    assert!(!controller.at_setpoint());
+}
+
+#[test]
+fn pid_velocity_tolerance() {
+    let mut controller: PIDController = PIDController::new(0.05, 0.0, 0.0);
+    controller.set_tolerance_with_velocity(1.0, 5.0);
+    controller.set_set_point(10.0);
+
+    controller.calculate(5.0, 0.02);
+    controller.calculate(10.0, 0.02);
+    assert!(!controller.at_setpoint());
+    controller.calculate(10.0, 0.02);
+    assert!(controller.at_setpoint());
+}
+
+#[test]
+fn profiled_pid() {
+    let constraints = TrapezoidProfileConstraints::new(1.0, 2.0);
+    let mut controller: ProfiledPIDController =
+        ProfiledPIDController::new(1.0, 0.0, 0.0, constraints);
+    controller.set_limits(-10.0, 10.0, -10.0, 10.0);
+    controller.reset_to_position(0.0);
+    controller.set_set_point(2.0);
+    assert_relative_eq!(controller.get_set_point(), 2.0);
+
+    // a mechanism that follows the profiled set point exactly
+    let mut position = 0.0;
+    for _ in 0..200 {
+        controller.calculate(position, 0.02);
+        let setpoint = controller.get_setpoint();
+        assert!(setpoint.velocity <= constraints.max_velocity + 1e-9);
+        position = setpoint.position;
+    }
+    assert_eq!(
+        controller.get_setpoint(),
+        TrapezoidProfileState::new(2.0, 0.0)
+    );
+    assert_relative_eq!(controller.calculate(position, 0.02), 0.0);
+    assert!(controller.at_goal());
+
+    // the profile restarts from the measured state
+    controller.reset_to(TrapezoidProfileState::new(1.0, -0.5));
+    controller.calculate(1.0, 0.02);
+    assert!(controller.get_setpoint().position < 1.0);
+}
+
+#[test]
+fn profiled_pid_continuous_input() {
+    let constraints = TrapezoidProfileConstraints::new(90.0, 180.0);
+    let mut controller: ProfiledPIDController =
+        ProfiledPIDController::new(0.01, 0.0, 0.0, constraints);
+    controller.enable_continuous_input(-180.0, 180.0);
+    assert!(controller.is_continuous_input_enabled());
+
+    // from 170° the shorter way to −170° is through 180°
+    controller.reset_to_position(170.0);
+    controller.set_goal_position(-170.0);
+    assert!(controller.calculate(170.0, 0.02) > 0.0);
+    assert!(controller.get_setpoint().position > 170.0);
+    assert!(controller.get_setpoint().velocity > 0.0);
+    assert!(!controller.at_goal());
+
+    controller.disable_continuous_input();
+    controller.reset_to_position(170.0);
+    controller.set_goal_position(-170.0);
+    assert!(controller.calculate(170.0, 0.02) < 0.0);
+}
+
+#[test]
+fn lqr() {
+    // a discretized integrator, x_k+1 = x_k + u_k, has S² − S − 1 = 0 for unit costs
+    let golden_ratio = (1.0 + 5.0f64.sqrt()) / 2.0;
+    let controller = LinearQuadraticRegulator::new_qr(
+        &Matrix1::new(0.0),
+        &Matrix1::new(1.0),
+        &Matrix1::new(1.0),
+        &Matrix1::new(1.0),
+        1.0,
+    );
+    assert_relative_eq!(
+        controller.get_k()[0],
+        golden_ratio / (golden_ratio + 1.0),
+        epsilon = 1e-9
+    );
+
+    // a zero cross-term changes nothing
+    let with_cross_term = LinearQuadraticRegulator::new_qrn(
+        &Matrix1::new(0.0),
+        &Matrix1::new(1.0),
+        &Matrix1::new(1.0),
+        &Matrix1::new(1.0),
+        &Matrix1::new(0.0),
+        1.0,
+    );
+    assert_relative_eq!(
+        *with_cross_term.get_k(),
+        *controller.get_k(),
+        epsilon = 1e-9
+    );
+
+    let plant = LinearSystemId::create_elevator_system(DCMotor::new_neo(2), 5.0, 0.05, 12.0);
+    let mut controller =
+        LinearQuadraticRegulator::new(&plant, Vector2::new(0.02, 0.4), Vector1::new(12.0), 0.02);
+    let k = *controller.get_k();
+    assert!(k[0] > 0.0 && k[1] > 0.0);
+
+    let u = controller.calculate_with_r(&Vector2::new(0.5, 0.0), &Vector2::new(1.0, 0.0));
+    assert_relative_eq!(u[0], k[0] * 0.5);
+    assert_eq!(*controller.get_u(), u);
+    controller.reset();
+    assert_eq!(*controller.get_r(), Vector2::zeros());
+    assert_eq!(*controller.get_u(), Vector1::zeros());
+}
+
+#[test]
+fn lqr_latency_compensate() {
+    let plant = LinearSystemId::identify_position_system(0.0, 1.0);
+    let integrator = LinearSystemId::identify_velocity_system(0.0, 1.0);
+
+    let mut controller = LinearQuadraticRegulator::new_qr(
+        &Matrix1::new(0.0),
+        &Matrix1::new(1.0),
+        &Matrix1::new(1.0),
+        &Matrix1::new(1.0),
+        1.0,
+    );
+    let k = controller.get_k()[0];
+    // K (A − BK)^½ with A = B = 1
+    controller.latency_compensate(&integrator, 1.0, 0.5);
+    assert_relative_eq!(controller.get_k()[0], k * (1.0 - k).sqrt(), epsilon = 1e-9);
+
+    let uncompensated =
+        LinearQuadraticRegulator::new(&plant, Vector2::new(0.1, 1.0), Vector1::new(12.0), 0.02);
+    let mut controller = uncompensated;
+    controller.latency_compensate(&plant, 0.02, 0.0);
+    assert_relative_eq!(*controller.get_k(), *uncompensated.get_k(), epsilon = 1e-9);
+
+    // a whole period of delay looks one step further ahead
+    controller.latency_compensate(&plant, 0.02, 0.02);
+    let (a, b) = Discretization::discretize_ab(plant.get_a(), plant.get_b(), 0.02);
+    let k = *uncompensated.get_k();
+    assert_relative_eq!(*controller.get_k(), k * (a - b * k), epsilon = 1e-9);
+}
+
+#[test]
+fn linear_plant_inversion_feedforward() {
+    let plant = LinearSystem::new(
+        Matrix2::identity(),
+        Vector2::new(0.0, 1.0),
+        Matrix1x2::new(1.0, 0.0),
+        Matrix1::zeros(),
+    );
+    let mut feedforward = LinearPlantInversionFeedforward::new(&plant, 0.02);
+
+    let uff = feedforward.calculate_with_r(&Vector2::new(2.0, 2.0), &Vector2::new(3.0, 3.0));
+    assert_relative_eq!(uff[0], 47.502_599, epsilon = 0.002);
+    assert_eq!(*feedforward.get_r(), Vector2::new(3.0, 3.0));
+
+    // holding the reference only needs to cancel the growth of the second state
+    let uff = feedforward.calculate(&Vector2::new(3.0, 3.0));
+    assert_relative_eq!(uff[0], -3.0, epsilon = 1e-9);
+
+    feedforward.reset(&Vector2::new(1.0, 1.0));
+    assert_eq!(*feedforward.get_uff(), Vector1::zeros());
+}
+
+#[test]
+fn lqr_elevator() {
+    // gains from WPILib's reference tests
+    let vex_775_pro = |motors| {
+        DCMotor::new(
+            12.0,
+            0.71,
+            134.0,
+            0.7,
+            RotationPerMinute::new(18_730.0),
+            motors,
+        )
+    };
+
+    let plant = LinearSystemId::create_elevator_system(vex_775_pro(2), 5.0, 0.018_186_4, 1.0);
+    let controller =
+        LinearQuadraticRegulator::new(&plant, Vector2::new(0.02, 0.4), Vector1::new(12.0), 0.00505);
+    assert_relative_eq!(controller.get_k()[0], 522.153_142_69, epsilon = 1e-6);
+    assert_relative_eq!(controller.get_k()[1], 38.201_385_96, epsilon = 1e-6);
+
+    let plant =
+        LinearSystemId::create_elevator_system(vex_775_pro(4), 8.0, 0.75 * 25.4 / 1000.0, 14.67);
+    let controller =
+        LinearQuadraticRegulator::new(&plant, Vector2::new(0.1, 0.2), Vector1::new(12.0), 0.020);
+    assert_relative_eq!(controller.get_k()[0], 10.381, epsilon = 1e-2);
+    assert_relative_eq!(controller.get_k()[1], 0.6929, epsilon = 1e-2);
+}
+
+#[test]
+fn simple_feedforward() {
+    let feedforward: Simple = Simple::new(0.5, 3.0, 0.6);
+    assert_relative_eq!(feedforward.v_a_calculate(2.0, 1.0).value(), 7.1);
+    assert_relative_eq!(feedforward.v_calculate(-2.0).value(), -6.5);
+    assert_relative_eq!(
+        feedforward.max_velocity(12.0, 1.0).value(),
+        11.5 / 3.0 - 0.2,
+        epsilon = 1e-9
+    );
+    assert_relative_eq!(
+        feedforward.max_acceleration(12.0, 2.0).value(),
+        5.5 / 0.6,
+        epsilon = 1e-9
+    );
+
+    // from WPILib's reference tests
+    let calculated = feedforward.calculate(2.0, 3.0, 0.02);
+    assert_relative_eq!(
+        calculated.value(),
+        37.524_995_834_325_16 + 0.5,
+        epsilon = 0.002
+    );
+    // friction doesn't push a motor that stays at rest
+    assert_relative_eq!(feedforward.calculate(0.0, 0.0, 0.02).value(), 0.0);
+
+    let plant = LinearSystemId::identify_velocity_system(3.0, 0.6);
+    let mut plant_inversion = LinearPlantInversionFeedforward::new(&plant, 0.02);
+    let uff = plant_inversion.calculate_with_r(&Vector1::new(2.0), &Vector1::new(3.0));
+    assert_relative_eq!(calculated.value(), uff[0] + 0.5, epsilon = 1e-9);
+
+    // constant acceleration over the step lands in the same ballpark
+    assert_relative_eq!(
+        calculated.value(),
+        feedforward.v_a_calculate(2.0, 1.0 / 0.02).value(),
+        epsilon = 2.0
+    );
+}
+
+#[test]
+fn simple_feedforward_no_acceleration_gain() {
+    let feedforward: Simple = Simple::new(0.5, 3.0, 0.0);
+    assert_relative_eq!(feedforward.calculate(2.0, 3.0, 0.02).value(), 9.5);
+    assert_relative_eq!(feedforward.calculate(1.0, 0.0, 0.02).value(), 0.0);
+
+    let feedforward: Static = Static::new(3.0, 0.0);
+    assert_relative_eq!(feedforward.calculate(2.0, -3.0, 0.02).value(), -9.0);
+}
+
+#[test]
+fn elevator_feedforward() {
+    let feedforward: Elevator = Elevator::new(0.5, 1.0, 1.5, 2.0);
+    assert_relative_eq!(
+        feedforward
+            .v_a_calculate(MeterPerSecond::new(2.0), 1.0)
+            .value(),
+        6.5
+    );
+    assert_relative_eq!(feedforward.v_calculate(-2.0).value(), -2.5);
+    assert_relative_eq!(
+        feedforward
+            .max_velocity(12.0, MeterPerSecondSquared::new(0.0))
+            .value(),
+        7.0
+    );
+    assert_relative_eq!(
+        feedforward
+            .min_velocity(11.5, MeterPerSecondSquared::new(0.0))
+            .value(),
+        -8.0
+    );
+
+    let plant = LinearSystemId::identify_velocity_system(1.5, 2.0);
+    let mut plant_inversion = LinearPlantInversionFeedforward::new(&plant, 0.02);
+    let uff = plant_inversion.calculate_with_r(&Vector1::new(2.0), &Vector1::new(3.0));
+    assert_relative_eq!(
+        feedforward.calculate(2.0, 3.0, 0.02).value(),
+        uff[0] + 0.5 + 1.0,
+        epsilon = 1e-9
+    );
+    // holding still only takes holding up against gravity
+    assert_relative_eq!(feedforward.calculate(0.0, 0.0, 0.02).value(), 1.0);
+
+    // other linear units convert to meters per second
+    assert_relative_eq!(
+        feedforward
+            .calculate(FeetPerSecond::new(2.0 / 0.3048), 3.0, 0.02)
+            .value(),
+        uff[0] + 0.5 + 1.0,
+        epsilon = 1e-6
+    );
+
+    // gains identified in radians work too, such as for an elevator driven by a spool
+    let feedforward: Elevator<RadianPerSecond> = Elevator::new(0.5, 1.0, 1.5, 2.0);
+    assert_relative_eq!(feedforward.v_calculate(2.0).value(), 4.5);
+}
+
+#[test]
+fn arm_feedforward() {
+    let feedforward = Arm::new(0.5, 1.0, 1.5, 2.0);
+    assert_relative_eq!(feedforward.p_v_a_calculate(0.0, 1.0, 1.0).value(), 5.0);
+    assert_relative_eq!(
+        feedforward.p_v_calculate(Degree::new(90.0), 1.0).value(),
+        2.0,
+        epsilon = 1e-9
+    );
+
+    let plant = LinearSystemId::identify_velocity_system(1.5, 2.0);
+    let mut plant_inversion = LinearPlantInversionFeedforward::new(&plant, 0.02);
+    let uff = plant_inversion.calculate_with_r(&Vector1::new(1.0), &Vector1::new(1.5));
+    assert_relative_eq!(
+        feedforward
+            .calculate(Degree::new(60.0), 1.0, 1.5, 0.02)
+            .value(),
+        uff[0] + 0.5 + 0.5,
+        epsilon = 1e-9
+    );
 }
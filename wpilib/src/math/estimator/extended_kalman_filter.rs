@@ -0,0 +1,220 @@
+use std::fmt::Debug;
+
+use nalgebra::{SMatrix, SVector};
+
+use crate::math::system::{Discretization, NumericalIntegration, NumericalJacobian};
+use crate::math::units::time::Second;
+use crate::math::util::state_space_util::StateSpaceUtil;
+
+use super::KalmanTypeFilter;
+
+type DynamicsFunction<const STATES: usize, const INPUTS: usize> =
+    Box<dyn Fn(&SVector<f64, STATES>, &SVector<f64, INPUTS>) -> SVector<f64, STATES> + Send + Sync>;
+type MeasurementFunction<const STATES: usize, const INPUTS: usize, const OUTPUTS: usize> = Box<
+    dyn Fn(&SVector<f64, STATES>, &SVector<f64, INPUTS>) -> SVector<f64, OUTPUTS> + Send + Sync,
+>;
+type VectorFunction<const N: usize> =
+    Box<dyn Fn(&SVector<f64, N>, &SVector<f64, N>) -> SVector<f64, N> + Send + Sync>;
+
+/// A Kalman filter for a nonlinear plant `dx/dt = f(x, u)`, `y = h(x, u)`, which linearizes the
+/// model around the state estimate with numerical Jacobians every step.
+pub struct ExtendedKalmanFilter<const STATES: usize, const INPUTS: usize, const OUTPUTS: usize> {
+    f: DynamicsFunction<STATES, INPUTS>,
+    h: MeasurementFunction<STATES, INPUTS, OUTPUTS>,
+    residual_func_y: VectorFunction<OUTPUTS>,
+    add_func_x: VectorFunction<STATES>,
+    cont_q: SMatrix<f64, STATES, STATES>,
+    cont_r: SMatrix<f64, OUTPUTS, OUTPUTS>,
+    dt: Second,
+    xhat: SVector<f64, STATES>,
+    p: SMatrix<f64, STATES, STATES>,
+}
+
+impl<const STATES: usize, const INPUTS: usize, const OUTPUTS: usize>
+    ExtendedKalmanFilter<STATES, INPUTS, OUTPUTS>
+{
+    /// Creates a filter that runs every `dt`, starting with zero error covariance.
+    #[must_use]
+    pub fn new<F, H>(
+        f: F,
+        h: H,
+        state_std_devs: SVector<f64, STATES>,
+        measurement_std_devs: SVector<f64, OUTPUTS>,
+        dt: impl Into<Second>,
+    ) -> Self
+    where
+        F: Fn(&SVector<f64, STATES>, &SVector<f64, INPUTS>) -> SVector<f64, STATES>
+            + Send
+            + Sync
+            + 'static,
+        H: Fn(&SVector<f64, STATES>, &SVector<f64, INPUTS>) -> SVector<f64, OUTPUTS>
+            + Send
+            + Sync
+            + 'static,
+    {
+        Self {
+            f: Box::new(f),
+            h: Box::new(h),
+            residual_func_y: Box::new(|a, b| a - b),
+            add_func_x: Box::new(|a, b| a + b),
+            cont_q: StateSpaceUtil::make_covariance_matrix(&state_std_devs),
+            cont_r: StateSpaceUtil::make_covariance_matrix(&measurement_std_devs),
+            dt: dt.into(),
+            xhat: SVector::zeros(),
+            p: SMatrix::zeros(),
+        }
+    }
+
+    /// Replaces subtraction as the way to find the difference between two measurements, such as
+    /// when a measurement is an angle that wraps around.
+    #[must_use]
+    pub fn with_residual_func_y<F>(mut self, residual_func_y: F) -> Self
+    where
+        F: Fn(&SVector<f64, OUTPUTS>, &SVector<f64, OUTPUTS>) -> SVector<f64, OUTPUTS>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.residual_func_y = Box::new(residual_func_y);
+        self
+    }
+
+    /// Replaces addition as the way to apply a correction to the state estimate, such as when a
+    /// state is an angle that wraps around.
+    #[must_use]
+    pub fn with_add_func_x<F>(mut self, add_func_x: F) -> Self
+    where
+        F: Fn(&SVector<f64, STATES>, &SVector<f64, STATES>) -> SVector<f64, STATES>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.add_func_x = Box::new(add_func_x);
+        self
+    }
+
+    #[must_use]
+    pub fn get_xhat_at(&self, row: usize) -> f64 {
+        self.xhat[row]
+    }
+
+    pub fn set_xhat_at(&mut self, row: usize, value: f64) {
+        self.xhat[row] = value;
+    }
+
+    /// Corrects the state estimate with a measurement `y = h(x, u)` other than the one the
+    /// filter was created with, whose continuous noise covariance is `cont_r`.
+    ///
+    /// # Panics
+    /// Panics if the innovation covariance isn't positive definite.
+    pub fn correct_with<const ROWS: usize>(
+        &mut self,
+        u: &SVector<f64, INPUTS>,
+        y: &SVector<f64, ROWS>,
+        h: impl Fn(&SVector<f64, STATES>, &SVector<f64, INPUTS>) -> SVector<f64, ROWS>,
+        cont_r: &SMatrix<f64, ROWS, ROWS>,
+    ) {
+        self.correct_with_residual(u, y, h, cont_r, |a, b| a - b);
+    }
+
+    /// Corrects the state estimate with a measurement `y = h(x, u)` other than the one the
+    /// filter was created with, finding the difference between measurements with
+    /// `residual_func_y`.
+    ///
+    /// # Panics
+    /// Panics if the innovation covariance isn't positive definite.
+    pub fn correct_with_residual<const ROWS: usize>(
+        &mut self,
+        u: &SVector<f64, INPUTS>,
+        y: &SVector<f64, ROWS>,
+        h: impl Fn(&SVector<f64, STATES>, &SVector<f64, INPUTS>) -> SVector<f64, ROWS>,
+        cont_r: &SMatrix<f64, ROWS, ROWS>,
+        residual_func_y: impl Fn(&SVector<f64, ROWS>, &SVector<f64, ROWS>) -> SVector<f64, ROWS>,
+    ) {
+        let (xhat, p) = self.corrected(u, y, h, cont_r, residual_func_y);
+        self.xhat = xhat;
+        self.p = p;
+    }
+
+    fn corrected<const ROWS: usize>(
+        &self,
+        u: &SVector<f64, INPUTS>,
+        y: &SVector<f64, ROWS>,
+        h: impl Fn(&SVector<f64, STATES>, &SVector<f64, INPUTS>) -> SVector<f64, ROWS>,
+        cont_r: &SMatrix<f64, ROWS, ROWS>,
+        residual_func_y: impl Fn(&SVector<f64, ROWS>, &SVector<f64, ROWS>) -> SVector<f64, ROWS>,
+    ) -> (SVector<f64, STATES>, SMatrix<f64, STATES, STATES>) {
+        let c = NumericalJacobian::numerical_jacobian_x(&h, &self.xhat, u);
+        let discrete_r = Discretization::discretize_r(cont_r, self.dt);
+
+        // K = PCᵀS⁻¹, solved as Kᵀ = S⁻¹CPᵀ since S is symmetric
+        let s = c * self.p * c.transpose() + discrete_r;
+        let k = s
+            .cholesky()
+            .expect("Innovation covariance must be positive definite")
+            .solve(&(c * self.p.transpose()))
+            .transpose();
+
+        let xhat = (self.add_func_x)(&self.xhat, &(k * residual_func_y(y, &h(&self.xhat, u))));
+
+        // Joseph form keeps P symmetric positive definite
+        let i_kc = SMatrix::<f64, STATES, STATES>::identity() - k * c;
+        let p = i_kc * self.p * i_kc.transpose() + k * discrete_r * k.transpose();
+
+        (xhat, p)
+    }
+}
+
+impl<const STATES: usize, const INPUTS: usize, const OUTPUTS: usize>
+    KalmanTypeFilter<STATES, INPUTS, OUTPUTS> for ExtendedKalmanFilter<STATES, INPUTS, OUTPUTS>
+{
+    fn get_p(&self) -> &SMatrix<f64, STATES, STATES> {
+        &self.p
+    }
+
+    fn set_p(&mut self, p: SMatrix<f64, STATES, STATES>) {
+        self.p = p;
+    }
+
+    fn get_xhat(&self) -> &SVector<f64, STATES> {
+        &self.xhat
+    }
+
+    fn set_xhat(&mut self, xhat: SVector<f64, STATES>) {
+        self.xhat = xhat;
+    }
+
+    fn reset(&mut self) {
+        self.xhat = SVector::zeros();
+        self.p = SMatrix::zeros();
+    }
+
+    fn predict(&mut self, u: &SVector<f64, INPUTS>, dt: impl Into<Second>) {
+        let dt: Second = dt.into();
+        let cont_a = NumericalJacobian::numerical_jacobian_x(&self.f, &self.xhat, u);
+        let (discrete_a, discrete_q) = Discretization::discretize_aq(&cont_a, &self.cont_q, dt);
+
+        self.xhat = NumericalIntegration::rk4(&self.f, &self.xhat, u, dt);
+        self.p = discrete_a * self.p * discrete_a.transpose() + discrete_q;
+    }
+
+    fn correct(&mut self, u: &SVector<f64, INPUTS>, y: &SVector<f64, OUTPUTS>) {
+        let (xhat, p) = self.corrected(u, y, &self.h, &self.cont_r, &self.residual_func_y);
+        self.xhat = xhat;
+        self.p = p;
+    }
+}
+
+impl<const STATES: usize, const INPUTS: usize, const OUTPUTS: usize> Debug
+    for ExtendedKalmanFilter<STATES, INPUTS, OUTPUTS>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExtendedKalmanFilter")
+            .field("cont_q", &self.cont_q)
+            .field("cont_r", &self.cont_r)
+            .field("dt", &self.dt)
+            .field("xhat", &self.xhat)
+            .field("p", &self.p)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,142 @@
+use nalgebra::{SMatrix, SVector};
+
+use crate::math::system::{Discretization, LinearSystem};
+use crate::math::units::time::Second;
+use crate::math::util::dare::Dare;
+use crate::math::util::state_space_util::StateSpaceUtil;
+
+use super::KalmanTypeFilter;
+
+/// A Kalman filter for a linear plant.
+///
+/// The error covariance starts at the steady-state solution for the nominal period, so the
+/// filter applies the steady-state gain from its first measurement instead of taking time to
+/// converge.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct KalmanFilter<const STATES: usize, const INPUTS: usize, const OUTPUTS: usize> {
+    plant: LinearSystem<STATES, INPUTS, OUTPUTS>,
+    cont_q: SMatrix<f64, STATES, STATES>,
+    cont_r: SMatrix<f64, OUTPUTS, OUTPUTS>,
+    dt: Second,
+    init_p: SMatrix<f64, STATES, STATES>,
+    xhat: SVector<f64, STATES>,
+    p: SMatrix<f64, STATES, STATES>,
+}
+
+impl<const STATES: usize, const INPUTS: usize, const OUTPUTS: usize>
+    KalmanFilter<STATES, INPUTS, OUTPUTS>
+{
+    /// Creates a filter that runs every `dt`.
+    ///
+    /// # Panics
+    /// Panics if the plant isn't detectable or a measurement standard deviation isn't positive.
+    #[must_use]
+    pub fn new(
+        plant: LinearSystem<STATES, INPUTS, OUTPUTS>,
+        state_std_devs: SVector<f64, STATES>,
+        measurement_std_devs: SVector<f64, OUTPUTS>,
+        dt: impl Into<Second>,
+    ) -> Self {
+        let dt: Second = dt.into();
+        let cont_q = StateSpaceUtil::make_covariance_matrix(&state_std_devs);
+        let cont_r = StateSpaceUtil::make_covariance_matrix(&measurement_std_devs);
+
+        let (discrete_a, discrete_q) = Discretization::discretize_aq(plant.get_a(), &cont_q, dt);
+        let discrete_r = Discretization::discretize_r(&cont_r, dt);
+
+        // the estimation problem is the dual of the control problem
+        let init_p = Dare::dare(
+            &discrete_a.transpose(),
+            &plant.get_c().transpose(),
+            &discrete_q,
+            &discrete_r,
+        );
+
+        Self {
+            plant,
+            cont_q,
+            cont_r,
+            dt,
+            init_p,
+            xhat: SVector::zeros(),
+            p: init_p,
+        }
+    }
+
+    #[must_use]
+    pub fn get_xhat_at(&self, row: usize) -> f64 {
+        self.xhat[row]
+    }
+
+    pub fn set_xhat_at(&mut self, row: usize, value: f64) {
+        self.xhat[row] = value;
+    }
+
+    /// Corrects the state estimate with measurement `y`, using the continuous measurement noise
+    /// covariance `cont_r` instead of the one the filter was created with.
+    ///
+    /// # Panics
+    /// Panics if the innovation covariance isn't positive definite.
+    pub fn correct_with_r(
+        &mut self,
+        u: &SVector<f64, INPUTS>,
+        y: &SVector<f64, OUTPUTS>,
+        cont_r: &SMatrix<f64, OUTPUTS, OUTPUTS>,
+    ) {
+        let c = self.plant.get_c();
+        let discrete_r = Discretization::discretize_r(cont_r, self.dt);
+
+        // K = PCᵀS⁻¹, solved as Kᵀ = S⁻¹CPᵀ since S is symmetric
+        let s = c * self.p * c.transpose() + discrete_r;
+        let k = s
+            .cholesky()
+            .expect("Innovation covariance must be positive definite")
+            .solve(&(c * self.p.transpose()))
+            .transpose();
+
+        self.xhat += k * (y - self.plant.calculate_y(&self.xhat, u));
+
+        // Joseph form keeps P symmetric positive definite
+        let i_kc = SMatrix::<f64, STATES, STATES>::identity() - k * c;
+        self.p = i_kc * self.p * i_kc.transpose() + k * discrete_r * k.transpose();
+    }
+}
+
+impl<const STATES: usize, const INPUTS: usize, const OUTPUTS: usize>
+    KalmanTypeFilter<STATES, INPUTS, OUTPUTS> for KalmanFilter<STATES, INPUTS, OUTPUTS>
+{
+    fn get_p(&self) -> &SMatrix<f64, STATES, STATES> {
+        &self.p
+    }
+
+    fn set_p(&mut self, p: SMatrix<f64, STATES, STATES>) {
+        self.p = p;
+    }
+
+    fn get_xhat(&self) -> &SVector<f64, STATES> {
+        &self.xhat
+    }
+
+    fn set_xhat(&mut self, xhat: SVector<f64, STATES>) {
+        self.xhat = xhat;
+    }
+
+    fn reset(&mut self) {
+        self.xhat = SVector::zeros();
+        self.p = self.init_p;
+    }
+
+    fn predict(&mut self, u: &SVector<f64, INPUTS>, dt: impl Into<Second>) {
+        let dt: Second = dt.into();
+        let (discrete_a, discrete_q) =
+            Discretization::discretize_aq(self.plant.get_a(), &self.cont_q, dt);
+
+        self.xhat = self.plant.calculate_x(&self.xhat, u, dt);
+        self.p = discrete_a * self.p * discrete_a.transpose() + discrete_q;
+    }
+
+    fn correct(&mut self, u: &SVector<f64, INPUTS>, y: &SVector<f64, OUTPUTS>) {
+        let cont_r = self.cont_r;
+        self.correct_with_r(u, y, &cont_r);
+    }
+}
@@ -0,0 +1,29 @@
+use nalgebra::{SMatrix, SVector};
+
+use crate::math::units::time::Second;
+
+/// A state observer that fuses model predictions with noisy measurements.
+///
+/// Filters are built from state standard deviations, which describe how much the model is
+/// trusted, and measurement standard deviations, which describe how much the sensors are; larger
+/// values mean less trust.
+pub trait KalmanTypeFilter<const STATES: usize, const INPUTS: usize, const OUTPUTS: usize> {
+    /// Returns the error covariance matrix.
+    fn get_p(&self) -> &SMatrix<f64, STATES, STATES>;
+
+    fn set_p(&mut self, p: SMatrix<f64, STATES, STATES>);
+
+    /// Returns the state estimate.
+    fn get_xhat(&self) -> &SVector<f64, STATES>;
+
+    fn set_xhat(&mut self, xhat: SVector<f64, STATES>);
+
+    /// Zeroes the state estimate and resets the error covariance to its initial value.
+    fn reset(&mut self);
+
+    /// Projects the state estimate forward by `dt` with input `u` applied.
+    fn predict(&mut self, u: &SVector<f64, INPUTS>, dt: impl Into<Second>);
+
+    /// Corrects the state estimate with measurement `y`, taken while input `u` was applied.
+    fn correct(&mut self, u: &SVector<f64, INPUTS>, y: &SVector<f64, OUTPUTS>);
+}
@@ -0,0 +1,92 @@
+use nalgebra::{DMatrix, SMatrix, SVector};
+
+/// Generates sigma points and their weights with Van der Merwe's scaled sigma point algorithm,
+/// for use by an [`UnscentedKalmanFilter`](super::UnscentedKalmanFilter).
+///
+/// `alpha` sets how far the points spread from the mean, `beta` encodes prior knowledge of the
+/// distribution (2 is optimal for a gaussian) and `kappa` is a secondary scaling parameter,
+/// usually `3 − STATES`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MerweScaledSigmaPoints<const STATES: usize> {
+    /// `STATES + λ`, the factor the covariance is scaled by before taking its square root.
+    scale: f64,
+    wm: Vec<f64>,
+    wc: Vec<f64>,
+}
+
+#[allow(clippy::cast_precision_loss)]
+impl<const STATES: usize> Default for MerweScaledSigmaPoints<STATES> {
+    fn default() -> Self {
+        Self::new(1e-3, 2.0, 3.0 - STATES as f64)
+    }
+}
+
+impl<const STATES: usize> MerweScaledSigmaPoints<STATES> {
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn new(alpha: f64, beta: f64, kappa: f64) -> Self {
+        let n = STATES as f64;
+        let scale = alpha.powi(2) * (n + kappa);
+        let lambda = scale - n;
+
+        let mut wm = vec![0.5 / scale; 2 * STATES + 1];
+        let mut wc = wm.clone();
+        wm[0] = lambda / scale;
+        wc[0] = lambda / scale + alpha.mul_add(-alpha, 1.0) + beta;
+
+        Self { scale, wm, wc }
+    }
+
+    /// Returns the number of sigma points, `2 * STATES + 1`.
+    #[must_use]
+    pub const fn get_num_sigmas(&self) -> usize {
+        2 * STATES + 1
+    }
+
+    /// Returns the sigma points for a distribution with mean `x` and covariance `p`.
+    ///
+    /// The first point is the mean, and the rest are spread symmetrically around it along the
+    /// columns of the scaled square root of `p`.
+    #[must_use]
+    pub fn sigma_points(
+        &self,
+        x: &SVector<f64, STATES>,
+        p: &SMatrix<f64, STATES, STATES>,
+    ) -> Vec<SVector<f64, STATES>> {
+        let u = matrix_sqrt(&(p * self.scale));
+
+        let mut sigmas = Vec::with_capacity(self.get_num_sigmas());
+        sigmas.push(*x);
+        sigmas.extend(u.column_iter().map(|column| x + column));
+        sigmas.extend(u.column_iter().map(|column| x - column));
+        sigmas
+    }
+
+    /// Returns the weights for computing the mean of the sigma points.
+    #[must_use]
+    pub fn get_wm(&self) -> &[f64] {
+        &self.wm
+    }
+
+    /// Returns the weights for computing the covariance of the sigma points.
+    #[must_use]
+    pub fn get_wc(&self) -> &[f64] {
+        &self.wc
+    }
+}
+
+/// Returns an `L` with `LLᵀ = m` for a symmetric positive semidefinite `m`.
+fn matrix_sqrt<const N: usize>(m: &SMatrix<f64, N, N>) -> SMatrix<f64, N, N> {
+    if let Some(cholesky) = m.cholesky() {
+        return cholesky.l();
+    }
+
+    // a semidefinite covariance, such as the zero matrix a filter starts with, has no Cholesky
+    // factor, so use the eigendecomposition and drop the negative eigenvalues from rounding
+    let mut dynamic = DMatrix::zeros(N, N);
+    dynamic.copy_from(m);
+    let eigen = dynamic.symmetric_eigen();
+    let root = &eigen.eigenvectors
+        * DMatrix::from_diagonal(&eigen.eigenvalues.map(|value| value.max(0.0).sqrt()));
+    root.fixed_view::<N, N>(0, 0).into_owned()
+}
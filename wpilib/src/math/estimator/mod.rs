@@ -0,0 +1,14 @@
+mod extended_kalman_filter;
+mod kalman_filter;
+mod kalman_type_filter;
+mod merwe_scaled_sigma_points;
+mod pose_estimator;
+mod test;
+mod unscented_kalman_filter;
+
+pub use extended_kalman_filter::*;
+pub use kalman_filter::*;
+pub use kalman_type_filter::*;
+pub use merwe_scaled_sigma_points::*;
+pub use pose_estimator::*;
+pub use unscented_kalman_filter::*;
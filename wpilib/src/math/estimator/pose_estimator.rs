@@ -0,0 +1,193 @@
+use nalgebra::{Matrix3, Vector3};
+
+use crate::math::geometry::{Pose2d, Rotation2d, Twist2d};
+use crate::math::kinematics::{
+    DifferentialDriveKinematics, Kinematics, MecanumDriveKinematics, Odometry,
+    SwerveDriveKinematics,
+};
+use crate::math::units::time::Second;
+use crate::math::util::interpolatable::Interpolatable;
+use crate::math::util::time_interpolatable_buffer::TimeInterpolatableBuffer;
+
+const BUFFER_DURATION: f64 = 1.5;
+
+#[derive(Copy, Clone, Debug)]
+struct InterpolationRecord<P: Interpolatable> {
+    pose: Pose2d,
+    gyro_angle: Rotation2d,
+    wheel_positions: P,
+}
+
+impl<P: Interpolatable> Interpolatable for InterpolationRecord<P> {
+    fn interpolate(&self, end_value: &Self, t: f64) -> Self {
+        Self {
+            pose: self.pose.interpolate(&end_value.pose, t),
+            gyro_angle: self.gyro_angle.interpolate(&end_value.gyro_angle, t),
+            wheel_positions: self
+                .wheel_positions
+                .interpolate(&end_value.wheel_positions, t),
+        }
+    }
+}
+
+/// Fuses drivetrain odometry with latency-delayed vision pose measurements.
+///
+/// Odometry is integrated every loop and recorded in a short pose history. When a vision
+/// measurement arrives, the history is sampled at the measurement's timestamp, the pose at that
+/// time is nudged toward the measurement, and the odometry recorded since is replayed on top.
+///
+/// The state standard deviations weigh the odometry and the vision standard deviations the
+/// vision measurements, as in a [`KalmanTypeFilter`](super::KalmanTypeFilter), with x and y in
+/// meters and the heading in radians.
+#[derive(Clone, Debug)]
+pub struct PoseEstimator<K: Kinematics> {
+    odometry: Odometry<K>,
+    q: Vector3<f64>,
+    vision_k: Matrix3<f64>,
+    pose_buffer: TimeInterpolatableBuffer<InterpolationRecord<K::WheelPositions>>,
+}
+
+pub type DifferentialDrivePoseEstimator = PoseEstimator<DifferentialDriveKinematics>;
+pub type MecanumDrivePoseEstimator = PoseEstimator<MecanumDriveKinematics>;
+pub type SwerveDrivePoseEstimator<const N: usize> = PoseEstimator<SwerveDriveKinematics<N>>;
+
+impl<K: Kinematics> PoseEstimator<K> {
+    #[must_use]
+    pub fn new(
+        kinematics: K,
+        gyro_angle: Rotation2d,
+        wheel_positions: K::WheelPositions,
+        initial_pose: Pose2d,
+        state_std_devs: Vector3<f64>,
+        vision_measurement_std_devs: Vector3<f64>,
+    ) -> Self {
+        let mut estimator = Self {
+            odometry: Odometry::new(kinematics, gyro_angle, wheel_positions, initial_pose),
+            q: state_std_devs.component_mul(&state_std_devs),
+            vision_k: Matrix3::zeros(),
+            pose_buffer: TimeInterpolatableBuffer::new(BUFFER_DURATION),
+        };
+        estimator.set_vision_measurement_std_devs(vision_measurement_std_devs);
+        estimator
+    }
+
+    /// Sets how much vision measurements are trusted from now on.
+    pub fn set_vision_measurement_std_devs(&mut self, vision_measurement_std_devs: Vector3<f64>) {
+        let r = vision_measurement_std_devs.component_mul(&vision_measurement_std_devs);
+
+        // closed form steady-state Kalman gain for a continuous system with A = 0 and C = I
+        self.vision_k = Matrix3::zeros();
+        for row in 0..3 {
+            if self.q[row] != 0.0 {
+                self.vision_k[(row, row)] =
+                    self.q[row] / (self.q[row] + (self.q[row] * r[row]).sqrt());
+            }
+        }
+    }
+
+    /// Resets the robot's pose and forgets all recorded history.
+    ///
+    /// The gyro angle and wheel positions do not need to be reset beforehand.
+    pub fn reset_position(
+        &mut self,
+        gyro_angle: Rotation2d,
+        wheel_positions: K::WheelPositions,
+        pose: Pose2d,
+    ) {
+        self.odometry
+            .reset_position(gyro_angle, wheel_positions, pose);
+        self.pose_buffer.clear();
+    }
+
+    #[must_use]
+    pub const fn get_estimated_position(&self) -> Pose2d {
+        self.odometry.get_pose()
+    }
+
+    /// Integrates odometry recorded at `timestamp`, returning the new estimated pose.
+    ///
+    /// Timestamps must share a timebase with the ones passed to [`Self::add_vision_measurement`].
+    pub fn update_with_time(
+        &mut self,
+        timestamp: impl Into<Second>,
+        gyro_angle: Rotation2d,
+        wheel_positions: K::WheelPositions,
+    ) -> Pose2d {
+        self.odometry.update(gyro_angle, wheel_positions);
+        self.pose_buffer.add_sample(
+            timestamp.into(),
+            InterpolationRecord {
+                pose: self.get_estimated_position(),
+                gyro_angle,
+                wheel_positions,
+            },
+        );
+        self.get_estimated_position()
+    }
+
+    /// Corrects the estimate with a vision pose captured at `timestamp`.
+    ///
+    /// Measurements older than the recorded history are ignored.
+    pub fn add_vision_measurement(
+        &mut self,
+        vision_robot_pose: Pose2d,
+        timestamp: impl Into<Second>,
+    ) {
+        let timestamp: Second = timestamp.into();
+
+        let Some((newest, _)) = self.pose_buffer.get_internal_buffer().last() else {
+            return;
+        };
+        if *newest - Second::new(BUFFER_DURATION) > timestamp {
+            return;
+        }
+
+        let Some(sample) = self.pose_buffer.get_sample(timestamp) else {
+            return;
+        };
+
+        // only move part of the way toward the measurement, weighted by the Kalman gain
+        let twist = sample.pose.log(&vision_robot_pose);
+        let scaled =
+            self.vision_k * Vector3::new(twist.dx.value(), twist.dy.value(), twist.dtheta.value());
+        let corrected = sample
+            .pose
+            .exp(Twist2d::new(scaled[0], scaled[1], scaled[2]));
+
+        self.odometry
+            .reset_position(sample.gyro_angle, sample.wheel_positions, corrected);
+        self.pose_buffer.add_sample(
+            timestamp,
+            InterpolationRecord {
+                pose: corrected,
+                gyro_angle: sample.gyro_angle,
+                wheel_positions: sample.wheel_positions,
+            },
+        );
+
+        // replay the odometry recorded since the measurement on top of the corrected pose
+        let replay: Vec<_> = self
+            .pose_buffer
+            .get_internal_buffer()
+            .iter()
+            .filter(|(time, _)| *time > timestamp)
+            .copied()
+            .collect();
+        for (time, record) in replay {
+            self.update_with_time(time, record.gyro_angle, record.wheel_positions);
+        }
+    }
+
+    /// Corrects the estimate with a vision pose, trusting it according to `vision_measurement_std_devs`.
+    ///
+    /// The standard deviations apply to this and all later measurements.
+    pub fn add_vision_measurement_with_std_devs(
+        &mut self,
+        vision_robot_pose: Pose2d,
+        timestamp: impl Into<Second>,
+        vision_measurement_std_devs: Vector3<f64>,
+    ) {
+        self.set_vision_measurement_std_devs(vision_measurement_std_devs);
+        self.add_vision_measurement(vision_robot_pose, timestamp);
+    }
+}
@@ -0,0 +1,397 @@
+use crate::math::estimator::{
+    DifferentialDrivePoseEstimator, ExtendedKalmanFilter, KalmanFilter, KalmanTypeFilter,
+    MerweScaledSigmaPoints, SwerveDrivePoseEstimator, UnscentedKalmanFilter,
+};
+use crate::math::geometry::{Pose2d, Rotation2d, Translation2d, Twist2d};
+use crate::math::kinematics::{
+    DifferentialDriveKinematics, DifferentialDriveWheelPositions, SwerveDriveKinematics,
+    SwerveModulePosition,
+};
+use crate::math::system::{DCMotor, LinearSystemId, NumericalIntegration};
+use approx::assert_relative_eq;
+use nalgebra::{Matrix1, Matrix2, Vector1, Vector2, Vector3};
+
+/// Deterministic gaussian noise so the tests are repeatable.
+struct Noise(u64);
+
+impl Noise {
+    fn uniform(&mut self) -> f64 {
+        self.0 = self
+            .0
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        ((self.0 >> 11) as f64 + 0.5) / (1u64 << 53) as f64
+    }
+
+    fn gaussian(&mut self, std_dev: f64) -> f64 {
+        let u1 = self.uniform();
+        let u2 = self.uniform();
+        std_dev * (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+    }
+}
+
+fn noisy(pose: &Pose2d, noise: &mut Noise, std_dev: f64) -> Pose2d {
+    Pose2d::new(
+        Translation2d::new(
+            pose.translation.x.value() + noise.gaussian(std_dev),
+            pose.translation.y.value() + noise.gaussian(std_dev),
+        ),
+        pose.rotation + Rotation2d::new(noise.gaussian(std_dev)),
+    )
+}
+
+#[test]
+fn swerve_converges_to_vision() {
+    let mut noise = Noise(42);
+    let kinematics = SwerveDriveKinematics::new([
+        Translation2d::new(1.0, 1.0),
+        Translation2d::new(1.0, -1.0),
+        Translation2d::new(-1.0, 1.0),
+        Translation2d::new(-1.0, -1.0),
+    ]);
+    let zero = [SwerveModulePosition::default(); 4];
+
+    // the estimator starts out believing the robot is somewhere it isn't
+    let mut estimator = SwerveDrivePoseEstimator::new(
+        kinematics,
+        Rotation2d::default(),
+        zero,
+        Pose2d::new_xy_rot(1.0, -1.0, Rotation2d::default()),
+        Vector3::new(0.1, 0.1, 0.1),
+        Vector3::new(0.9, 0.9, 0.9),
+    );
+
+    let dt = 0.02;
+    let latency = 0.06;
+    let mut history = Vec::new();
+    let mut true_pose = Pose2d::default();
+    for tick in 0..250 {
+        let time = f64::from(tick) * dt;
+        let distance = time;
+        true_pose = Pose2d::new_xy_rot(distance, 0.0, Rotation2d::default());
+        history.push((time, true_pose));
+
+        estimator.update_with_time(
+            time,
+            Rotation2d::default(),
+            [SwerveModulePosition::new(distance, Rotation2d::default()); 4],
+        );
+
+        if tick % 5 == 0 && time > latency {
+            let seen_at = time - latency;
+            let (_, seen) = history
+                .iter()
+                .min_by(|a, b| (a.0 - seen_at).abs().total_cmp(&(b.0 - seen_at).abs()))
+                .expect("history is not empty");
+            estimator.add_vision_measurement(noisy(seen, &mut noise, 0.05), seen_at);
+        }
+    }
+
+    let estimate = estimator.get_estimated_position();
+    let error = estimate.translation.get_distance(&true_pose.translation);
+    assert!(error.value() < 0.1, "estimate was off by {error}");
+    assert!(estimate.rotation.value.value().abs() < 0.05);
+}
+
+#[test]
+fn differential_corrects_wheel_slip() {
+    let mut noise = Noise(7);
+    let track_width = 0.6;
+    let mut estimator = DifferentialDrivePoseEstimator::new(
+        DifferentialDriveKinematics::new(track_width),
+        Rotation2d::default(),
+        DifferentialDriveWheelPositions::default(),
+        Pose2d::default(),
+        Vector3::new(0.02, 0.02, 0.01),
+        Vector3::new(0.1, 0.1, 0.1),
+    );
+
+    // drive a circle of radius 2 meters at 1 m/s while the wheels over-report by 10%
+    let dt = 0.02;
+    let radius = 2.0;
+    let mut true_pose = Pose2d::default();
+    let mut odometry_only = Pose2d::default();
+    let mut left = 0.0;
+    let mut right = 0.0;
+    for tick in 1..=500 {
+        let time = f64::from(tick) * dt;
+        let dtheta = dt / radius;
+        let step = Twist2d::new(dt, 0.0, dtheta);
+        true_pose = true_pose.exp(step);
+        odometry_only = odometry_only.exp(Twist2d::new(dt * 1.1, 0.0, dtheta));
+
+        left += 1.1 * dtheta * (radius - track_width / 2.0);
+        right += 1.1 * dtheta * (radius + track_width / 2.0);
+        estimator.update_with_time(
+            time,
+            true_pose.rotation,
+            DifferentialDriveWheelPositions::new(left, right),
+        );
+
+        if tick % 5 == 0 {
+            estimator.add_vision_measurement(noisy(&true_pose, &mut noise, 0.05), time);
+        }
+    }
+
+    let estimate = estimator.get_estimated_position();
+    let error = estimate.translation.get_distance(&true_pose.translation);
+    let odometry_error = odometry_only
+        .translation
+        .get_distance(&true_pose.translation);
+    assert!(error.value() < 0.2, "estimate was off by {error}");
+    assert!(error < odometry_error);
+}
+
+#[test]
+fn stale_vision_is_ignored() {
+    let mut estimator = DifferentialDrivePoseEstimator::new(
+        DifferentialDriveKinematics::new(0.6),
+        Rotation2d::default(),
+        DifferentialDriveWheelPositions::default(),
+        Pose2d::default(),
+        Vector3::new(0.02, 0.02, 0.01),
+        Vector3::new(0.1, 0.1, 0.1),
+    );
+
+    // no history yet, so there is nothing to correct
+    estimator.add_vision_measurement(Pose2d::new_xy_rot(5.0, 5.0, Rotation2d::default()), 0.0);
+    assert_eq!(estimator.get_estimated_position(), Pose2d::default());
+
+    estimator.update_with_time(
+        10.0,
+        Rotation2d::default(),
+        DifferentialDriveWheelPositions::default(),
+    );
+    estimator.add_vision_measurement(Pose2d::new_xy_rot(5.0, 5.0, Rotation2d::default()), 5.0);
+    assert_eq!(estimator.get_estimated_position(), Pose2d::default());
+}
+
+#[test]
+fn kalman_filter_steady_state() {
+    let plant = LinearSystemId::create_flywheel_system(DCMotor::new_neo(1), 0.002, 1.0);
+    let mut filter = KalmanFilter::new(plant, Vector1::new(3.0), Vector1::new(0.5), 0.02);
+
+    // a full correct and predict cycle returns the covariance to its steady-state value
+    let steady_state = *filter.get_p();
+    filter.correct(&Vector1::zeros(), &Vector1::zeros());
+    assert!(filter.get_p()[0] < steady_state[0]);
+    filter.predict(&Vector1::zeros(), 0.02);
+    assert_relative_eq!(filter.get_p()[0], steady_state[0], epsilon = 1e-9);
+
+    filter.set_p(Matrix1::new(100.0));
+    filter.set_xhat_at(0, 50.0);
+    filter.reset();
+    assert_relative_eq!(filter.get_xhat_at(0), 0.0);
+    assert_relative_eq!(filter.get_p()[0], steady_state[0]);
+}
+
+#[test]
+fn kalman_filter_tracks_flywheel() {
+    let mut noise = Noise(7);
+    let plant = LinearSystemId::create_flywheel_system(DCMotor::new_neo(1), 0.002, 1.0);
+    let mut filter = KalmanFilter::new(plant, Vector1::new(3.0), Vector1::new(5.0), 0.02);
+
+    let u = Vector1::new(6.0);
+    let mut x = Vector1::zeros();
+    for _ in 0..200 {
+        x = plant.calculate_x(&x, &u, 0.02);
+        filter.predict(&u, 0.02);
+        filter.correct(&u, &Vector1::new(x[0] + noise.gaussian(5.0)));
+    }
+
+    // the estimate is much closer than a single measurement's noise
+    assert_relative_eq!(filter.get_xhat_at(0), x[0], epsilon = 2.0);
+}
+
+#[test]
+fn extended_kalman_filter_matches_linear() {
+    let mut noise = Noise(11);
+    let plant = LinearSystemId::create_flywheel_system(DCMotor::new_neo(1), 0.002, 1.0);
+    let mut linear = KalmanFilter::new(plant, Vector1::new(3.0), Vector1::new(5.0), 0.02);
+    let mut extended = ExtendedKalmanFilter::new(
+        move |x: &Vector1<f64>, u: &Vector1<f64>| plant.get_a() * x + plant.get_b() * u,
+        move |x: &Vector1<f64>, u: &Vector1<f64>| plant.calculate_y(x, u),
+        Vector1::new(3.0),
+        Vector1::new(5.0),
+        0.02,
+    );
+
+    let u = Vector1::new(6.0);
+    let mut x = Vector1::zeros();
+    for _ in 0..200 {
+        x = plant.calculate_x(&x, &u, 0.02);
+        let y = Vector1::new(x[0] + noise.gaussian(5.0));
+        linear.predict(&u, 0.02);
+        linear.correct(&u, &y);
+        extended.predict(&u, 0.02);
+        extended.correct(&u, &y);
+    }
+
+    // the covariance converges to the same steady state from zero
+    assert_relative_eq!(extended.get_p()[0], linear.get_p()[0], epsilon = 1e-6);
+    assert_relative_eq!(
+        extended.get_xhat_at(0),
+        linear.get_xhat_at(0),
+        epsilon = 1e-3
+    );
+}
+
+/// A pendulum with a torque input, `[angle, angular velocity]`.
+fn pendulum(x: &Vector2<f64>, u: &Vector1<f64>) -> Vector2<f64> {
+    Vector2::new(x[1], -9.8 * x[0].sin() - 0.1 * x[1] + u[0])
+}
+
+#[test]
+fn extended_kalman_filter_pendulum() {
+    let mut noise = Noise(3);
+    let mut filter = ExtendedKalmanFilter::new(
+        pendulum,
+        |x: &Vector2<f64>, _u: &Vector1<f64>| Vector1::new(x[0]),
+        Vector2::new(0.01, 0.01),
+        Vector1::new(0.05),
+        0.01,
+    );
+    filter.set_p(Matrix2::identity());
+
+    let u = Vector1::zeros();
+    let mut x = Vector2::new(1.0, 0.0);
+    for _ in 0..500 {
+        x = NumericalIntegration::rk4(pendulum, &x, &u, 0.01);
+        filter.predict(&u, 0.01);
+        filter.correct(&u, &Vector1::new(x[0] + noise.gaussian(0.05)));
+    }
+
+    assert_relative_eq!(filter.get_xhat_at(0), x[0], epsilon = 0.05);
+    assert_relative_eq!(filter.get_xhat_at(1), x[1], epsilon = 0.1);
+
+    // a velocity measurement from another sensor pulls the estimate toward it
+    let before = (filter.get_xhat_at(1) - x[1]).abs();
+    filter.correct_with(
+        &u,
+        &Vector1::new(x[1]),
+        |x: &Vector2<f64>, _u: &Vector1<f64>| Vector1::new(x[1]),
+        &Matrix1::new(1e-6),
+    );
+    assert!((filter.get_xhat_at(1) - x[1]).abs() <= before);
+}
+
+#[test]
+fn merwe_scaled_sigma_points() {
+    let pts = MerweScaledSigmaPoints::<2>::default();
+    assert_eq!(pts.get_num_sigmas(), 5);
+    assert_relative_eq!(pts.get_wm().iter().sum::<f64>(), 1.0, epsilon = 1e-9);
+
+    // the weighted sigma points reproduce the mean and covariance they came from
+    let x = Vector2::new(1.0, -2.0);
+    let p = Matrix2::new(2.0, 0.5, 0.5, 1.0);
+    let sigmas = pts.sigma_points(&x, &p);
+    let mean = sigmas
+        .iter()
+        .zip(pts.get_wm())
+        .fold(Vector2::zeros(), |mean, (sigma, w)| mean + sigma * *w);
+    let covariance = sigmas
+        .iter()
+        .zip(pts.get_wc())
+        .fold(Matrix2::zeros(), |covariance, (sigma, w)| {
+            covariance + (sigma - mean) * (sigma - mean).transpose() * *w
+        });
+    assert_relative_eq!(mean, x, epsilon = 1e-9);
+    assert_relative_eq!(covariance, p, epsilon = 1e-6);
+
+    // a zero covariance collapses every point onto the mean
+    for sigma in pts.sigma_points(&x, &Matrix2::zeros()) {
+        assert_relative_eq!(sigma, x);
+    }
+}
+
+#[test]
+fn unscented_kalman_filter_pendulum() {
+    let mut noise = Noise(5);
+    let mut filter = UnscentedKalmanFilter::new(
+        pendulum,
+        |x: &Vector2<f64>, _u: &Vector1<f64>| Vector1::new(x[0]),
+        Vector2::new(0.01, 0.01),
+        Vector1::new(0.05),
+        0.01,
+    );
+    filter.set_p(Matrix2::identity());
+
+    let u = Vector1::new(0.5);
+    let mut x = Vector2::new(2.0, 0.0);
+    for _ in 0..500 {
+        x = NumericalIntegration::rk4(pendulum, &x, &u, 0.01);
+        filter.predict(&u, 0.01);
+        filter.correct(&u, &Vector1::new(x[0] + noise.gaussian(0.05)));
+    }
+
+    assert_relative_eq!(filter.get_xhat_at(0), x[0], epsilon = 0.05);
+    assert_relative_eq!(filter.get_xhat_at(1), x[1], epsilon = 0.1);
+
+    let before = (filter.get_xhat_at(1) - x[1]).abs();
+    filter.correct_with(
+        &u,
+        &Vector1::new(x[1]),
+        |x: &Vector2<f64>, _u: &Vector1<f64>| Vector1::new(x[1]),
+        &Matrix1::new(1e-6),
+    );
+    assert!((filter.get_xhat_at(1) - x[1]).abs() <= before);
+}
+
+#[test]
+fn unscented_kalman_filter_wrapped_angle() {
+    let wrap = |angle: f64| {
+        (angle + std::f64::consts::PI).rem_euclid(std::f64::consts::TAU) - std::f64::consts::PI
+    };
+
+    // a heading that spins at a constant rate, measured as an angle in [−π, π)
+    let mut filter = UnscentedKalmanFilter::new(
+        |x: &Vector2<f64>, _u: &Vector1<f64>| Vector2::new(x[1], 0.0),
+        move |x: &Vector2<f64>, _u: &Vector1<f64>| Vector1::new(wrap(x[0])),
+        Vector2::new(0.01, 0.01),
+        Vector1::new(0.01),
+        0.02,
+    )
+    .with_mean_func_x(|sigmas: &[Vector2<f64>], wm: &[f64]| {
+        let (sin, cos, velocity) =
+            sigmas
+                .iter()
+                .zip(wm)
+                .fold((0.0, 0.0, 0.0), |(sin, cos, velocity), (sigma, w)| {
+                    (
+                        sin + sigma[0].sin() * w,
+                        cos + sigma[0].cos() * w,
+                        velocity + sigma[1] * w,
+                    )
+                });
+        Vector2::new(sin.atan2(cos), velocity)
+    })
+    .with_mean_func_y(|sigmas: &[Vector1<f64>], wm: &[f64]| {
+        let (sin, cos) = sigmas
+            .iter()
+            .zip(wm)
+            .fold((0.0, 0.0), |(sin, cos), (sigma, w)| {
+                (sin + sigma[0].sin() * w, cos + sigma[0].cos() * w)
+            });
+        Vector1::new(sin.atan2(cos))
+    })
+    .with_residual_func_x(move |a: &Vector2<f64>, b: &Vector2<f64>| {
+        Vector2::new(wrap(a[0] - b[0]), a[1] - b[1])
+    })
+    .with_residual_func_y(move |a: &Vector1<f64>, b: &Vector1<f64>| Vector1::new(wrap(a[0] - b[0])))
+    .with_add_func_x(move |a: &Vector2<f64>, b: &Vector2<f64>| {
+        Vector2::new(wrap(a[0] + b[0]), a[1] + b[1])
+    });
+    filter.set_xhat(Vector2::new(0.0, 2.0));
+    filter.set_p(Matrix2::identity() * 0.1);
+
+    let mut heading = 0.0;
+    for _ in 0..300 {
+        heading += 2.0 * 0.02;
+        filter.predict(&Vector1::zeros(), 0.02);
+        filter.correct(&Vector1::zeros(), &Vector1::new(wrap(heading)));
+    }
+
+    // the estimate follows the heading through several wraps without jumping
+    assert_relative_eq!(wrap(filter.get_xhat_at(0) - heading), 0.0, epsilon = 0.02);
+    assert_relative_eq!(filter.get_xhat_at(1), 2.0, epsilon = 0.05);
+}
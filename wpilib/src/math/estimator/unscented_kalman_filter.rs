@@ -0,0 +1,316 @@
+use std::fmt::Debug;
+
+use nalgebra::{SMatrix, SVector};
+
+use crate::math::system::{Discretization, NumericalIntegration, NumericalJacobian};
+use crate::math::units::time::Second;
+use crate::math::util::state_space_util::StateSpaceUtil;
+
+use super::{KalmanTypeFilter, MerweScaledSigmaPoints};
+
+type DynamicsFunction<const STATES: usize, const INPUTS: usize> =
+    Box<dyn Fn(&SVector<f64, STATES>, &SVector<f64, INPUTS>) -> SVector<f64, STATES> + Send + Sync>;
+type MeasurementFunction<const STATES: usize, const INPUTS: usize, const OUTPUTS: usize> = Box<
+    dyn Fn(&SVector<f64, STATES>, &SVector<f64, INPUTS>) -> SVector<f64, OUTPUTS> + Send + Sync,
+>;
+type MeanFunction<const N: usize> =
+    Box<dyn Fn(&[SVector<f64, N>], &[f64]) -> SVector<f64, N> + Send + Sync>;
+type VectorFunction<const N: usize> =
+    Box<dyn Fn(&SVector<f64, N>, &SVector<f64, N>) -> SVector<f64, N> + Send + Sync>;
+
+/// A Kalman filter for a nonlinear plant `dx/dt = f(x, u)`, `y = h(x, u)`, which passes a set of
+/// sigma points through the model instead of linearizing it.
+///
+/// This handles strongly nonlinear models better than an
+/// [`ExtendedKalmanFilter`](super::ExtendedKalmanFilter), at the cost of evaluating the model
+/// once per sigma point.
+pub struct UnscentedKalmanFilter<const STATES: usize, const INPUTS: usize, const OUTPUTS: usize> {
+    f: DynamicsFunction<STATES, INPUTS>,
+    h: MeasurementFunction<STATES, INPUTS, OUTPUTS>,
+    mean_func_x: MeanFunction<STATES>,
+    mean_func_y: MeanFunction<OUTPUTS>,
+    residual_func_x: VectorFunction<STATES>,
+    residual_func_y: VectorFunction<OUTPUTS>,
+    add_func_x: VectorFunction<STATES>,
+    pts: MerweScaledSigmaPoints<STATES>,
+    cont_q: SMatrix<f64, STATES, STATES>,
+    cont_r: SMatrix<f64, OUTPUTS, OUTPUTS>,
+    dt: Second,
+    xhat: SVector<f64, STATES>,
+    p: SMatrix<f64, STATES, STATES>,
+}
+
+impl<const STATES: usize, const INPUTS: usize, const OUTPUTS: usize>
+    UnscentedKalmanFilter<STATES, INPUTS, OUTPUTS>
+{
+    /// Creates a filter that runs every `dt` with the default sigma point parameters, starting
+    /// with zero error covariance.
+    #[must_use]
+    pub fn new<F, H>(
+        f: F,
+        h: H,
+        state_std_devs: SVector<f64, STATES>,
+        measurement_std_devs: SVector<f64, OUTPUTS>,
+        dt: impl Into<Second>,
+    ) -> Self
+    where
+        F: Fn(&SVector<f64, STATES>, &SVector<f64, INPUTS>) -> SVector<f64, STATES>
+            + Send
+            + Sync
+            + 'static,
+        H: Fn(&SVector<f64, STATES>, &SVector<f64, INPUTS>) -> SVector<f64, OUTPUTS>
+            + Send
+            + Sync
+            + 'static,
+    {
+        Self {
+            f: Box::new(f),
+            h: Box::new(h),
+            mean_func_x: Box::new(weighted_mean),
+            mean_func_y: Box::new(weighted_mean),
+            residual_func_x: Box::new(|a, b| a - b),
+            residual_func_y: Box::new(|a, b| a - b),
+            add_func_x: Box::new(|a, b| a + b),
+            pts: MerweScaledSigmaPoints::default(),
+            cont_q: StateSpaceUtil::make_covariance_matrix(&state_std_devs),
+            cont_r: StateSpaceUtil::make_covariance_matrix(&measurement_std_devs),
+            dt: dt.into(),
+            xhat: SVector::zeros(),
+            p: SMatrix::zeros(),
+        }
+    }
+
+    /// Replaces the default sigma point parameters.
+    #[must_use]
+    pub fn with_sigma_points(mut self, pts: MerweScaledSigmaPoints<STATES>) -> Self {
+        self.pts = pts;
+        self
+    }
+
+    /// Replaces the weighted sum as the way to average sigma points in state space, such as when
+    /// a state is an angle that wraps around.
+    #[must_use]
+    pub fn with_mean_func_x<F>(mut self, mean_func_x: F) -> Self
+    where
+        F: Fn(&[SVector<f64, STATES>], &[f64]) -> SVector<f64, STATES> + Send + Sync + 'static,
+    {
+        self.mean_func_x = Box::new(mean_func_x);
+        self
+    }
+
+    /// Replaces the weighted sum as the way to average sigma points in measurement space.
+    #[must_use]
+    pub fn with_mean_func_y<F>(mut self, mean_func_y: F) -> Self
+    where
+        F: Fn(&[SVector<f64, OUTPUTS>], &[f64]) -> SVector<f64, OUTPUTS> + Send + Sync + 'static,
+    {
+        self.mean_func_y = Box::new(mean_func_y);
+        self
+    }
+
+    /// Replaces subtraction as the way to find the difference between two states.
+    #[must_use]
+    pub fn with_residual_func_x<F>(mut self, residual_func_x: F) -> Self
+    where
+        F: Fn(&SVector<f64, STATES>, &SVector<f64, STATES>) -> SVector<f64, STATES>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.residual_func_x = Box::new(residual_func_x);
+        self
+    }
+
+    /// Replaces subtraction as the way to find the difference between two measurements.
+    #[must_use]
+    pub fn with_residual_func_y<F>(mut self, residual_func_y: F) -> Self
+    where
+        F: Fn(&SVector<f64, OUTPUTS>, &SVector<f64, OUTPUTS>) -> SVector<f64, OUTPUTS>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.residual_func_y = Box::new(residual_func_y);
+        self
+    }
+
+    /// Replaces addition as the way to apply a correction to the state estimate.
+    #[must_use]
+    pub fn with_add_func_x<F>(mut self, add_func_x: F) -> Self
+    where
+        F: Fn(&SVector<f64, STATES>, &SVector<f64, STATES>) -> SVector<f64, STATES>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.add_func_x = Box::new(add_func_x);
+        self
+    }
+
+    #[must_use]
+    pub fn get_xhat_at(&self, row: usize) -> f64 {
+        self.xhat[row]
+    }
+
+    pub fn set_xhat_at(&mut self, row: usize, value: f64) {
+        self.xhat[row] = value;
+    }
+
+    /// Corrects the state estimate with a measurement `y = h(x, u)` other than the one the
+    /// filter was created with, whose continuous noise covariance is `cont_r`.
+    ///
+    /// # Panics
+    /// Panics if the innovation covariance isn't positive definite.
+    pub fn correct_with<const ROWS: usize>(
+        &mut self,
+        u: &SVector<f64, INPUTS>,
+        y: &SVector<f64, ROWS>,
+        h: impl Fn(&SVector<f64, STATES>, &SVector<f64, INPUTS>) -> SVector<f64, ROWS>,
+        cont_r: &SMatrix<f64, ROWS, ROWS>,
+    ) {
+        self.correct_with_functions(u, y, h, cont_r, weighted_mean, |a, b| a - b);
+    }
+
+    /// Corrects the state estimate with a measurement `y = h(x, u)` other than the one the
+    /// filter was created with, averaging measurements with `mean_func_y` and finding their
+    /// difference with `residual_func_y`.
+    ///
+    /// # Panics
+    /// Panics if the innovation covariance isn't positive definite.
+    pub fn correct_with_functions<const ROWS: usize>(
+        &mut self,
+        u: &SVector<f64, INPUTS>,
+        y: &SVector<f64, ROWS>,
+        h: impl Fn(&SVector<f64, STATES>, &SVector<f64, INPUTS>) -> SVector<f64, ROWS>,
+        cont_r: &SMatrix<f64, ROWS, ROWS>,
+        mean_func_y: impl Fn(&[SVector<f64, ROWS>], &[f64]) -> SVector<f64, ROWS>,
+        residual_func_y: impl Fn(&SVector<f64, ROWS>, &SVector<f64, ROWS>) -> SVector<f64, ROWS>,
+    ) {
+        let (xhat, p) = self.corrected(u, y, h, cont_r, mean_func_y, residual_func_y);
+        self.xhat = xhat;
+        self.p = p;
+    }
+
+    fn corrected<const ROWS: usize>(
+        &self,
+        u: &SVector<f64, INPUTS>,
+        y: &SVector<f64, ROWS>,
+        h: impl Fn(&SVector<f64, STATES>, &SVector<f64, INPUTS>) -> SVector<f64, ROWS>,
+        cont_r: &SMatrix<f64, ROWS, ROWS>,
+        mean_func_y: impl Fn(&[SVector<f64, ROWS>], &[f64]) -> SVector<f64, ROWS>,
+        residual_func_y: impl Fn(&SVector<f64, ROWS>, &SVector<f64, ROWS>) -> SVector<f64, ROWS>,
+    ) -> (SVector<f64, STATES>, SMatrix<f64, STATES, STATES>) {
+        let discrete_r = Discretization::discretize_r(cont_r, self.dt);
+
+        // transform the sigma points into measurement space
+        let sigmas = self.pts.sigma_points(&self.xhat, &self.p);
+        let sigmas_h: Vec<_> = sigmas.iter().map(|sigma| h(sigma, u)).collect();
+        let yhat = mean_func_y(&sigmas_h, self.pts.get_wm());
+
+        // innovation covariance and cross covariance between state and measurement
+        let mut s = discrete_r;
+        let mut p_xy = SMatrix::<f64, STATES, ROWS>::zeros();
+        for ((sigma, sigma_h), wc) in sigmas.iter().zip(&sigmas_h).zip(self.pts.get_wc()) {
+            let dx = (self.residual_func_x)(sigma, &self.xhat);
+            let dy = residual_func_y(sigma_h, &yhat);
+            s += dy * dy.transpose() * *wc;
+            p_xy += dx * dy.transpose() * *wc;
+        }
+
+        // K = P_xyS⁻¹, solved as Kᵀ = S⁻¹P_xyᵀ since S is symmetric
+        let k = s
+            .cholesky()
+            .expect("Innovation covariance must be positive definite")
+            .solve(&p_xy.transpose())
+            .transpose();
+
+        let xhat = (self.add_func_x)(&self.xhat, &(k * residual_func_y(y, &yhat)));
+        let p = self.p - k * s * k.transpose();
+
+        (xhat, p)
+    }
+}
+
+impl<const STATES: usize, const INPUTS: usize, const OUTPUTS: usize>
+    KalmanTypeFilter<STATES, INPUTS, OUTPUTS> for UnscentedKalmanFilter<STATES, INPUTS, OUTPUTS>
+{
+    fn get_p(&self) -> &SMatrix<f64, STATES, STATES> {
+        &self.p
+    }
+
+    fn set_p(&mut self, p: SMatrix<f64, STATES, STATES>) {
+        self.p = p;
+    }
+
+    fn get_xhat(&self) -> &SVector<f64, STATES> {
+        &self.xhat
+    }
+
+    fn set_xhat(&mut self, xhat: SVector<f64, STATES>) {
+        self.xhat = xhat;
+    }
+
+    fn reset(&mut self) {
+        self.xhat = SVector::zeros();
+        self.p = SMatrix::zeros();
+    }
+
+    fn predict(&mut self, u: &SVector<f64, INPUTS>, dt: impl Into<Second>) {
+        let dt: Second = dt.into();
+
+        // the process noise is discretized with the model linearized about the estimate
+        let cont_a = NumericalJacobian::numerical_jacobian_x(&self.f, &self.xhat, u);
+        let (_, discrete_q) = Discretization::discretize_aq(&cont_a, &self.cont_q, dt);
+
+        let sigmas_f: Vec<_> = self
+            .pts
+            .sigma_points(&self.xhat, &self.p)
+            .iter()
+            .map(|sigma| NumericalIntegration::rk4(&self.f, sigma, u, dt))
+            .collect();
+
+        self.xhat = (self.mean_func_x)(&sigmas_f, self.pts.get_wm());
+        self.p = discrete_q;
+        for (sigma, wc) in sigmas_f.iter().zip(self.pts.get_wc()) {
+            let dx = (self.residual_func_x)(sigma, &self.xhat);
+            self.p += dx * dx.transpose() * *wc;
+        }
+    }
+
+    fn correct(&mut self, u: &SVector<f64, INPUTS>, y: &SVector<f64, OUTPUTS>) {
+        let (xhat, p) = self.corrected(
+            u,
+            y,
+            &self.h,
+            &self.cont_r,
+            &self.mean_func_y,
+            &self.residual_func_y,
+        );
+        self.xhat = xhat;
+        self.p = p;
+    }
+}
+
+impl<const STATES: usize, const INPUTS: usize, const OUTPUTS: usize> Debug
+    for UnscentedKalmanFilter<STATES, INPUTS, OUTPUTS>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UnscentedKalmanFilter")
+            .field("pts", &self.pts)
+            .field("cont_q", &self.cont_q)
+            .field("cont_r", &self.cont_r)
+            .field("dt", &self.dt)
+            .field("xhat", &self.xhat)
+            .field("p", &self.p)
+            .finish_non_exhaustive()
+    }
+}
+
+fn weighted_mean<const N: usize>(sigmas: &[SVector<f64, N>], wm: &[f64]) -> SVector<f64, N> {
+    sigmas
+        .iter()
+        .zip(wm)
+        .fold(SVector::zeros(), |mean, (sigma, weight)| {
+            mean + sigma * *weight
+        })
+}
@@ -1,5 +1,6 @@
 mod pose2d;
 mod pose3d;
+mod quaternion;
 mod rotation2d;
 mod rotation3d;
 mod test;
@@ -13,6 +14,8 @@ mod twist3d;
 pub use pose2d::*;
 pub use pose3d::*;
 
+pub use quaternion::*;
+
 pub use rotation2d::*;
 pub use rotation3d::*;
 
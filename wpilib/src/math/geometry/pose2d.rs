@@ -90,7 +90,7 @@ impl Pose2d {
     #[must_use]
     pub fn log(&self, end: &Self) -> Twist2d {
         let transform: Self = end.relative_to(self);
-        let dtheta: f64 = transform.rotation.value.into();
+        let dtheta: f64 = transform.rotation.sin.atan2(transform.rotation.cos);
         let half_dtheta: f64 = dtheta / 2.0;
 
         let cos_minus_one = transform.rotation.cos - 1.0;
@@ -1,11 +1,11 @@
-use nalgebra::{Matrix3, Matrix3x1, Quaternion, Rotation, Rotation3, Vector3};
-// KEEP UNUSED IMPORTS FOR COMMENTED OUT SECTION
+use nalgebra::{Matrix3, Rotation3, Vector3};
+use serde::{Deserialize, Serialize};
 
-use crate::math::units::{angle::Radian, distance::Meter};
+use crate::math::units::distance::Meter;
 
 use super::{Pose2d, Rotation3d, Transform3d, Translation3d, Twist3d};
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Pose3d {
     pub translation: Translation3d,
     pub rotation: Rotation3d,
@@ -73,94 +73,130 @@ impl Pose3d {
         Self::new_trans_rot(transform.translation, transform.rotation)
     }
 
-    // pub fn exp(&self, twist: Twist3d) -> Self {
-    //     let u = Vector3::new(
-    //         f64::from(twist.dx),
-    //         f64::from(twist.dy),
-    //         f64::from(twist.dz),
-    //     );
-    //     let rvec = Vector3::new(twist.rx, twist.ry, twist.rz);
-    //     let omega = self.rotation_vector_to_matrix(rvec);
-    //     let omgega_sq = omega * omega;
-    //     let theta = rvec.norm();
-    //     let theta_sq = theta * theta;
-
-    //     let a: f64;
-    //     let b: f64;
-    //     let c: f64;
-    //     if theta.abs() < 1E-7 {
-    //         a = 1.0 - theta_sq / 6.0 + theta_sq * theta_sq / 120.0;
-    //         b = 0.5 - theta_sq / 24.0 + theta_sq * theta_sq / 720.0;
-    //         c = 1.0 / 6.0 - theta_sq / 120.0 + theta_sq * theta_sq / 5040.0;
-    //     } else {
-    //         a = theta.sin() / theta;
-    //         b = (1.0 - theta.cos()) / theta_sq;
-    //         c = (1.0 - a) / theta_sq;
-    //     }
-
-    //     let r: Matrix3<f64> = Matrix3::identity() + a.into() * omega + b.into() * omgega_sq;
-    //     let v: Matrix3<f64> = Matrix3::identity() + b.into() * omega + c.into() * omgega_sq;
-    //     let translation_component: Matrix3x1<f64> = v * u;
-
-    //     let transform = Transform3d::new_trans_rot(
-    //         Translation3d::new_xyz(
-    //             translation_component[0],
-    //             translation_component[1],
-    //             translation_component[2],
-    //         ),
-    //         Rotation3d::new_rotation_matrix(Rotation3::from_matrix(&r)),
-    //     );
-    //     self.plus(transform)
-    // }
-
-    // pub fn log(&self, end: &Self) -> Twist3d {
-    //     let transform = end.relative_to(self);
-    //     let rvec = transform.rotation.q.as_vector();
-
-    //     let omega = transform.rotation.q.to_rotation_matrix();
-    //     let theta = rvec.norm();
-    //     let theta_sq = theta * theta;
-
-    //     let c: f64;
-    //     if theta.abs() < 1E-7 {
-    //         c = 1.0 / 12.0 + theta_sq / 720.0 + theta_sq * theta_sq / 30240.0;
-    //     } else {
-    //         let a = theta.sin() / theta;
-    //         let b = (1.0 - theta.cos()) / theta_sq;
-    //         c = (1.0 - a / (2.0 * b)) / theta_sq;
-    //     }
-
-    //     let v_inv: Matrix3<f64> =
-    //         Matrix3::identity() - (0.5.into() * omega) + c.into() * omega * omega;
-
-    //     let twist_translation: Vector3<f64> = v_inv
-    //         * Vector3::new(
-    //             f64::from(transform.translation.x),
-    //             f64::from(transform.translation.y),
-    //             f64::from(transform.translation.z),
-    //         );
-
-    //     Twist3d::new_dv(
-    //         twist_translation[0],
-    //         twist_translation[1],
-    //         twist_translation[2],
-    //         rvec[0],
-    //         rvec[1],
-    //         rvec[2],
-    //     )
-    // }
-
-    fn rotation_vector_to_matrix(&self, rotation: Vector3<Radian>) -> Matrix3<Radian> {
+    #[must_use]
+    pub fn exp(&self, twist: Twist3d) -> Self {
+        let u = Vector3::new(
+            f64::from(twist.dx),
+            f64::from(twist.dy),
+            f64::from(twist.dz),
+        );
+        let rvec = Vector3::new(
+            f64::from(twist.rx),
+            f64::from(twist.ry),
+            f64::from(twist.rz),
+        );
+        let omega = Self::rotation_vector_to_matrix(&rvec);
+        let omega_sq = omega * omega;
+        let theta_sq = rvec.norm_squared();
+
+        let a: f64;
+        let b: f64;
+        let c: f64;
+        if theta_sq < 1E-9 * 1E-9 {
+            a = 1.0 - theta_sq / 6.0 + theta_sq * theta_sq / 120.0;
+            b = 0.5 - theta_sq / 24.0 + theta_sq * theta_sq / 720.0;
+            c = 1.0 / 6.0 - theta_sq / 120.0 + theta_sq * theta_sq / 5040.0;
+        } else {
+            let theta = theta_sq.sqrt();
+            a = theta.sin() / theta;
+            b = (1.0 - theta.cos()) / theta_sq;
+            c = (1.0 - a) / theta_sq;
+        }
+
+        let r: Matrix3<f64> = Matrix3::identity() + omega * a + omega_sq * b;
+        let v: Matrix3<f64> = Matrix3::identity() + omega * b + omega_sq * c;
+        let translation_component: Vector3<f64> = v * u;
+
+        let transform = Transform3d::new_trans_rot(
+            Translation3d::new(
+                translation_component[0],
+                translation_component[1],
+                translation_component[2],
+            ),
+            Rotation3d::new_rotation_matrix(Rotation3::from_matrix_unchecked(r)),
+        );
+        self.plus(transform)
+    }
+
+    #[must_use]
+    pub fn log(&self, end: &Self) -> Twist3d {
+        let transform = end.relative_to(self);
+        let rvec = transform.rotation.q.scaled_axis();
+
+        let omega = Self::rotation_vector_to_matrix(&rvec);
+        let omega_sq = omega * omega;
+        let theta_sq = rvec.norm_squared();
+
+        let c: f64 = if theta_sq < 1E-9 * 1E-9 {
+            1.0 / 12.0 + theta_sq / 720.0 + theta_sq * theta_sq / 30240.0
+        } else {
+            let theta = theta_sq.sqrt();
+            let a = theta.sin() / theta;
+            let b = (1.0 - theta.cos()) / theta_sq;
+            (1.0 - a / (2.0 * b)) / theta_sq
+        };
+
+        let v_inv: Matrix3<f64> = Matrix3::identity() - omega * 0.5 + omega_sq * c;
+
+        let twist_translation: Vector3<f64> = v_inv
+            * Vector3::new(
+                f64::from(transform.translation.x),
+                f64::from(transform.translation.y),
+                f64::from(transform.translation.z),
+            );
+
+        Twist3d::new(
+            twist_translation[0],
+            twist_translation[1],
+            twist_translation[2],
+            rvec[0],
+            rvec[1],
+            rvec[2],
+        )
+    }
+
+    /// Returns the pose with the closest translation, or `None` if `poses` is empty.
+    #[must_use]
+    pub fn nearest(&self, poses: &[Self]) -> Option<Self> {
+        poses.iter().copied().min_by(|a, b| {
+            let distance_a = self.translation.get_distance(&a.translation).value();
+            let distance_b = self.translation.get_distance(&b.translation).value();
+            distance_a.total_cmp(&distance_b)
+        })
+    }
+
+    #[must_use]
+    pub fn interpolate(&self, end_value: &Self, t: f64) -> Self {
+        if t < 0.0 {
+            *self
+        } else if t >= 1.0 {
+            *end_value
+        } else {
+            let twist = self.log(end_value);
+            let scaled_twist = Twist3d::new(
+                f64::from(twist.dx) * t,
+                f64::from(twist.dy) * t,
+                f64::from(twist.dz) * t,
+                f64::from(twist.rx) * t,
+                f64::from(twist.ry) * t,
+                f64::from(twist.rz) * t,
+            );
+            self.exp(scaled_twist)
+        }
+    }
+
+    /// Returns the skew-symmetric matrix of a rotation vector.
+    fn rotation_vector_to_matrix(rotation: &Vector3<f64>) -> Matrix3<f64> {
         Matrix3::new(
-            0.0.into(),
+            0.0,
             -rotation[2],
             rotation[1],
             rotation[2],
-            0.0.into(),
+            0.0,
             -rotation[0],
             -rotation[1],
             rotation[0],
-            0.0.into(),
+            0.0,
         )
     }
 }
@@ -0,0 +1,282 @@
+use nalgebra::Vector3;
+use serde::{Deserialize, Serialize};
+use std::ops;
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub struct Quaternion {
+    pub w: f64,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Quaternion {
+    #[must_use]
+    pub const fn new(w: f64, x: f64, y: f64, z: f64) -> Self {
+        Self { w, x, y, z }
+    }
+
+    /// Returns the quaternion representing a rotation of `rvec.norm()` radians about `rvec`.
+    #[must_use]
+    pub fn from_rotation_vector(rvec: Vector3<f64>) -> Self {
+        let theta = rvec.norm();
+        let cos = (theta / 2.0).cos();
+
+        // Taylor series of sin(θ/2)/θ near θ = 0: 1/2 - θ²/48 + O(θ⁴)
+        let axial_scalar = if theta < 1E-9 {
+            1.0 / 2.0 - theta * theta / 48.0
+        } else {
+            (theta / 2.0).sin() / theta
+        };
+
+        Self::new(
+            cos,
+            axial_scalar * rvec[0],
+            axial_scalar * rvec[1],
+            axial_scalar * rvec[2],
+        )
+    }
+
+    #[must_use]
+    pub fn plus(&self, other: &Self) -> Self {
+        Self::new(
+            self.w + other.w,
+            self.x + other.x,
+            self.y + other.y,
+            self.z + other.z,
+        )
+    }
+
+    #[must_use]
+    pub fn minus(&self, other: &Self) -> Self {
+        Self::new(
+            self.w - other.w,
+            self.x - other.x,
+            self.y - other.y,
+            self.z - other.z,
+        )
+    }
+
+    #[must_use]
+    pub fn times(&self, scalar: f64) -> Self {
+        Self::new(
+            self.w * scalar,
+            self.x * scalar,
+            self.y * scalar,
+            self.z * scalar,
+        )
+    }
+
+    #[must_use]
+    pub fn divide(&self, scalar: f64) -> Self {
+        self.times(1.0 / scalar)
+    }
+
+    /// Returns the Hamilton product `self * other`.
+    #[must_use]
+    pub fn multiply(&self, other: &Self) -> Self {
+        let v1 = Vector3::new(self.x, self.y, self.z);
+        let v2 = Vector3::new(other.x, other.y, other.z);
+
+        // r = r₁r₂ − v₁ ⋅ v₂, v = r₁v₂ + r₂v₁ + v₁ × v₂
+        let r = self.w.mul_add(other.w, -v1.dot(&v2));
+        let v = v2 * self.w + v1 * other.w + v1.cross(&v2);
+        Self::new(r, v[0], v[1], v[2])
+    }
+
+    #[must_use]
+    pub fn conjugate(&self) -> Self {
+        Self::new(self.w, -self.x, -self.y, -self.z)
+    }
+
+    #[must_use]
+    pub fn dot(&self, other: &Self) -> f64 {
+        self.w.mul_add(
+            other.w,
+            self.x
+                .mul_add(other.x, self.y.mul_add(other.y, self.z * other.z)),
+        )
+    }
+
+    #[must_use]
+    pub fn inverse(&self) -> Self {
+        let norm = self.norm();
+        self.conjugate().divide(norm * norm)
+    }
+
+    #[must_use]
+    pub fn norm(&self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    /// Returns this quaternion scaled to unit length, or the identity if it has zero length.
+    #[must_use]
+    pub fn normalize(&self) -> Self {
+        let norm = self.norm();
+        if norm == 0.0 {
+            Self::default()
+        } else {
+            self.divide(norm)
+        }
+    }
+
+    /// Raises this quaternion to the power `t`.
+    #[must_use]
+    pub fn pow(&self, t: f64) -> Self {
+        self.log().times(t).exp()
+    }
+
+    /// Applies the rotation `adjustment`, given as a quaternion in the tangent space, to this one.
+    #[must_use]
+    pub fn exp_with(&self, adjustment: &Self) -> Self {
+        adjustment.exp().multiply(self)
+    }
+
+    #[must_use]
+    pub fn exp(&self) -> Self {
+        let scalar = self.w.exp();
+
+        let axial_magnitude = self.vector_norm();
+        let cosine = axial_magnitude.cos();
+
+        // Taylor series of sin(x)/x near x = 0: 1 − x²/6 + x⁴/120 + O(x⁶)
+        let axial_scalar = if axial_magnitude < 1E-9 {
+            let axial_magnitude_sq = axial_magnitude * axial_magnitude;
+            1.0 - axial_magnitude_sq / 6.0 + axial_magnitude_sq * axial_magnitude_sq / 120.0
+        } else {
+            axial_magnitude.sin() / axial_magnitude
+        };
+
+        Self::new(
+            cosine * scalar,
+            self.x * axial_scalar * scalar,
+            self.y * axial_scalar * scalar,
+            self.z * axial_scalar * scalar,
+        )
+    }
+
+    /// Returns the tangent space quaternion that takes this quaternion to `end`.
+    #[must_use]
+    pub fn log_to(&self, end: &Self) -> Self {
+        end.multiply(&self.inverse()).log()
+    }
+
+    #[must_use]
+    pub fn log(&self) -> Self {
+        let norm = self.norm();
+        let scalar = norm.ln();
+
+        let v_norm = self.vector_norm();
+        let s_norm = self.w / norm;
+
+        if (s_norm + 1.0).abs() < 1E-9 {
+            return Self::new(scalar, -std::f64::consts::PI, 0.0, 0.0);
+        }
+
+        // Taylor series of atan(x)/x near x = 0: 1/w - x²/(3w³) + O(x⁴)
+        let v_scalar = if v_norm < 1E-9 {
+            1.0 / self.w - v_norm * v_norm / (3.0 * self.w.powi(3))
+        } else {
+            v_norm.atan2(self.w) / v_norm
+        };
+
+        Self::new(
+            scalar,
+            v_scalar * self.x,
+            v_scalar * self.y,
+            v_scalar * self.z,
+        )
+    }
+
+    /// Returns the rotation vector (axis scaled by angle in radians) of this unit quaternion.
+    #[must_use]
+    pub fn to_rotation_vector(&self) -> Vector3<f64> {
+        let norm = self.vector_norm();
+
+        // Taylor series of 2 atan(x)/x near x = 0: 2/w - 2x²/(3w³) + O(x⁴)
+        let coeff = if norm < 1E-9 {
+            2.0 / self.w - 2.0 / 3.0 * norm * norm / self.w.powi(3)
+        } else if self.w < 0.0 {
+            2.0 * (-norm).atan2(-self.w) / norm
+        } else {
+            2.0 * norm.atan2(self.w) / norm
+        };
+
+        Vector3::new(coeff * self.x, coeff * self.y, coeff * self.z)
+    }
+
+    fn vector_norm(&self) -> f64 {
+        self.x
+            .mul_add(self.x, self.y.mul_add(self.y, self.z * self.z))
+            .sqrt()
+    }
+}
+
+impl Default for Quaternion {
+    fn default() -> Self {
+        Self::new(1.0, 0.0, 0.0, 0.0)
+    }
+}
+
+/// Quaternions are equal if they point the same way and have the same length.
+impl PartialEq for Quaternion {
+    fn eq(&self, other: &Self) -> bool {
+        self.norm().mul_add(-other.norm(), self.dot(other)).abs() < 1E-9
+            && (self.norm() - other.norm()).abs() < 1E-9
+    }
+}
+
+impl From<nalgebra::Quaternion<f64>> for Quaternion {
+    fn from(q: nalgebra::Quaternion<f64>) -> Self {
+        Self::new(q.w, q.i, q.j, q.k)
+    }
+}
+
+impl From<Quaternion> for nalgebra::Quaternion<f64> {
+    fn from(q: Quaternion) -> Self {
+        Self::new(q.w, q.x, q.y, q.z)
+    }
+}
+
+impl ops::Add for Quaternion {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        self.plus(&other)
+    }
+}
+
+impl ops::Sub for Quaternion {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        self.minus(&other)
+    }
+}
+
+impl ops::Mul for Quaternion {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        self.multiply(&other)
+    }
+}
+
+impl ops::Mul<f64> for Quaternion {
+    type Output = Self;
+    fn mul(self, scalar: f64) -> Self {
+        self.times(scalar)
+    }
+}
+
+impl ops::Mul<Quaternion> for f64 {
+    type Output = Quaternion;
+    fn mul(self, quaternion: Quaternion) -> Quaternion {
+        quaternion.times(self)
+    }
+}
+
+impl ops::Div<f64> for Quaternion {
+    type Output = Self;
+    fn div(self, scalar: f64) -> Self {
+        self.divide(scalar)
+    }
+}
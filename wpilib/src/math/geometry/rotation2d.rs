@@ -2,7 +2,6 @@ use crate::math::units::{angle::Radian, distance::Meter};
 use std::fmt::Display;
 use std::ops;
 
-use nalgebra::ComplexField;
 use num::clamp;
 
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -17,14 +16,14 @@ impl Rotation2d {
         let value: Radian = angle.into();
         Self {
             value,
-            sin: value.sin().into(),
-            cos: value.cos().into(),
+            sin: value.value().sin(),
+            cos: value.value().cos(),
         }
     }
     pub fn new_xy(x: impl Into<Meter>, y: impl Into<Meter>) -> Self {
         let x = x.into();
         let y = y.into();
-        let magnitude = x.hypot(y);
+        let magnitude = x.value().hypot(y.value());
         let sin;
         let cos;
         if magnitude > 1e-6 {
@@ -72,9 +71,12 @@ impl Rotation2d {
         self.sin / self.cos
     }
 
+    /// Interpolates along the shortest arc between the two rotations.
     #[must_use]
     pub fn interpolate(&self, end_value: &Self, t: f64) -> Self {
-        self.plus(&end_value.minus(self).times(clamp(t, 0.0, 1.0)))
+        let delta = end_value.minus(self);
+        let delta = Self::new(delta.sin.atan2(delta.cos));
+        self.plus(&delta.times(clamp(t, 0.0, 1.0)))
     }
 }
 
@@ -3,11 +3,12 @@ use nalgebra::{
     Rotation3, Unit, UnitQuaternion, Vector3, U1, U3,
 };
 use num::clamp;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::ops;
 
 use crate::math::units::angle::Radian;
 
-use super::Rotation2d;
+use super::{Quaternion as WpiQuaternion, Rotation2d};
 
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct Rotation3d {
@@ -26,10 +27,11 @@ impl Rotation3d {
         }
     }
 
+    /// Constructs a rotation from a quaternion, normalizing it first.
     #[must_use]
-    pub fn new_quaternion(q: Quaternion<f64>) -> Self {
+    pub fn new_quaternion(q: WpiQuaternion) -> Self {
         Self {
-            q: UnitQuaternion::new_normalize(q),
+            q: UnitQuaternion::new_normalize(q.into()),
         }
     }
 
@@ -40,6 +42,9 @@ impl Rotation3d {
 
     #[must_use]
     fn new_axis_angle(axis: Vector3<f64>, angle: impl Into<Radian>) -> Self {
+        if axis.norm() == 0.0 {
+            return Self::default();
+        }
         let axis: Unit<Vector3<f64>> = Unit::new_normalize(axis);
         Self {
             q: UnitQuaternion::from_axis_angle(&axis, f64::from(angle.into())),
@@ -122,13 +127,19 @@ impl Rotation3d {
         self.times(1.0 / scalar)
     }
 
+    /// Applies `other` after this rotation, measured in the fixed frame.
     #[must_use]
     pub fn rotate_by(&self, other: &Self) -> Self {
         Self {
-            q: self.q * other.q,
+            q: other.q * self.q,
         }
     }
 
+    #[must_use]
+    pub fn get_quaternion(&self) -> WpiQuaternion {
+        (*self.q.quaternion()).into()
+    }
+
     #[must_use]
     pub fn get_x(&self) -> Radian {
         let w = self.q.w;
@@ -138,7 +149,7 @@ impl Rotation3d {
 
         f64::atan2(
             2.0 * w.mul_add(x, y * z),
-            2.0f64.mul_add((-x).mul_add(x, y * y), 1.0),
+            (-2.0f64).mul_add(x.mul_add(x, y * y), 1.0),
         )
         .into()
     }
@@ -167,17 +178,18 @@ impl Rotation3d {
 
         f64::atan2(
             2.0 * w.mul_add(z, x * y),
-            2.0f64.mul_add((-y).mul_add(y, z * z), 1.0),
+            (-2.0f64).mul_add(y.mul_add(y, z * z), 1.0),
         )
         .into()
     }
 
     #[must_use]
     pub fn get_axis(&self) -> Vector3<Radian> {
-        self.q.axis().map_or_else(
-            || Vector3::new(0.0.into(), 0.0.into(), 0.0.into()),
-            |axis: Unit<Vector3<f64>>| Vector3::new(axis.x.into(), axis.y.into(), axis.z.into()),
-        )
+        self.q
+            .axis()
+            .map_or_else(Vector3::zeros, |axis: Unit<Vector3<f64>>| {
+                axis.into_inner().cast()
+            })
     }
 
     #[must_use]
@@ -199,6 +211,28 @@ impl Default for Rotation3d {
     }
 }
 
+/// Rotations are stored as `{"quaternion": {"W": .., "X": .., "Y": .., "Z": ..}}`, like WPILib's JSON.
+#[derive(Serialize, Deserialize)]
+struct QuaternionRotation {
+    quaternion: WpiQuaternion,
+}
+
+impl Serialize for Rotation3d {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        QuaternionRotation {
+            quaternion: self.get_quaternion(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Rotation3d {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        QuaternionRotation::deserialize(deserializer)
+            .map(|rotation| Self::new_quaternion(rotation.quaternion))
+    }
+}
+
 impl From<Rotation2d> for Rotation3d {
     fn from(r: Rotation2d) -> Self {
         Self::new(0.0, 0.0, r.value)
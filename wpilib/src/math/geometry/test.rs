@@ -1,8 +1,11 @@
-use crate::math::geometry::{Rotation2d, Rotation3d, Translation2d, Translation3d};
-use crate::math::units::angle::Degree;
+use crate::math::geometry::{
+    Pose3d, Quaternion, Rotation2d, Rotation3d, Transform3d, Translation2d, Translation3d, Twist3d,
+};
+use crate::math::units::angle::{Degree, Radian};
 use crate::math::units::distance::Meter;
 use approx::assert_relative_eq;
-use nalgebra::UnitQuaternion;
+use nalgebra::{UnitQuaternion, Vector2, Vector3};
+use std::f64::consts::PI;
 
 #[test]
 fn rotation2d() {
@@ -79,6 +82,20 @@ fn translation2d() {
         blahaj_1,
         Translation2d::new(Meter::new(1.0), Meter::new(1.0))
     );
+
+    // Typed vectors
+    let vector = Vector2::new(Meter::new(3.0), Meter::new(-4.0));
+    let translation = Translation2d::new_vector(vector);
+    assert_eq!(translation, Translation2d::new(3.0, -4.0));
+    assert_eq!(translation.get_vector(), vector);
+    assert_eq!(
+        Translation2d::from(vector + vector).get_norm(),
+        Meter::new(10.0)
+    );
+    assert_eq!(
+        Vector2::<Meter>::from(blahaj_2),
+        Vector2::new(Meter::new(2.0), Meter::new(2.0))
+    );
 }
 
 #[test]
@@ -134,6 +151,13 @@ fn rotation3d() {
         temp.q,
         Rotation3d::new(Degree::new(45.0), Degree::new(0.0), Degree::new(0.0)).q,
     );
+
+    // Axis
+    assert_relative_eq!(
+        rot_1.get_axis(),
+        Vector3::new(Radian::new(1.0), Radian::new(0.0), Radian::new(0.0))
+    );
+    assert_eq!(Rotation3d::default().get_axis(), Vector3::zeros());
 }
 
 fn quaternian_relative_eq(q1: UnitQuaternion<f64>, q2: UnitQuaternion<f64>) {
@@ -185,3 +209,306 @@ fn translation3d() {
         Translation3d::new(Meter::new(0.5), Meter::new(0.5), Meter::new(0.5))
     );
 }
+
+fn pose3d_relative_eq(actual: Pose3d, expected: Pose3d) {
+    assert_relative_eq!(
+        actual.translation.x.value(),
+        expected.translation.x.value(),
+        epsilon = 1e-9
+    );
+    assert_relative_eq!(
+        actual.translation.y.value(),
+        expected.translation.y.value(),
+        epsilon = 1e-9
+    );
+    assert_relative_eq!(
+        actual.translation.z.value(),
+        expected.translation.z.value(),
+        epsilon = 1e-9
+    );
+    assert_relative_eq!(
+        actual.rotation.q.angle_to(&expected.rotation.q),
+        0.0,
+        epsilon = 1e-9
+    );
+}
+
+#[test]
+fn pose3d() {
+    let yaw = |degrees: f64| Rotation3d::new(0.0, 0.0, Degree::new(degrees));
+
+    // Transform by rotations
+    let initial = Pose3d::new();
+    let transformed = initial
+        .transform_by(Transform3d::new_trans_rot(
+            Translation3d::default(),
+            Rotation3d::new(Degree::new(90.0), Degree::new(45.0), 0.0),
+        ))
+        .transform_by(Transform3d::new_trans_rot(
+            Translation3d::default(),
+            Rotation3d::new(Degree::new(-90.0), 0.0, 0.0),
+        ))
+        .transform_by(Transform3d::new_trans_rot(
+            Translation3d::default(),
+            Rotation3d::new(0.0, Degree::new(-45.0), 0.0),
+        ));
+    assert_relative_eq!(transformed.rotation.get_x().value(), 0.0, epsilon = 1e-9);
+    assert_relative_eq!(transformed.rotation.get_y().value(), 0.0, epsilon = 1e-9);
+    assert_relative_eq!(transformed.rotation.get_z().value(), 0.0, epsilon = 1e-9);
+
+    // Transform by
+    let initial = Pose3d::new_xyz_rot(1.0, 2.0, 0.0, yaw(45.0));
+    let transformed = initial.plus(Transform3d::new_trans_rot(
+        Translation3d::new(5.0, 0.0, 0.0),
+        yaw(5.0),
+    ));
+    pose3d_relative_eq(
+        transformed,
+        Pose3d::new_xyz_rot(
+            1.0 + 5.0 / 2.0_f64.sqrt(),
+            2.0 + 5.0 / 2.0_f64.sqrt(),
+            0.0,
+            yaw(50.0),
+        ),
+    );
+
+    // Relative to and minus
+    let initial = Pose3d::new_xyz_rot(0.0, 0.0, 0.0, yaw(45.0));
+    let last = Pose3d::new_xyz_rot(5.0, 5.0, 0.0, yaw(45.0));
+    let expected = Pose3d::new_xyz_rot(5.0 * 2.0_f64.sqrt(), 0.0, 0.0, yaw(0.0));
+    pose3d_relative_eq(last.relative_to(&initial), expected);
+    let transform = last.minus(&initial);
+    pose3d_relative_eq(
+        Pose3d::new_trans_rot(transform.translation, transform.rotation),
+        expected,
+    );
+
+    // Nearest
+    let poses = [
+        Pose3d::new_xyz_rot(5.0, 5.0, 0.0, yaw(0.0)),
+        Pose3d::new_xyz_rot(1.0, 1.0, 1.0, yaw(0.0)),
+        Pose3d::new_xyz_rot(-3.0, 0.0, 0.0, yaw(0.0)),
+    ];
+    assert_eq!(Pose3d::new().nearest(&poses), Some(poses[1]));
+    assert_eq!(Pose3d::new().nearest(&[]), None);
+
+    // Interpolate
+    let start = Pose3d::new();
+    let end = Pose3d::new_xyz_rot(5.0, 5.0, 0.0, yaw(90.0));
+    assert_eq!(start.interpolate(&end, -0.5), start);
+    assert_eq!(start.interpolate(&end, 1.5), end);
+    let halfway = start.interpolate(&end, 0.5);
+    assert_relative_eq!(
+        halfway.translation.x.value(),
+        5.0 / 2.0_f64.sqrt(),
+        epsilon = 1e-9
+    );
+    assert_relative_eq!(
+        halfway.translation.y.value(),
+        5.0 - 5.0 / 2.0_f64.sqrt(),
+        epsilon = 1e-9
+    );
+    assert_relative_eq!(
+        Degree::from(halfway.rotation.get_z()).value(),
+        45.0,
+        epsilon = 1e-9
+    );
+}
+
+#[test]
+fn twist3d() {
+    // Straight line
+    let pose = Pose3d::new().exp(Twist3d::new(5.0, 0.0, 0.0, 0.0, 0.0, 0.0));
+    pose3d_relative_eq(
+        pose,
+        Pose3d::new_xyz_rot(5.0, 0.0, 0.0, Rotation3d::default()),
+    );
+
+    // Quarter circle
+    let quarter_circle = Twist3d::new(5.0 / 2.0 * PI, 0.0, 0.0, 0.0, 0.0, PI / 2.0);
+    let pose = Pose3d::new().exp(quarter_circle);
+    pose3d_relative_eq(
+        pose,
+        Pose3d::new_xyz_rot(5.0, 5.0, 0.0, Rotation3d::new(0.0, 0.0, Degree::new(90.0))),
+    );
+
+    // Diagonal with no rotation
+    let pose = Pose3d::new().exp(Twist3d::new(2.0, 2.0, 0.0, 0.0, 0.0, 0.0));
+    pose3d_relative_eq(
+        pose,
+        Pose3d::new_xyz_rot(2.0, 2.0, 0.0, Rotation3d::default()),
+    );
+
+    // Log undoes exp
+    let twist = Pose3d::new().log(&pose3d_quarter_circle_end());
+    assert_relative_eq!(twist.dx.value(), 5.0 / 2.0 * PI, epsilon = 1e-9);
+    assert_relative_eq!(twist.dy.value(), 0.0, epsilon = 1e-9);
+    assert_relative_eq!(twist.dz.value(), 0.0, epsilon = 1e-9);
+    assert_relative_eq!(twist.rx.value(), 0.0, epsilon = 1e-9);
+    assert_relative_eq!(twist.ry.value(), 0.0, epsilon = 1e-9);
+    assert_relative_eq!(twist.rz.value(), PI / 2.0, epsilon = 1e-9);
+    pose3d_relative_eq(Pose3d::new().exp(twist), pose3d_quarter_circle_end());
+
+    // Small angles fall back to the Taylor series
+    let pose = Pose3d::new_xyz_rot(1.0, 2.0, 3.0, Rotation3d::new(0.1, -0.2, 0.3));
+    let end = pose.exp(Twist3d::new(0.5, -0.25, 0.1, 1e-12, 0.0, -1e-12));
+    let twist = pose.log(&end);
+    assert_relative_eq!(twist.dx.value(), 0.5, epsilon = 1e-9);
+    assert_relative_eq!(twist.dy.value(), -0.25, epsilon = 1e-9);
+    assert_relative_eq!(twist.dz.value(), 0.1, epsilon = 1e-9);
+}
+
+fn pose3d_quarter_circle_end() -> Pose3d {
+    Pose3d::new_xyz_rot(5.0, 5.0, 0.0, Rotation3d::new(0.0, 0.0, Degree::new(90.0)))
+}
+
+#[test]
+fn transform3d() {
+    let yaw = |degrees: f64| Rotation3d::new(0.0, 0.0, Degree::new(degrees));
+    let initial = Pose3d::new_xyz_rot(1.0, 2.0, 3.0, yaw(45.0));
+    let transform_1 = Transform3d::new_trans_rot(Translation3d::new(5.0, 4.0, 3.0), yaw(5.0));
+    let transform_2 = Transform3d::new_trans_rot(
+        Translation3d::new(5.0, 0.0, 1.0),
+        Rotation3d::new(Degree::new(10.0), 0.0, 0.0),
+    );
+
+    // Inverse
+    pose3d_relative_eq(
+        initial.plus(transform_1).plus(transform_1.inverse()),
+        initial,
+    );
+    pose3d_relative_eq(initial.plus(transform_1).plus(-transform_1), initial);
+
+    // Composition
+    pose3d_relative_eq(
+        initial.plus(transform_1).plus(transform_2),
+        initial.plus(transform_1 + transform_2),
+    );
+    pose3d_relative_eq(
+        initial.plus(transform_1).plus(transform_2.inverse()),
+        initial.plus(transform_1 - transform_2),
+    );
+    let mut temp = transform_1;
+    temp += transform_2;
+    temp -= transform_2;
+    pose3d_relative_eq(initial.plus(temp), initial.plus(transform_1));
+
+    // Scaling
+    let scaled = transform_1 * 2.0 / 2.0;
+    assert_relative_eq!(scaled.translation.x.value(), 5.0, epsilon = 1e-9);
+    assert_relative_eq!(
+        scaled.rotation.q.angle_to(&transform_1.rotation.q),
+        0.0,
+        epsilon = 1e-9
+    );
+}
+
+fn quaternion_relative_eq(actual: Quaternion, expected: Quaternion) {
+    assert_relative_eq!(actual.w, expected.w, epsilon = 1e-9);
+    assert_relative_eq!(actual.x, expected.x, epsilon = 1e-9);
+    assert_relative_eq!(actual.y, expected.y, epsilon = 1e-9);
+    assert_relative_eq!(actual.z, expected.z, epsilon = 1e-9);
+}
+
+#[test]
+fn quaternion() {
+    let q = Quaternion::new(0.75, 0.3, 0.4, 0.5);
+    let quarter_x = Quaternion::new((PI / 4.0).cos(), (PI / 4.0).sin(), 0.0, 0.0);
+    let half_x = Quaternion::new(0.0, 1.0, 0.0, 0.0);
+
+    // Arithmetic
+    quaternion_relative_eq(q + q, Quaternion::new(1.5, 0.6, 0.8, 1.0));
+    quaternion_relative_eq(q - q, Quaternion::new(0.0, 0.0, 0.0, 0.0));
+    quaternion_relative_eq(q * 2.0, q + q);
+    quaternion_relative_eq(2.0 * q / 2.0, q);
+    quaternion_relative_eq(quarter_x * quarter_x, half_x);
+    quaternion_relative_eq(q * q.inverse(), Quaternion::default());
+    quaternion_relative_eq(q.conjugate(), Quaternion::new(0.75, -0.3, -0.4, -0.5));
+    assert_relative_eq!(q.dot(&q), 1.0625);
+    assert_relative_eq!(q.norm(), 1.0625_f64.sqrt());
+    assert_relative_eq!(q.normalize().norm(), 1.0);
+    assert_eq!(
+        Quaternion::new(0.0, 0.0, 0.0, 0.0).normalize(),
+        Quaternion::default()
+    );
+
+    // Equality ignores floating point noise but not length or direction
+    assert_eq!(q, Quaternion::new(0.75 + 1e-12, 0.3, 0.4, 0.5));
+    assert_ne!(q, q * 2.0);
+    assert_ne!(q, -1.0 * q);
+
+    // Exp and log
+    let q = q.normalize();
+    quaternion_relative_eq(q.log().exp(), q);
+    quaternion_relative_eq(
+        Quaternion::default().log(),
+        Quaternion::new(0.0, 0.0, 0.0, 0.0),
+    );
+    quaternion_relative_eq(half_x.log(), Quaternion::new(0.0, PI / 2.0, 0.0, 0.0));
+    quaternion_relative_eq(half_x.log().exp(), half_x);
+    quaternion_relative_eq(
+        Quaternion::new(-1.0, 0.0, 0.0, 0.0).log(),
+        Quaternion::new(0.0, -PI, 0.0, 0.0),
+    );
+    quaternion_relative_eq(quarter_x.log_to(&half_x), quarter_x.log());
+    quaternion_relative_eq(quarter_x.exp_with(&quarter_x.log()), half_x);
+
+    // Pow
+    quaternion_relative_eq(q.pow(2.0), q * q);
+    quaternion_relative_eq(q.pow(0.5) * q.pow(0.5), q);
+    quaternion_relative_eq(q.pow(0.0), Quaternion::default());
+    quaternion_relative_eq(quarter_x.pow(2.0), half_x);
+}
+
+#[test]
+fn quaternion_rotation_vector() {
+    // 180° about z
+    let rvec = Vector3::new(0.0, 0.0, PI);
+    let q = Quaternion::from_rotation_vector(rvec);
+    quaternion_relative_eq(q, Quaternion::new(0.0, 0.0, 0.0, 1.0));
+    assert_relative_eq!(q.to_rotation_vector(), rvec, epsilon = 1e-9);
+
+    // just past 180°, where w goes negative
+    let q = Quaternion::new(-1e-12, 0.0, 0.0, 1.0);
+    assert_relative_eq!(q.to_rotation_vector().norm(), PI, epsilon = 1e-9);
+
+    // small angles fall back to the Taylor series
+    let rvec = Vector3::new(1e-12, -2e-12, 0.0);
+    let q = Quaternion::from_rotation_vector(rvec);
+    assert_relative_eq!(q.to_rotation_vector(), rvec, epsilon = 1e-18);
+
+    let rvec = Vector3::new(0.3, -0.4, 0.5);
+    assert_relative_eq!(
+        Quaternion::from_rotation_vector(rvec).to_rotation_vector(),
+        rvec,
+        epsilon = 1e-9
+    );
+    quaternion_relative_eq(
+        Quaternion::from_rotation_vector(rvec),
+        Rotation3d::new_rotation_vector(rvec).get_quaternion(),
+    );
+}
+
+#[test]
+fn quaternion_rotation3d() {
+    let rotation = Rotation3d::new(PI, 0.0, 0.0);
+    quaternion_relative_eq(
+        rotation.get_quaternion(),
+        Quaternion::new(0.0, 1.0, 0.0, 0.0),
+    );
+
+    let rotation = Rotation3d::new_quaternion(Quaternion::new(2.0, 0.0, 0.0, 2.0));
+    quaternion_relative_eq(
+        rotation.get_quaternion(),
+        Quaternion::new(0.5_f64.sqrt(), 0.0, 0.0, 0.5_f64.sqrt()),
+    );
+    assert_relative_eq!(Degree::from(rotation.get_z()).value(), 90.0, epsilon = 1e-9);
+}
+
+#[test]
+fn quaternion_serde() {
+    let q = Quaternion::new(1.0, 0.0, 0.5, -0.5);
+    let json = serde_json::to_string(&q).unwrap();
+    assert_eq!(json, r#"{"W":1.0,"X":0.0,"Y":0.5,"Z":-0.5}"#);
+    assert_eq!(serde_json::from_str::<Quaternion>(&json).unwrap(), q);
+}
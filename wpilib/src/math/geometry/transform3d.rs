@@ -1,4 +1,5 @@
 use super::{Pose3d, Rotation3d, Transform2d, Translation3d};
+use std::ops;
 
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct Transform3d {
@@ -54,6 +55,11 @@ impl Transform3d {
         )
     }
 
+    #[must_use]
+    pub fn minus(&self, other: &Self) -> Self {
+        self.plus(&other.inverse())
+    }
+
     #[must_use]
     pub fn inverse(&self) -> Self {
         Self::new_trans_rot(
@@ -82,3 +88,69 @@ impl From<Transform3d> for Transform2d {
         Self::new_trans_rot(transform.translation.into(), transform.rotation.into())
     }
 }
+
+impl ops::Add for Transform3d {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        self.plus(&other)
+    }
+}
+
+impl ops::AddAssign for Transform3d {
+    fn add_assign(&mut self, other: Self) {
+        *self = self.plus(&other);
+    }
+}
+
+impl ops::Sub for Transform3d {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        self.minus(&other)
+    }
+}
+
+impl ops::SubAssign for Transform3d {
+    fn sub_assign(&mut self, other: Self) {
+        *self = self.minus(&other);
+    }
+}
+
+impl ops::Neg for Transform3d {
+    type Output = Self;
+    fn neg(self) -> Self {
+        self.inverse()
+    }
+}
+
+impl ops::Mul<f64> for Transform3d {
+    type Output = Self;
+    fn mul(self, scalar: f64) -> Self {
+        self.times(scalar)
+    }
+}
+
+impl ops::Mul<Transform3d> for f64 {
+    type Output = Transform3d;
+    fn mul(self, transform: Transform3d) -> Transform3d {
+        transform.times(self)
+    }
+}
+
+impl ops::MulAssign<f64> for Transform3d {
+    fn mul_assign(&mut self, scalar: f64) {
+        *self = self.times(scalar);
+    }
+}
+
+impl ops::Div<f64> for Transform3d {
+    type Output = Self;
+    fn div(self, scalar: f64) -> Self {
+        self.times(1.0 / scalar)
+    }
+}
+
+impl ops::DivAssign<f64> for Transform3d {
+    fn div_assign(&mut self, scalar: f64) {
+        *self = self.times(1.0 / scalar);
+    }
+}
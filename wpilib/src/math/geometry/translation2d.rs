@@ -1,4 +1,4 @@
-use nalgebra::{ComplexField, Translation2};
+use nalgebra::Vector2;
 use std::ops;
 
 use super::Rotation2d;
@@ -20,6 +20,13 @@ impl Translation2d {
         }
     }
     #[must_use]
+    pub fn new_vector(vector: Vector2<Meter>) -> Self {
+        Self {
+            x: vector.x,
+            y: vector.y,
+        }
+    }
+    #[must_use]
     pub fn new_dist_angle(distance: impl Into<Meter>, angle: Rotation2d) -> Self {
         let distance: Meter = distance.into();
         Self {
@@ -32,12 +39,12 @@ impl Translation2d {
     pub fn get_distance(&self, other: &Self) -> Meter {
         let delta_x = other.x - self.x;
         let delta_y = other.y - self.y;
-        ComplexField::hypot(delta_x, delta_y).into()
+        Meter::new(delta_x.value().hypot(delta_y.value()))
     }
 
     #[must_use]
     pub fn get_norm(&self) -> Meter {
-        ComplexField::hypot(self.x, self.y).into()
+        Meter::new(self.x.value().hypot(self.y.value()))
     }
 
     #[must_use]
@@ -96,8 +103,8 @@ impl Translation2d {
     }
 
     #[must_use]
-    pub const fn get_vector(&self) -> Translation2<Meter> {
-        Translation2::new(self.x, self.y)
+    pub const fn get_vector(&self) -> Vector2<Meter> {
+        Vector2::new(self.x, self.y)
     }
 
     // #[must_use] pub fn strength(&self) -> Meter {
@@ -122,6 +129,18 @@ impl Default for Translation2d {
     }
 }
 
+impl From<Vector2<Meter>> for Translation2d {
+    fn from(vector: Vector2<Meter>) -> Self {
+        Self::new_vector(vector)
+    }
+}
+
+impl From<Translation2d> for Vector2<Meter> {
+    fn from(translation: Translation2d) -> Self {
+        translation.get_vector()
+    }
+}
+
 impl ops::Add for Translation2d {
     type Output = Self;
     fn add(self, other: Self) -> Self {
@@ -1,4 +1,5 @@
-use nalgebra::{ComplexField, Quaternion};
+use nalgebra::Quaternion;
+use serde::{Deserialize, Serialize};
 use std::ops;
 
 use crate::math::units::distance::Meter;
@@ -6,7 +7,7 @@ use crate::math::util::math_util::MathUtil;
 
 use super::{Rotation3d, Translation2d};
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Translation3d {
     pub x: Meter,
     pub y: Meter,
@@ -34,15 +35,18 @@ impl Translation3d {
     }
 
     #[must_use]
-    fn get_distance(&self, other: &Self) -> Meter {
-        ComplexField::sqrt(
-            (other.x - self.x).square() + (other.y - self.y).square() + (other.z - self.z).square(),
+    pub fn get_distance(&self, other: &Self) -> Meter {
+        Meter::new(
+            (other.x - self.x)
+                .value()
+                .hypot((other.y - self.y).value())
+                .hypot((other.z - self.z).value()),
         )
     }
 
     #[must_use]
     pub fn get_norm(&self) -> Meter {
-        ComplexField::sqrt(self.x.square() + self.y.square() + self.z.square())
+        Meter::new(self.x.value().hypot(self.y.value()).hypot(self.z.value()))
     }
 
     #[must_use]
@@ -0,0 +1,88 @@
+use std::ops;
+
+use crate::math::geometry::Rotation2d;
+use crate::math::units::angular_velocity::RadianPerSecond;
+use crate::math::units::linear_velocity::MeterPerSecond;
+
+/// The velocity of a robot chassis, relative to the robot unless stated otherwise.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ChassisSpeeds {
+    pub vx: MeterPerSecond,
+    pub vy: MeterPerSecond,
+    pub omega: RadianPerSecond,
+}
+
+impl ChassisSpeeds {
+    #[must_use]
+    pub fn new(
+        vx: impl Into<MeterPerSecond>,
+        vy: impl Into<MeterPerSecond>,
+        omega: impl Into<RadianPerSecond>,
+    ) -> Self {
+        Self {
+            vx: vx.into(),
+            vy: vy.into(),
+            omega: omega.into(),
+        }
+    }
+
+    /// Converts field-relative speeds into robot-relative speeds given the robot's heading.
+    #[must_use]
+    pub fn from_field_relative_speeds(
+        vx: impl Into<MeterPerSecond>,
+        vy: impl Into<MeterPerSecond>,
+        omega: impl Into<RadianPerSecond>,
+        robot_angle: Rotation2d,
+    ) -> Self {
+        let vx: f64 = vx.into().value();
+        let vy: f64 = vy.into().value();
+        Self::new(
+            vx.mul_add(robot_angle.cos, vy * robot_angle.sin),
+            (-vx).mul_add(robot_angle.sin, vy * robot_angle.cos),
+            omega,
+        )
+    }
+
+    #[must_use]
+    pub fn times(&self, scalar: f64) -> Self {
+        Self::new(
+            self.vx.value() * scalar,
+            self.vy.value() * scalar,
+            self.omega.value() * scalar,
+        )
+    }
+}
+
+impl Default for ChassisSpeeds {
+    fn default() -> Self {
+        Self::new(0.0, 0.0, 0.0)
+    }
+}
+
+impl ops::Add for ChassisSpeeds {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.vx + rhs.vx, self.vy + rhs.vy, self.omega + rhs.omega)
+    }
+}
+
+impl ops::Sub for ChassisSpeeds {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.vx - rhs.vx, self.vy - rhs.vy, self.omega - rhs.omega)
+    }
+}
+
+impl ops::Neg for ChassisSpeeds {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self::new(-self.vx, -self.vy, -self.omega)
+    }
+}
+
+impl ops::Mul<f64> for ChassisSpeeds {
+    type Output = Self;
+    fn mul(self, rhs: f64) -> Self::Output {
+        self.times(rhs)
+    }
+}
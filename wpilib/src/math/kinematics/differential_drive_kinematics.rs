@@ -0,0 +1,115 @@
+use crate::math::geometry::Twist2d;
+use crate::math::units::distance::Meter;
+use crate::math::units::linear_velocity::MeterPerSecond;
+use crate::math::util::interpolatable::Interpolatable;
+use crate::math::util::math_util::MathUtil;
+
+use super::{ChassisSpeeds, Kinematics};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DifferentialDriveWheelSpeeds {
+    pub left: MeterPerSecond,
+    pub right: MeterPerSecond,
+}
+
+impl DifferentialDriveWheelSpeeds {
+    #[must_use]
+    pub fn new(left: impl Into<MeterPerSecond>, right: impl Into<MeterPerSecond>) -> Self {
+        Self {
+            left: left.into(),
+            right: right.into(),
+        }
+    }
+
+    /// Scales both speeds down so neither exceeds `max_speed`, keeping their ratio.
+    pub fn desaturate(&mut self, max_speed: impl Into<MeterPerSecond>) {
+        let max_speed: f64 = max_speed.into().value();
+        let real_max = self.left.value().abs().max(self.right.value().abs());
+        if real_max > max_speed {
+            self.left = (self.left.value() / real_max * max_speed).into();
+            self.right = (self.right.value() / real_max * max_speed).into();
+        }
+    }
+}
+
+impl Default for DifferentialDriveWheelSpeeds {
+    fn default() -> Self {
+        Self::new(0.0, 0.0)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DifferentialDriveWheelPositions {
+    pub left: Meter,
+    pub right: Meter,
+}
+
+impl DifferentialDriveWheelPositions {
+    #[must_use]
+    pub fn new(left: impl Into<Meter>, right: impl Into<Meter>) -> Self {
+        Self {
+            left: left.into(),
+            right: right.into(),
+        }
+    }
+}
+
+impl Default for DifferentialDriveWheelPositions {
+    fn default() -> Self {
+        Self::new(0.0, 0.0)
+    }
+}
+
+impl Interpolatable for DifferentialDriveWheelPositions {
+    fn interpolate(&self, end_value: &Self, t: f64) -> Self {
+        Self::new(
+            MathUtil::interpolate(self.left.value(), end_value.left.value(), t),
+            MathUtil::interpolate(self.right.value(), end_value.right.value(), t),
+        )
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DifferentialDriveKinematics {
+    pub track_width: Meter,
+}
+
+impl DifferentialDriveKinematics {
+    #[must_use]
+    pub fn new(track_width: impl Into<Meter>) -> Self {
+        Self {
+            track_width: track_width.into(),
+        }
+    }
+}
+
+impl Kinematics for DifferentialDriveKinematics {
+    type WheelSpeeds = DifferentialDriveWheelSpeeds;
+    type WheelPositions = DifferentialDriveWheelPositions;
+
+    fn to_chassis_speeds(&self, wheel_speeds: &Self::WheelSpeeds) -> ChassisSpeeds {
+        let left = wheel_speeds.left.value();
+        let right = wheel_speeds.right.value();
+        ChassisSpeeds::new(
+            (left + right) / 2.0,
+            0.0,
+            (right - left) / self.track_width.value(),
+        )
+    }
+
+    fn to_wheel_speeds(&self, chassis_speeds: &ChassisSpeeds) -> Self::WheelSpeeds {
+        let vx = chassis_speeds.vx.value();
+        let offset = self.track_width.value() / 2.0 * chassis_speeds.omega.value();
+        DifferentialDriveWheelSpeeds::new(vx - offset, vx + offset)
+    }
+
+    fn to_twist2d(&self, start: &Self::WheelPositions, end: &Self::WheelPositions) -> Twist2d {
+        let left = (end.left - start.left).value();
+        let right = (end.right - start.right).value();
+        Twist2d::new(
+            (left + right) / 2.0,
+            0.0,
+            (right - left) / self.track_width.value(),
+        )
+    }
+}
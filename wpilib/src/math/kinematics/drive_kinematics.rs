@@ -0,0 +1,17 @@
+use crate::math::geometry::Twist2d;
+use crate::math::util::interpolatable::Interpolatable;
+
+use super::ChassisSpeeds;
+
+/// Converts between the velocities/positions of a drivetrain's wheels and the motion of its chassis.
+pub trait Kinematics {
+    type WheelSpeeds;
+    type WheelPositions: Interpolatable;
+
+    /// Returns the chassis speeds produced by the given wheel speeds.
+    fn to_chassis_speeds(&self, wheel_speeds: &Self::WheelSpeeds) -> ChassisSpeeds;
+    /// Returns the wheel speeds needed to achieve the given chassis speeds.
+    fn to_wheel_speeds(&self, chassis_speeds: &ChassisSpeeds) -> Self::WheelSpeeds;
+    /// Returns the twist the chassis underwent while the wheels moved from `start` to `end`.
+    fn to_twist2d(&self, start: &Self::WheelPositions, end: &Self::WheelPositions) -> Twist2d;
+}
@@ -0,0 +1,182 @@
+use nalgebra::{Matrix3x4, Matrix4x3, Vector3, Vector4};
+
+use crate::math::geometry::{Translation2d, Twist2d};
+use crate::math::units::distance::Meter;
+use crate::math::units::linear_velocity::MeterPerSecond;
+use crate::math::util::interpolatable::Interpolatable;
+use crate::math::util::math_util::MathUtil;
+
+use super::{ChassisSpeeds, Kinematics};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct MecanumDriveWheelSpeeds {
+    pub front_left: MeterPerSecond,
+    pub front_right: MeterPerSecond,
+    pub rear_left: MeterPerSecond,
+    pub rear_right: MeterPerSecond,
+}
+
+impl MecanumDriveWheelSpeeds {
+    #[must_use]
+    pub fn new(
+        front_left: impl Into<MeterPerSecond>,
+        front_right: impl Into<MeterPerSecond>,
+        rear_left: impl Into<MeterPerSecond>,
+        rear_right: impl Into<MeterPerSecond>,
+    ) -> Self {
+        Self {
+            front_left: front_left.into(),
+            front_right: front_right.into(),
+            rear_left: rear_left.into(),
+            rear_right: rear_right.into(),
+        }
+    }
+
+    /// Scales all speeds down so none exceeds `max_speed`, keeping their ratios.
+    pub fn desaturate(&mut self, max_speed: impl Into<MeterPerSecond>) {
+        let max_speed: f64 = max_speed.into().value();
+        let real_max = self
+            .front_left
+            .value()
+            .abs()
+            .max(self.front_right.value().abs())
+            .max(self.rear_left.value().abs())
+            .max(self.rear_right.value().abs());
+        if real_max > max_speed {
+            let scale = max_speed / real_max;
+            self.front_left = (self.front_left.value() * scale).into();
+            self.front_right = (self.front_right.value() * scale).into();
+            self.rear_left = (self.rear_left.value() * scale).into();
+            self.rear_right = (self.rear_right.value() * scale).into();
+        }
+    }
+}
+
+impl Default for MecanumDriveWheelSpeeds {
+    fn default() -> Self {
+        Self::new(0.0, 0.0, 0.0, 0.0)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct MecanumDriveWheelPositions {
+    pub front_left: Meter,
+    pub front_right: Meter,
+    pub rear_left: Meter,
+    pub rear_right: Meter,
+}
+
+impl MecanumDriveWheelPositions {
+    #[must_use]
+    pub fn new(
+        front_left: impl Into<Meter>,
+        front_right: impl Into<Meter>,
+        rear_left: impl Into<Meter>,
+        rear_right: impl Into<Meter>,
+    ) -> Self {
+        Self {
+            front_left: front_left.into(),
+            front_right: front_right.into(),
+            rear_left: rear_left.into(),
+            rear_right: rear_right.into(),
+        }
+    }
+}
+
+impl Default for MecanumDriveWheelPositions {
+    fn default() -> Self {
+        Self::new(0.0, 0.0, 0.0, 0.0)
+    }
+}
+
+impl Interpolatable for MecanumDriveWheelPositions {
+    fn interpolate(&self, end_value: &Self, t: f64) -> Self {
+        Self::new(
+            MathUtil::interpolate(self.front_left.value(), end_value.front_left.value(), t),
+            MathUtil::interpolate(self.front_right.value(), end_value.front_right.value(), t),
+            MathUtil::interpolate(self.rear_left.value(), end_value.rear_left.value(), t),
+            MathUtil::interpolate(self.rear_right.value(), end_value.rear_right.value(), t),
+        )
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct MecanumDriveKinematics {
+    inverse_kinematics: Matrix4x3<f64>,
+    forward_kinematics: Matrix3x4<f64>,
+}
+
+impl MecanumDriveKinematics {
+    /// Creates kinematics from the location of each wheel relative to the robot center.
+    ///
+    /// # Panics
+    /// Panics if the wheels are all at the same location, which leaves the chassis rotation
+    /// unsolvable.
+    #[must_use]
+    pub fn new(
+        front_left: Translation2d,
+        front_right: Translation2d,
+        rear_left: Translation2d,
+        rear_right: Translation2d,
+    ) -> Self {
+        let fl = (front_left.x.value(), front_left.y.value());
+        let fr = (front_right.x.value(), front_right.y.value());
+        let rl = (rear_left.x.value(), rear_left.y.value());
+        let rr = (rear_right.x.value(), rear_right.y.value());
+        #[rustfmt::skip]
+        let inverse_kinematics = Matrix4x3::new(
+            1.0, -1.0, -(fl.0 + fl.1),
+            1.0, 1.0, fr.0 - fr.1,
+            1.0, 1.0, rl.0 - rl.1,
+            1.0, -1.0, -(rr.0 + rr.1),
+        );
+        assert!(
+            inverse_kinematics.rank(1e-9) == 3,
+            "mecanum wheels must not all be at the same location"
+        );
+        let forward_kinematics = inverse_kinematics
+            .pseudo_inverse(1e-9)
+            .expect("epsilon is non-negative");
+        Self {
+            inverse_kinematics,
+            forward_kinematics,
+        }
+    }
+}
+
+impl Kinematics for MecanumDriveKinematics {
+    type WheelSpeeds = MecanumDriveWheelSpeeds;
+    type WheelPositions = MecanumDriveWheelPositions;
+
+    fn to_chassis_speeds(&self, wheel_speeds: &Self::WheelSpeeds) -> ChassisSpeeds {
+        let chassis = self.forward_kinematics
+            * Vector4::new(
+                wheel_speeds.front_left.value(),
+                wheel_speeds.front_right.value(),
+                wheel_speeds.rear_left.value(),
+                wheel_speeds.rear_right.value(),
+            );
+        ChassisSpeeds::new(chassis[0], chassis[1], chassis[2])
+    }
+
+    fn to_wheel_speeds(&self, chassis_speeds: &ChassisSpeeds) -> Self::WheelSpeeds {
+        let wheels = self.inverse_kinematics
+            * Vector3::new(
+                chassis_speeds.vx.value(),
+                chassis_speeds.vy.value(),
+                chassis_speeds.omega.value(),
+            );
+        MecanumDriveWheelSpeeds::new(wheels[0], wheels[1], wheels[2], wheels[3])
+    }
+
+    fn to_twist2d(&self, start: &Self::WheelPositions, end: &Self::WheelPositions) -> Twist2d {
+        let twist = self.forward_kinematics
+            * Vector4::new(
+                (end.front_left - start.front_left).value(),
+                (end.front_right - start.front_right).value(),
+                (end.rear_left - start.rear_left).value(),
+                (end.rear_right - start.rear_right).value(),
+            );
+        Twist2d::new(twist[0], twist[1], twist[2])
+    }
+}
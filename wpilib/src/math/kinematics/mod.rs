@@ -0,0 +1,14 @@
+mod chassis_speeds;
+mod differential_drive_kinematics;
+mod drive_kinematics;
+mod mecanum_drive_kinematics;
+mod odometry;
+mod swerve_drive_kinematics;
+mod test;
+
+pub use chassis_speeds::*;
+pub use differential_drive_kinematics::*;
+pub use drive_kinematics::*;
+pub use mecanum_drive_kinematics::*;
+pub use odometry::*;
+pub use swerve_drive_kinematics::*;
@@ -0,0 +1,168 @@
+use crate::math::geometry::{Pose2d, Pose3d, Rotation2d, Rotation3d, Twist3d};
+use crate::math::util::math_util::MathUtil;
+
+use super::{
+    DifferentialDriveKinematics, Kinematics, MecanumDriveKinematics, SwerveDriveKinematics,
+};
+
+/// Tracks a robot's field pose by integrating wheel positions and a gyro heading.
+///
+/// The gyro is trusted for heading; the wheels only contribute translation.
+#[derive(Clone, Debug)]
+pub struct Odometry<K: Kinematics> {
+    kinematics: K,
+    pose: Pose2d,
+    gyro_offset: Rotation2d,
+    previous_angle: Rotation2d,
+    previous_wheel_positions: K::WheelPositions,
+}
+
+pub type DifferentialDriveOdometry = Odometry<DifferentialDriveKinematics>;
+pub type MecanumDriveOdometry = Odometry<MecanumDriveKinematics>;
+pub type SwerveDriveOdometry<const N: usize> = Odometry<SwerveDriveKinematics<N>>;
+
+impl<K: Kinematics> Odometry<K> {
+    #[must_use]
+    pub fn new(
+        kinematics: K,
+        gyro_angle: Rotation2d,
+        wheel_positions: K::WheelPositions,
+        initial_pose: Pose2d,
+    ) -> Self {
+        Self {
+            kinematics,
+            pose: initial_pose,
+            gyro_offset: initial_pose.rotation - gyro_angle,
+            previous_angle: initial_pose.rotation,
+            previous_wheel_positions: wheel_positions,
+        }
+    }
+
+    /// Resets the robot's pose.
+    ///
+    /// The gyro angle and wheel positions do not need to be reset beforehand.
+    pub fn reset_position(
+        &mut self,
+        gyro_angle: Rotation2d,
+        wheel_positions: K::WheelPositions,
+        pose: Pose2d,
+    ) {
+        self.pose = pose;
+        self.previous_angle = pose.rotation;
+        self.gyro_offset = pose.rotation - gyro_angle;
+        self.previous_wheel_positions = wheel_positions;
+    }
+
+    #[must_use]
+    pub const fn get_pose(&self) -> Pose2d {
+        self.pose
+    }
+
+    #[must_use]
+    pub const fn get_kinematics(&self) -> &K {
+        &self.kinematics
+    }
+
+    /// Updates the pose from the current gyro angle and wheel positions, returning the new pose.
+    pub fn update(&mut self, gyro_angle: Rotation2d, wheel_positions: K::WheelPositions) -> Pose2d {
+        let angle = gyro_angle + self.gyro_offset;
+
+        let mut twist = self
+            .kinematics
+            .to_twist2d(&self.previous_wheel_positions, &wheel_positions);
+        twist.dtheta = MathUtil::angle_modulus((angle - self.previous_angle).value.value()).into();
+
+        let new_pose = self.pose.exp(twist);
+
+        self.previous_wheel_positions = wheel_positions;
+        self.previous_angle = angle;
+        self.pose = Pose2d::new(new_pose.translation, angle);
+
+        self.pose
+    }
+}
+
+/// Tracks a robot's pose in 3D by integrating wheel positions and a 3D gyro orientation.
+///
+/// Useful when the robot drives over uneven terrain where pitch and roll are non-negligible.
+#[derive(Clone, Debug)]
+pub struct Odometry3d<K: Kinematics> {
+    kinematics: K,
+    pose: Pose3d,
+    gyro_offset: Rotation3d,
+    previous_angle: Rotation3d,
+    previous_wheel_positions: K::WheelPositions,
+}
+
+pub type DifferentialDriveOdometry3d = Odometry3d<DifferentialDriveKinematics>;
+pub type MecanumDriveOdometry3d = Odometry3d<MecanumDriveKinematics>;
+pub type SwerveDriveOdometry3d<const N: usize> = Odometry3d<SwerveDriveKinematics<N>>;
+
+impl<K: Kinematics> Odometry3d<K> {
+    #[must_use]
+    pub fn new(
+        kinematics: K,
+        gyro_angle: Rotation3d,
+        wheel_positions: K::WheelPositions,
+        initial_pose: Pose3d,
+    ) -> Self {
+        Self {
+            kinematics,
+            pose: initial_pose,
+            gyro_offset: initial_pose.rotation - gyro_angle,
+            previous_angle: initial_pose.rotation,
+            previous_wheel_positions: wheel_positions,
+        }
+    }
+
+    /// Resets the robot's pose.
+    ///
+    /// The gyro angle and wheel positions do not need to be reset beforehand.
+    pub fn reset_position(
+        &mut self,
+        gyro_angle: Rotation3d,
+        wheel_positions: K::WheelPositions,
+        pose: Pose3d,
+    ) {
+        self.pose = pose;
+        self.previous_angle = pose.rotation;
+        self.gyro_offset = pose.rotation - gyro_angle;
+        self.previous_wheel_positions = wheel_positions;
+    }
+
+    #[must_use]
+    pub const fn get_pose(&self) -> Pose3d {
+        self.pose
+    }
+
+    #[must_use]
+    pub const fn get_kinematics(&self) -> &K {
+        &self.kinematics
+    }
+
+    /// Updates the pose from the current gyro orientation and wheel positions, returning the new pose.
+    pub fn update(&mut self, gyro_angle: Rotation3d, wheel_positions: K::WheelPositions) -> Pose3d {
+        let angle = gyro_angle + self.gyro_offset;
+        let angle_difference = (angle - self.previous_angle).q.scaled_axis();
+
+        let twist2d = self
+            .kinematics
+            .to_twist2d(&self.previous_wheel_positions, &wheel_positions);
+        let twist = Twist3d::new(
+            twist2d.dx,
+            twist2d.dy,
+            0.0,
+            angle_difference[0],
+            angle_difference[1],
+            angle_difference[2],
+        );
+
+        let new_pose = self.pose.exp(twist);
+
+        self.previous_wheel_positions = wheel_positions;
+        self.previous_angle = angle;
+        self.pose = Pose3d::new_trans_rot(new_pose.translation, angle);
+
+        self.pose
+    }
+}
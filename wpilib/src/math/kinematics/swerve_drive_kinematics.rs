@@ -0,0 +1,184 @@
+use std::f64::consts::FRAC_PI_2;
+
+use nalgebra::{DMatrix, DVector, Vector3};
+
+use crate::math::geometry::{Rotation2d, Translation2d, Twist2d};
+use crate::math::units::angle::Degree;
+use crate::math::units::distance::Meter;
+use crate::math::units::linear_velocity::MeterPerSecond;
+use crate::math::util::interpolatable::Interpolatable;
+use crate::math::util::math_util::MathUtil;
+
+use super::{ChassisSpeeds, Kinematics};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SwerveModuleState {
+    pub speed: MeterPerSecond,
+    pub angle: Rotation2d,
+}
+
+impl SwerveModuleState {
+    #[must_use]
+    pub fn new(speed: impl Into<MeterPerSecond>, angle: Rotation2d) -> Self {
+        Self {
+            speed: speed.into(),
+            angle,
+        }
+    }
+
+    /// Flips the desired state if that gets the module there with less than a quarter turn.
+    #[must_use]
+    pub fn optimize(desired: &Self, current_angle: &Rotation2d) -> Self {
+        let delta = MathUtil::angle_modulus((desired.angle.value - current_angle.value).value());
+        if delta.abs() > FRAC_PI_2 {
+            Self::new(
+                -desired.speed,
+                desired
+                    .angle
+                    .rotate_by(&Rotation2d::new(Degree::new(180.0))),
+            )
+        } else {
+            *desired
+        }
+    }
+}
+
+impl Default for SwerveModuleState {
+    fn default() -> Self {
+        Self::new(0.0, Rotation2d::default())
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SwerveModulePosition {
+    pub distance: Meter,
+    pub angle: Rotation2d,
+}
+
+impl SwerveModulePosition {
+    #[must_use]
+    pub fn new(distance: impl Into<Meter>, angle: Rotation2d) -> Self {
+        Self {
+            distance: distance.into(),
+            angle,
+        }
+    }
+}
+
+impl Default for SwerveModulePosition {
+    fn default() -> Self {
+        Self::new(0.0, Rotation2d::default())
+    }
+}
+
+impl Interpolatable for SwerveModulePosition {
+    fn interpolate(&self, end_value: &Self, t: f64) -> Self {
+        Self::new(
+            MathUtil::interpolate(self.distance.value(), end_value.distance.value(), t),
+            self.angle.interpolate(&end_value.angle, t),
+        )
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SwerveDriveKinematics<const N: usize> {
+    modules: [Translation2d; N],
+    inverse_kinematics: DMatrix<f64>,
+    forward_kinematics: DMatrix<f64>,
+}
+
+impl<const N: usize> SwerveDriveKinematics<N> {
+    /// Creates kinematics from the location of each module relative to the robot center.
+    ///
+    /// # Panics
+    /// Panics if fewer than two modules are given, or if they are all at the same location, which
+    /// leaves the chassis rotation unsolvable.
+    #[must_use]
+    pub fn new(modules: [Translation2d; N]) -> Self {
+        assert!(N >= 2, "a swerve drive requires at least two modules");
+        let mut inverse_kinematics = DMatrix::zeros(N * 2, 3);
+        for (i, module) in modules.iter().enumerate() {
+            inverse_kinematics[(i * 2, 0)] = 1.0;
+            inverse_kinematics[(i * 2, 2)] = -module.y.value();
+            inverse_kinematics[(i * 2 + 1, 1)] = 1.0;
+            inverse_kinematics[(i * 2 + 1, 2)] = module.x.value();
+        }
+        assert!(
+            inverse_kinematics.rank(1e-9) == 3,
+            "swerve modules must not all be at the same location"
+        );
+        let forward_kinematics = inverse_kinematics
+            .clone()
+            .pseudo_inverse(1e-9)
+            .expect("epsilon is non-negative");
+        Self {
+            modules,
+            inverse_kinematics,
+            forward_kinematics,
+        }
+    }
+
+    #[must_use]
+    pub const fn get_modules(&self) -> &[Translation2d; N] {
+        &self.modules
+    }
+
+    /// Scales all module speeds down so none exceeds `max_speed`, keeping their ratios.
+    pub fn desaturate_wheel_speeds(
+        states: &mut [SwerveModuleState; N],
+        max_speed: impl Into<MeterPerSecond>,
+    ) {
+        let max_speed: f64 = max_speed.into().value();
+        let real_max = states
+            .iter()
+            .map(|state| state.speed.value().abs())
+            .fold(0.0, f64::max);
+        if real_max > max_speed {
+            for state in states.iter_mut() {
+                state.speed = (state.speed.value() / real_max * max_speed).into();
+            }
+        }
+    }
+}
+
+impl<const N: usize> Kinematics for SwerveDriveKinematics<N> {
+    type WheelSpeeds = [SwerveModuleState; N];
+    type WheelPositions = [SwerveModulePosition; N];
+
+    fn to_chassis_speeds(&self, wheel_speeds: &Self::WheelSpeeds) -> ChassisSpeeds {
+        let mut module_velocities = DVector::zeros(N * 2);
+        for (i, state) in wheel_speeds.iter().enumerate() {
+            module_velocities[i * 2] = state.speed.value() * state.angle.cos;
+            module_velocities[i * 2 + 1] = state.speed.value() * state.angle.sin;
+        }
+        let chassis = &self.forward_kinematics * module_velocities;
+        ChassisSpeeds::new(chassis[0], chassis[1], chassis[2])
+    }
+
+    fn to_wheel_speeds(&self, chassis_speeds: &ChassisSpeeds) -> Self::WheelSpeeds {
+        let module_velocities = &self.inverse_kinematics
+            * Vector3::new(
+                chassis_speeds.vx.value(),
+                chassis_speeds.vy.value(),
+                chassis_speeds.omega.value(),
+            );
+        let mut states = [SwerveModuleState::default(); N];
+        for (i, state) in states.iter_mut().enumerate() {
+            let x = module_velocities[i * 2];
+            let y = module_velocities[i * 2 + 1];
+            *state = SwerveModuleState::new(x.hypot(y), Rotation2d::new_xy(x, y));
+        }
+        states
+    }
+
+    fn to_twist2d(&self, start: &Self::WheelPositions, end: &Self::WheelPositions) -> Twist2d {
+        let mut module_deltas = DVector::zeros(N * 2);
+        for (i, (start, end)) in start.iter().zip(end.iter()).enumerate() {
+            let distance = (end.distance - start.distance).value();
+            module_deltas[i * 2] = distance * end.angle.cos;
+            module_deltas[i * 2 + 1] = distance * end.angle.sin;
+        }
+        let twist = &self.forward_kinematics * module_deltas;
+        Twist2d::new(twist[0], twist[1], twist[2])
+    }
+}
@@ -0,0 +1,190 @@
+use crate::math::geometry::{Pose2d, Pose3d, Rotation2d, Rotation3d, Translation2d};
+use crate::math::kinematics::{
+    ChassisSpeeds, DifferentialDriveKinematics, DifferentialDriveOdometry,
+    DifferentialDriveOdometry3d, DifferentialDriveWheelPositions, Kinematics,
+    MecanumDriveKinematics, MecanumDriveOdometry, MecanumDriveWheelPositions,
+    MecanumDriveWheelSpeeds, SwerveDriveKinematics, SwerveDriveOdometry, SwerveModulePosition,
+};
+use crate::math::units::angle::Degree;
+use approx::assert_relative_eq;
+use std::f64::consts::PI;
+
+fn swerve_kinematics() -> SwerveDriveKinematics<4> {
+    SwerveDriveKinematics::new([
+        Translation2d::new(1.0, 1.0),
+        Translation2d::new(1.0, -1.0),
+        Translation2d::new(-1.0, 1.0),
+        Translation2d::new(-1.0, -1.0),
+    ])
+}
+
+fn mecanum_kinematics() -> MecanumDriveKinematics {
+    MecanumDriveKinematics::new(
+        Translation2d::new(12.0, 12.0),
+        Translation2d::new(12.0, -12.0),
+        Translation2d::new(-12.0, 12.0),
+        Translation2d::new(-12.0, -12.0),
+    )
+}
+
+#[test]
+fn differential_kinematics() {
+    let kinematics = DifferentialDriveKinematics::new(0.381 * 2.0);
+
+    let speeds = kinematics.to_wheel_speeds(&ChassisSpeeds::new(0.0, 0.0, PI));
+    assert_relative_eq!(speeds.left.value(), -0.381 * PI, epsilon = 1e-9);
+    assert_relative_eq!(speeds.right.value(), 0.381 * PI, epsilon = 1e-9);
+
+    let chassis = kinematics.to_chassis_speeds(&speeds);
+    assert_relative_eq!(chassis.vx.value(), 0.0, epsilon = 1e-9);
+    assert_relative_eq!(chassis.omega.value(), PI, epsilon = 1e-9);
+}
+
+#[test]
+fn mecanum_kinematics_round_trip() {
+    let kinematics = mecanum_kinematics();
+
+    let speeds = kinematics.to_wheel_speeds(&ChassisSpeeds::new(0.0, 0.0, 2.0 * PI));
+    assert_relative_eq!(speeds.front_left.value(), -150.796_447, epsilon = 1e-6);
+    assert_relative_eq!(speeds.front_right.value(), 150.796_447, epsilon = 1e-6);
+    assert_relative_eq!(speeds.rear_left.value(), -150.796_447, epsilon = 1e-6);
+    assert_relative_eq!(speeds.rear_right.value(), 150.796_447, epsilon = 1e-6);
+
+    let chassis = kinematics.to_chassis_speeds(&MecanumDriveWheelSpeeds::new(-4.0, 4.0, 4.0, -4.0));
+    assert_relative_eq!(chassis.vx.value(), 0.0, epsilon = 1e-9);
+    assert_relative_eq!(chassis.vy.value(), 4.0, epsilon = 1e-9);
+    assert_relative_eq!(chassis.omega.value(), 0.0, epsilon = 1e-9);
+}
+
+#[test]
+fn swerve_kinematics_round_trip() {
+    let kinematics = swerve_kinematics();
+
+    let states = kinematics.to_wheel_speeds(&ChassisSpeeds::new(0.0, 0.0, 1.0));
+    for state in &states {
+        assert_relative_eq!(state.speed.value(), 2.0_f64.sqrt(), epsilon = 1e-9);
+    }
+    assert_relative_eq!(
+        states[0].angle.value.value(),
+        Degree::new(135.0).value().to_radians(),
+        epsilon = 1e-9
+    );
+
+    let chassis = kinematics.to_chassis_speeds(&states);
+    assert_relative_eq!(chassis.vx.value(), 0.0, epsilon = 1e-9);
+    assert_relative_eq!(chassis.vy.value(), 0.0, epsilon = 1e-9);
+    assert_relative_eq!(chassis.omega.value(), 1.0, epsilon = 1e-9);
+}
+
+#[test]
+#[should_panic(expected = "swerve modules must not all be at the same location")]
+fn swerve_kinematics_degenerate() {
+    let _ = SwerveDriveKinematics::new([Translation2d::new(0.5, 0.5); 3]);
+}
+
+#[test]
+#[should_panic(expected = "mecanum wheels must not all be at the same location")]
+fn mecanum_kinematics_degenerate() {
+    let location = Translation2d::new(0.0, 0.0);
+    let _ = MecanumDriveKinematics::new(location, location, location, location);
+}
+
+#[test]
+fn differential_odometry_arc() {
+    let mut odometry = DifferentialDriveOdometry::new(
+        DifferentialDriveKinematics::new(0.5),
+        Rotation2d::default(),
+        DifferentialDriveWheelPositions::default(),
+        Pose2d::default(),
+    );
+
+    // a quarter circle of radius 1 meter, turning left
+    let pose = odometry.update(
+        Rotation2d::new(Degree::new(90.0)),
+        DifferentialDriveWheelPositions::new(0.75 * PI / 2.0, 1.25 * PI / 2.0),
+    );
+    assert_relative_eq!(pose.translation.x.value(), 1.0, epsilon = 1e-9);
+    assert_relative_eq!(pose.translation.y.value(), 1.0, epsilon = 1e-9);
+    assert_relative_eq!(pose.rotation.value.value(), PI / 2.0, epsilon = 1e-9);
+}
+
+#[test]
+fn odometry_gyro_wrap() {
+    let mut odometry = DifferentialDriveOdometry::new(
+        DifferentialDriveKinematics::new(0.5),
+        Rotation2d::new(Degree::new(179.0)),
+        DifferentialDriveWheelPositions::default(),
+        Pose2d::default(),
+    );
+
+    // the gyro rolling over from 179 to -179 degrees is a 2 degree turn, not 358
+    let pose = odometry.update(
+        Rotation2d::new(Degree::new(-179.0)),
+        DifferentialDriveWheelPositions::new(1.0, 1.0),
+    );
+    assert_relative_eq!(pose.translation.x.value(), 1.0, epsilon = 1e-3);
+    assert_relative_eq!(pose.translation.y.value(), 0.0175, epsilon = 1e-3);
+}
+
+#[test]
+fn odometry_reset_position() {
+    let mut odometry = MecanumDriveOdometry::new(
+        mecanum_kinematics(),
+        Rotation2d::default(),
+        MecanumDriveWheelPositions::default(),
+        Pose2d::default(),
+    );
+
+    odometry.reset_position(
+        Rotation2d::new(Degree::new(90.0)),
+        MecanumDriveWheelPositions::new(5.0, 5.0, 5.0, 5.0),
+        Pose2d::new_xy_rot(1.0, 2.0, Rotation2d::default()),
+    );
+
+    // drive straight while the gyro still reports the pre-reset heading
+    let pose = odometry.update(
+        Rotation2d::new(Degree::new(90.0)),
+        MecanumDriveWheelPositions::new(8.0, 8.0, 8.0, 8.0),
+    );
+    assert_relative_eq!(pose.translation.x.value(), 4.0, epsilon = 1e-9);
+    assert_relative_eq!(pose.translation.y.value(), 2.0, epsilon = 1e-9);
+    assert_relative_eq!(pose.rotation.value.value(), 0.0, epsilon = 1e-9);
+}
+
+#[test]
+fn swerve_odometry_two_iterations() {
+    let zero = SwerveModulePosition::default();
+    let mut odometry = SwerveDriveOdometry::new(
+        swerve_kinematics(),
+        Rotation2d::default(),
+        [zero; 4],
+        Pose2d::default(),
+    );
+
+    let position = SwerveModulePosition::new(0.5, Rotation2d::default());
+    odometry.update(Rotation2d::default(), [zero; 4]);
+    let pose = odometry.update(Rotation2d::default(), [position; 4]);
+
+    assert_relative_eq!(pose.translation.x.value(), 0.5, epsilon = 0.01);
+    assert_relative_eq!(pose.translation.y.value(), 0.0, epsilon = 0.01);
+    assert_relative_eq!(pose.rotation.value.value(), 0.0, epsilon = 0.01);
+}
+
+#[test]
+fn differential_odometry_3d() {
+    let mut odometry = DifferentialDriveOdometry3d::new(
+        DifferentialDriveKinematics::new(0.5),
+        Rotation3d::default(),
+        DifferentialDriveWheelPositions::default(),
+        Pose3d::default(),
+    );
+
+    let pose = odometry.update(
+        Rotation3d::new(0.0, 0.0, Degree::new(90.0)),
+        DifferentialDriveWheelPositions::new(0.75 * PI / 2.0, 1.25 * PI / 2.0),
+    );
+    assert_relative_eq!(pose.translation.x.value(), 1.0, epsilon = 1e-9);
+    assert_relative_eq!(pose.translation.y.value(), 1.0, epsilon = 1e-9);
+    assert_relative_eq!(pose.translation.z.value(), 0.0, epsilon = 1e-9);
+    assert_relative_eq!(pose.rotation.get_z().value(), PI / 2.0, epsilon = 1e-9);
+}
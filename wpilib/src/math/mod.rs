@@ -1,7 +1,11 @@
 pub mod controllers;
+pub mod estimator;
 pub mod filter;
 pub mod geometry;
+pub mod kinematics;
+#[cfg(feature = "simulation")]
 pub mod simulation;
+pub mod system;
 pub mod trajectory;
 pub mod units;
 pub mod util;
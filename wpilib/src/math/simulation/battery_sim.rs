@@ -0,0 +1,106 @@
+use crate::math::units::energy::{Amp, Ohm, Volt};
+
+/// Simulates the voltage sag of a battery under load, and the brownout the roboRIO goes into once
+/// the battery sags below its brownout voltage.
+///
+/// Every simulated mechanism takes the battery when its input voltage is set, which limits that
+/// voltage to [what the battery can supply](Self::get_available_voltage), or nothing while browned
+/// out. Only simulated mechanisms are limited this way; real outputs aren't affected.
+///
+/// Call [`update`](Self::update) with the currents the mechanisms draw once per loop, from
+/// [`UserRobot::sim_periodic`](crate::robots::UserRobot::sim_periodic), and return the battery from
+/// [`UserRobot::sim_battery`](crate::robots::UserRobot::sim_battery) so the robot publishes its
+/// voltage and calls [`UserRobot::robot_brownout`](crate::robots::UserRobot::robot_brownout)
+/// when it browns out.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct BatterySim {
+    nominal_voltage: Volt,
+    resistance: Ohm,
+    brownout_voltage: Volt,
+    voltage: Volt,
+}
+
+impl BatterySim {
+    /// Creates an unloaded battery with `nominal_voltage` and internal `resistance`, browning out
+    /// below 6.75 V like the roboRIO.
+    #[must_use]
+    pub fn new(nominal_voltage: impl Into<Volt>, resistance: impl Into<Ohm>) -> Self {
+        let nominal_voltage = nominal_voltage.into();
+        Self {
+            nominal_voltage,
+            resistance: resistance.into(),
+            brownout_voltage: Volt::new(6.75),
+            voltage: nominal_voltage,
+        }
+    }
+
+    #[must_use]
+    pub fn with_brownout_voltage(mut self, brownout_voltage: impl Into<Volt>) -> Self {
+        self.brownout_voltage = brownout_voltage.into();
+        self
+    }
+
+    /// Sags the battery to its voltage while supplying `currents`, returning that voltage.
+    pub fn update(&mut self, currents: &[Amp]) -> Volt {
+        self.voltage =
+            Self::calculate_loaded_battery_voltage(self.nominal_voltage, self.resistance, currents);
+        self.voltage
+    }
+
+    /// Returns the voltage across the battery as of the last [`update`](Self::update).
+    #[must_use]
+    pub const fn get_voltage(&self) -> Volt {
+        self.voltage
+    }
+
+    #[must_use]
+    pub const fn get_brownout_voltage(&self) -> Volt {
+        self.brownout_voltage
+    }
+
+    #[must_use]
+    pub fn is_browned_out(&self) -> bool {
+        self.voltage < self.brownout_voltage
+    }
+
+    /// Returns the largest voltage simulated mechanisms can apply: the battery voltage, or nothing
+    /// while browned out.
+    #[must_use]
+    pub fn get_available_voltage(&self) -> Volt {
+        if self.is_browned_out() {
+            Volt::new(0.0)
+        } else {
+            self.voltage
+        }
+    }
+
+    /// Returns the voltage across a battery with `nominal_voltage` and internal `resistance`
+    /// while supplying `currents`, never less than zero.
+    #[must_use]
+    pub fn calculate_loaded_battery_voltage(
+        nominal_voltage: impl Into<Volt>,
+        resistance: impl Into<Ohm>,
+        currents: &[Amp],
+    ) -> Volt {
+        let total_current: f64 = currents.iter().map(Amp::value).sum();
+        let voltage = resistance
+            .into()
+            .value()
+            .mul_add(-total_current, nominal_voltage.into().value());
+        Volt::new(voltage.max(0.0))
+    }
+
+    /// Returns the voltage across a typical 12 V FRC battery with 0.02 Ω of internal resistance
+    /// while supplying `currents`.
+    #[must_use]
+    pub fn calculate_default_battery_loaded_voltage(currents: &[Amp]) -> Volt {
+        Self::calculate_loaded_battery_voltage(12.0, 0.02, currents)
+    }
+}
+
+impl Default for BatterySim {
+    /// A typical 12 V FRC battery with 0.02 Ω of internal resistance.
+    fn default() -> Self {
+        Self::new(12.0, 0.02)
+    }
+}
@@ -0,0 +1,110 @@
+use nalgebra::{Vector1, Vector2};
+
+use crate::math::system::{DCMotor, LinearSystem, LinearSystemId};
+use crate::math::units::angle::Radian;
+use crate::math::units::angular_velocity::{RadianPerSecond, RotationPerMinute};
+use crate::math::units::energy::{Amp, Volt};
+use crate::math::units::moment_of_inertia::KilogramSquareMeter;
+use crate::math::units::time::Second;
+
+use super::battery_sim::BatterySim;
+use super::linear_system_sim::{motor_current_draw, LinearSystemSim};
+
+/// Simulates the position and velocity of a DC motor driving an inertial load.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DCMotorSim {
+    sim: LinearSystemSim<2, 1, 2>,
+    gearbox: DCMotor,
+    gearing: f64,
+}
+
+impl DCMotorSim {
+    /// `gearing` is the reduction from the motors to the load, greater than one for a reduction.
+    #[must_use]
+    pub fn new(
+        gearbox: DCMotor,
+        gearing: f64,
+        moment_of_inertia: impl Into<KilogramSquareMeter>,
+    ) -> Self {
+        Self::new_plant(
+            LinearSystemId::create_dc_motor_system(gearbox, moment_of_inertia, gearing),
+            gearbox,
+            gearing,
+        )
+    }
+
+    /// Creates a simulation of an existing plant, such as one from
+    /// [`LinearSystemId::create_dc_motor_system_kv_ka`].
+    #[must_use]
+    pub fn new_plant(plant: LinearSystem<2, 1, 2>, gearbox: DCMotor, gearing: f64) -> Self {
+        Self {
+            sim: LinearSystemSim::new(plant),
+            gearbox,
+            gearing,
+        }
+    }
+
+    /// Sets the standard deviations of the noise added to the measured position (radians) and
+    /// velocity (rad/s).
+    #[must_use]
+    pub const fn with_measurement_std_devs(mut self, position: f64, velocity: f64) -> Self {
+        self.sim = self
+            .sim
+            .with_measurement_std_devs(Vector2::new(position, velocity));
+        self
+    }
+
+    /// Applies `voltage`, limited to what `battery` can supply.
+    pub fn set_input_voltage(&mut self, voltage: impl Into<Volt>, battery: &BatterySim) {
+        self.sim.set_input(Vector1::new(voltage.into().value()));
+        self.sim
+            .clamp_input(battery.get_available_voltage().value());
+    }
+
+    #[must_use]
+    pub fn get_input_voltage(&self) -> Volt {
+        Volt::new(self.sim.get_input_at(0))
+    }
+
+    pub fn set_state(
+        &mut self,
+        angular_position: impl Into<Radian>,
+        angular_velocity: impl Into<RadianPerSecond>,
+    ) {
+        self.sim.set_state(Vector2::new(
+            angular_position.into().value(),
+            angular_velocity.into().value(),
+        ));
+    }
+
+    pub fn update(&mut self, dt: impl Into<Second>) {
+        self.sim.update(dt);
+    }
+
+    /// Returns the measured position of the load.
+    #[must_use]
+    pub fn get_angular_position(&self) -> Radian {
+        Radian::new(self.sim.get_output_at(0))
+    }
+
+    /// Returns the measured velocity of the load.
+    #[must_use]
+    pub fn get_angular_velocity(&self) -> RadianPerSecond {
+        RadianPerSecond::new(self.sim.get_output_at(1))
+    }
+
+    #[must_use]
+    pub fn get_angular_velocity_rpm(&self) -> RotationPerMinute {
+        self.get_angular_velocity().into()
+    }
+
+    /// Returns the current drawn by the gearbox.
+    #[must_use]
+    pub fn get_current_draw(&self) -> Amp {
+        motor_current_draw(
+            &self.gearbox,
+            self.sim.get_state_at(1) * self.gearing,
+            self.sim.get_input_at(0),
+        )
+    }
+}
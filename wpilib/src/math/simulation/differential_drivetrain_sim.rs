@@ -0,0 +1,197 @@
+use nalgebra::{SVector, Vector2};
+
+use crate::math::geometry::{Pose2d, Rotation2d, Twist2d};
+use crate::math::system::{DCMotor, LinearSystem, LinearSystemId};
+use crate::math::units::distance::Meter;
+use crate::math::units::energy::{Amp, Volt};
+use crate::math::units::linear_velocity::MeterPerSecond;
+use crate::math::units::mass::Kilogram;
+use crate::math::units::moment_of_inertia::KilogramSquareMeter;
+use crate::math::units::time::Second;
+use crate::math::util::state_space_util::StateSpaceUtil;
+
+use super::battery_sim::BatterySim;
+use super::linear_system_sim::motor_current_draw;
+
+/// Simulates the pose and wheel encoders of a differential drivetrain.
+///
+/// Measurements are ordered [x, y, heading, left velocity, right velocity, left position,
+/// right position], in meters, radians and m/s.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DifferentialDrivetrainSim {
+    plant: LinearSystem<2, 2, 2>,
+    gearbox: DCMotor,
+    gearing: f64,
+    wheel_radius: Meter,
+    track_width: Meter,
+    pose: Pose2d,
+    velocities: Vector2<f64>,
+    positions: Vector2<f64>,
+    u: Vector2<f64>,
+    y: SVector<f64, 7>,
+    measurement_std_devs: SVector<f64, 7>,
+}
+
+impl DifferentialDrivetrainSim {
+    /// `gearbox` is the motors driving one side of the drivetrain and `gearing` is the reduction
+    /// from them to the wheels.
+    #[must_use]
+    pub fn new(
+        gearbox: DCMotor,
+        gearing: f64,
+        moment_of_inertia: impl Into<KilogramSquareMeter>,
+        mass: impl Into<Kilogram>,
+        wheel_radius: impl Into<Meter>,
+        track_width: impl Into<Meter>,
+    ) -> Self {
+        let wheel_radius: Meter = wheel_radius.into();
+        let track_width: Meter = track_width.into();
+        Self {
+            plant: LinearSystemId::create_drivetrain_velocity_system(
+                gearbox,
+                mass,
+                wheel_radius,
+                track_width,
+                moment_of_inertia,
+                gearing,
+            ),
+            gearbox,
+            gearing,
+            wheel_radius,
+            track_width,
+            pose: Pose2d::default(),
+            velocities: Vector2::zeros(),
+            positions: Vector2::zeros(),
+            u: Vector2::zeros(),
+            y: SVector::zeros(),
+            measurement_std_devs: SVector::zeros(),
+        }
+    }
+
+    /// Sets the standard deviations of the noise added to each measurement.
+    #[must_use]
+    pub const fn with_measurement_std_devs(
+        mut self,
+        measurement_std_devs: SVector<f64, 7>,
+    ) -> Self {
+        self.measurement_std_devs = measurement_std_devs;
+        self
+    }
+
+    /// Applies voltages to each side, limited to what `battery` can supply.
+    pub fn set_input_voltage(
+        &mut self,
+        left_voltage: impl Into<Volt>,
+        right_voltage: impl Into<Volt>,
+        battery: &BatterySim,
+    ) {
+        let max_voltage = battery.get_available_voltage().value();
+        self.u = Vector2::new(left_voltage.into().value(), right_voltage.into().value())
+            .map(|voltage| voltage.clamp(-max_voltage, max_voltage));
+    }
+
+    #[must_use]
+    pub fn get_left_input_voltage(&self) -> Volt {
+        Volt::new(self.u[0])
+    }
+
+    #[must_use]
+    pub fn get_right_input_voltage(&self) -> Volt {
+        Volt::new(self.u[1])
+    }
+
+    /// Advances the simulation by `dt` with the current inputs held constant.
+    pub fn update(&mut self, dt: impl Into<Second>) {
+        let dt: Second = dt.into();
+        let velocities = self.plant.calculate_x(&self.velocities, &self.u, dt);
+        let distances = (self.velocities + velocities) / 2.0 * dt.value();
+        self.velocities = velocities;
+        self.positions += distances;
+
+        // the wheels sweep a constant-curvature arc over the step
+        self.pose = self.pose.exp(Twist2d::new(
+            distances.mean(),
+            0.0,
+            (distances[1] - distances[0]) / self.track_width.value(),
+        ));
+        self.y =
+            self.get_state() + StateSpaceUtil::make_white_noise_vector(&self.measurement_std_devs);
+    }
+
+    fn get_state(&self) -> SVector<f64, 7> {
+        SVector::from([
+            self.pose.translation.x.value(),
+            self.pose.translation.y.value(),
+            self.pose.rotation.value.value(),
+            self.velocities[0],
+            self.velocities[1],
+            self.positions[0],
+            self.positions[1],
+        ])
+    }
+
+    /// Moves the robot to `pose` and zeroes the wheel encoders.
+    pub fn set_pose(&mut self, pose: Pose2d) {
+        self.pose = pose;
+        self.positions = Vector2::zeros();
+        self.y = self.get_state();
+    }
+
+    /// Returns the measured pose of the robot.
+    #[must_use]
+    pub fn get_pose(&self) -> Pose2d {
+        Pose2d::new_xy_rot(self.y[0], self.y[1], self.get_heading())
+    }
+
+    /// Returns the measured heading of the robot.
+    #[must_use]
+    pub fn get_heading(&self) -> Rotation2d {
+        Rotation2d::new(self.y[2])
+    }
+
+    #[must_use]
+    pub fn get_left_velocity(&self) -> MeterPerSecond {
+        MeterPerSecond::new(self.y[3])
+    }
+
+    #[must_use]
+    pub fn get_right_velocity(&self) -> MeterPerSecond {
+        MeterPerSecond::new(self.y[4])
+    }
+
+    #[must_use]
+    pub fn get_left_position(&self) -> Meter {
+        Meter::new(self.y[5])
+    }
+
+    #[must_use]
+    pub fn get_right_position(&self) -> Meter {
+        Meter::new(self.y[6])
+    }
+
+    /// Returns the current drawn by the left gearbox.
+    #[must_use]
+    pub fn get_left_current_draw(&self) -> Amp {
+        motor_current_draw(
+            &self.gearbox,
+            self.velocities[0] / self.wheel_radius.value() * self.gearing,
+            self.u[0],
+        )
+    }
+
+    /// Returns the current drawn by the right gearbox.
+    #[must_use]
+    pub fn get_right_current_draw(&self) -> Amp {
+        motor_current_draw(
+            &self.gearbox,
+            self.velocities[1] / self.wheel_radius.value() * self.gearing,
+            self.u[1],
+        )
+    }
+
+    /// Returns the current drawn by both gearboxes.
+    #[must_use]
+    pub fn get_current_draw(&self) -> Amp {
+        self.get_left_current_draw() + self.get_right_current_draw()
+    }
+}
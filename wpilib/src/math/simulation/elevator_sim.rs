@@ -0,0 +1,155 @@
+use nalgebra::{Vector1, Vector2};
+
+use crate::math::system::{DCMotor, LinearSystemId};
+use crate::math::units::distance::Meter;
+use crate::math::units::energy::{Amp, Volt};
+use crate::math::units::linear_velocity::MeterPerSecond;
+use crate::math::units::mass::Kilogram;
+use crate::math::units::time::Second;
+
+use super::battery_sim::BatterySim;
+use super::linear_system_sim::{motor_current_draw, LinearSystemSim};
+
+/// Acceleration due to gravity in m/s².
+const GRAVITY: f64 = 9.8;
+
+/// Simulates an elevator carriage lifted by a drum, stopping hard at its travel limits.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ElevatorSim {
+    sim: LinearSystemSim<2, 1, 1>,
+    gearbox: DCMotor,
+    gearing: f64,
+    drum_radius: Meter,
+    min_height: Meter,
+    max_height: Meter,
+    simulate_gravity: bool,
+}
+
+impl ElevatorSim {
+    /// Creates an elevator resting at `min_height`.
+    ///
+    /// # Panics
+    /// Panics if the mass, drum radius or gearing isn't positive, or if `min_height` is above
+    /// `max_height`.
+    #[must_use]
+    pub fn new(
+        gearbox: DCMotor,
+        gearing: f64,
+        carriage_mass: impl Into<Kilogram>,
+        drum_radius: impl Into<Meter>,
+        min_height: impl Into<Meter>,
+        max_height: impl Into<Meter>,
+        simulate_gravity: bool,
+    ) -> Self {
+        let drum_radius: Meter = drum_radius.into();
+        let min_height: Meter = min_height.into();
+        let max_height: Meter = max_height.into();
+        assert!(
+            min_height <= max_height,
+            "min height must not be above max height"
+        );
+
+        let plant =
+            LinearSystemId::create_elevator_system(gearbox, carriage_mass, drum_radius, gearing);
+        let mut sim = Self {
+            sim: LinearSystemSim::new(plant),
+            gearbox,
+            gearing,
+            drum_radius,
+            min_height,
+            max_height,
+            simulate_gravity,
+        };
+        sim.set_state(min_height, 0.0);
+        sim
+    }
+
+    /// Sets the standard deviation of the noise added to the measured position, in meters.
+    #[must_use]
+    pub const fn with_measurement_std_dev(mut self, std_dev: f64) -> Self {
+        self.sim = self.sim.with_measurement_std_devs(Vector1::new(std_dev));
+        self
+    }
+
+    /// Applies `voltage`, limited to what `battery` can supply.
+    pub fn set_input_voltage(&mut self, voltage: impl Into<Volt>, battery: &BatterySim) {
+        self.sim.set_input(Vector1::new(voltage.into().value()));
+        self.sim
+            .clamp_input(battery.get_available_voltage().value());
+    }
+
+    #[must_use]
+    pub fn get_input_voltage(&self) -> Volt {
+        Volt::new(self.sim.get_input_at(0))
+    }
+
+    /// Moves the carriage, clamping the position to the travel limits.
+    pub fn set_state(&mut self, position: impl Into<Meter>, velocity: impl Into<MeterPerSecond>) {
+        let state = Vector2::new(position.into().value(), velocity.into().value());
+        self.sim.set_state(self.clamp_to_limits(state));
+    }
+
+    pub fn update(&mut self, dt: impl Into<Second>) {
+        let mut input = *self.sim.get_input();
+        if self.simulate_gravity {
+            // gravity is a constant acceleration, so it can be folded into the input
+            input[0] -= GRAVITY / self.sim.get_plant().get_b()[1];
+        }
+        let state = self
+            .sim
+            .get_plant()
+            .calculate_x(self.sim.get_state(), &input, dt);
+        self.sim.measure(self.clamp_to_limits(state));
+    }
+
+    fn clamp_to_limits(&self, state: Vector2<f64>) -> Vector2<f64> {
+        if self.would_hit_lower_limit(state[0]) {
+            Vector2::new(self.min_height.value(), 0.0)
+        } else if self.would_hit_upper_limit(state[0]) {
+            Vector2::new(self.max_height.value(), 0.0)
+        } else {
+            state
+        }
+    }
+
+    #[must_use]
+    pub fn would_hit_lower_limit(&self, height: impl Into<Meter>) -> bool {
+        height.into() <= self.min_height
+    }
+
+    #[must_use]
+    pub fn would_hit_upper_limit(&self, height: impl Into<Meter>) -> bool {
+        height.into() >= self.max_height
+    }
+
+    #[must_use]
+    pub fn has_hit_lower_limit(&self) -> bool {
+        self.would_hit_lower_limit(self.get_position())
+    }
+
+    #[must_use]
+    pub fn has_hit_upper_limit(&self) -> bool {
+        self.would_hit_upper_limit(self.get_position())
+    }
+
+    /// Returns the measured height of the carriage.
+    #[must_use]
+    pub fn get_position(&self) -> Meter {
+        Meter::new(self.sim.get_output_at(0))
+    }
+
+    #[must_use]
+    pub fn get_velocity(&self) -> MeterPerSecond {
+        MeterPerSecond::new(self.sim.get_state_at(1))
+    }
+
+    /// Returns the current drawn by the gearbox.
+    #[must_use]
+    pub fn get_current_draw(&self) -> Amp {
+        motor_current_draw(
+            &self.gearbox,
+            self.sim.get_state_at(1) / self.drum_radius.value() * self.gearing,
+            self.sim.get_input_at(0),
+        )
+    }
+}
@@ -0,0 +1,95 @@
+use nalgebra::Vector1;
+
+use crate::math::system::{DCMotor, LinearSystem, LinearSystemId};
+use crate::math::units::angular_velocity::{RadianPerSecond, RotationPerMinute};
+use crate::math::units::energy::{Amp, Volt};
+use crate::math::units::moment_of_inertia::KilogramSquareMeter;
+use crate::math::units::time::Second;
+
+use super::battery_sim::BatterySim;
+use super::linear_system_sim::{motor_current_draw, LinearSystemSim};
+
+/// Simulates a flywheel driven by a DC motor.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct FlywheelSim {
+    sim: LinearSystemSim<1, 1, 1>,
+    gearbox: DCMotor,
+    gearing: f64,
+}
+
+impl FlywheelSim {
+    /// `gearing` is the reduction from the motors to the flywheel, greater than one for a
+    /// reduction.
+    #[must_use]
+    pub fn new(
+        gearbox: DCMotor,
+        gearing: f64,
+        moment_of_inertia: impl Into<KilogramSquareMeter>,
+    ) -> Self {
+        Self::new_plant(
+            LinearSystemId::create_flywheel_system(gearbox, moment_of_inertia, gearing),
+            gearbox,
+            gearing,
+        )
+    }
+
+    /// Creates a simulation of an existing plant, such as one from
+    /// [`LinearSystemId::identify_velocity_system`].
+    #[must_use]
+    pub fn new_plant(plant: LinearSystem<1, 1, 1>, gearbox: DCMotor, gearing: f64) -> Self {
+        Self {
+            sim: LinearSystemSim::new(plant),
+            gearbox,
+            gearing,
+        }
+    }
+
+    /// Sets the standard deviation of the noise added to the measured velocity, in rad/s.
+    #[must_use]
+    pub const fn with_measurement_std_dev(mut self, std_dev: f64) -> Self {
+        self.sim = self.sim.with_measurement_std_devs(Vector1::new(std_dev));
+        self
+    }
+
+    /// Applies `voltage`, limited to what `battery` can supply.
+    pub fn set_input_voltage(&mut self, voltage: impl Into<Volt>, battery: &BatterySim) {
+        self.sim.set_input(Vector1::new(voltage.into().value()));
+        self.sim
+            .clamp_input(battery.get_available_voltage().value());
+    }
+
+    #[must_use]
+    pub fn get_input_voltage(&self) -> Volt {
+        Volt::new(self.sim.get_input_at(0))
+    }
+
+    pub fn set_state(&mut self, angular_velocity: impl Into<RadianPerSecond>) {
+        self.sim
+            .set_state(Vector1::new(angular_velocity.into().value()));
+    }
+
+    pub fn update(&mut self, dt: impl Into<Second>) {
+        self.sim.update(dt);
+    }
+
+    /// Returns the measured velocity of the flywheel.
+    #[must_use]
+    pub fn get_angular_velocity(&self) -> RadianPerSecond {
+        RadianPerSecond::new(self.sim.get_output_at(0))
+    }
+
+    #[must_use]
+    pub fn get_angular_velocity_rpm(&self) -> RotationPerMinute {
+        self.get_angular_velocity().into()
+    }
+
+    /// Returns the current drawn by the gearbox.
+    #[must_use]
+    pub fn get_current_draw(&self) -> Amp {
+        motor_current_draw(
+            &self.gearbox,
+            self.sim.get_state_at(0) * self.gearing,
+            self.sim.get_input_at(0),
+        )
+    }
+}
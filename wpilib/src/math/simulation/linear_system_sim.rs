@@ -0,0 +1,123 @@
+use nalgebra::SVector;
+
+use crate::math::system::{DCMotor, LinearSystem};
+use crate::math::units::energy::Amp;
+use crate::math::units::time::Second;
+use crate::math::util::state_space_util::StateSpaceUtil;
+
+/// Simulates a [`LinearSystem`], adding gaussian noise to its measured outputs.
+///
+/// Call [`update`](Self::update) once per loop, usually from
+/// [`UserRobot::sim_periodic`](crate::robots::UserRobot::sim_periodic).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct LinearSystemSim<const STATES: usize, const INPUTS: usize, const OUTPUTS: usize> {
+    plant: LinearSystem<STATES, INPUTS, OUTPUTS>,
+    x: SVector<f64, STATES>,
+    u: SVector<f64, INPUTS>,
+    y: SVector<f64, OUTPUTS>,
+    measurement_std_devs: SVector<f64, OUTPUTS>,
+}
+
+impl<const STATES: usize, const INPUTS: usize, const OUTPUTS: usize>
+    LinearSystemSim<STATES, INPUTS, OUTPUTS>
+{
+    /// Creates a noiseless simulation of `plant`, starting at rest with no input.
+    #[must_use]
+    pub fn new(plant: LinearSystem<STATES, INPUTS, OUTPUTS>) -> Self {
+        Self {
+            plant,
+            x: SVector::zeros(),
+            u: SVector::zeros(),
+            y: SVector::zeros(),
+            measurement_std_devs: SVector::zeros(),
+        }
+    }
+
+    /// Sets the standard deviations of the noise added to each measured output.
+    #[must_use]
+    pub const fn with_measurement_std_devs(
+        mut self,
+        measurement_std_devs: SVector<f64, OUTPUTS>,
+    ) -> Self {
+        self.measurement_std_devs = measurement_std_devs;
+        self
+    }
+
+    #[must_use]
+    pub const fn get_plant(&self) -> &LinearSystem<STATES, INPUTS, OUTPUTS> {
+        &self.plant
+    }
+
+    /// Advances the simulation by `dt` with the current input held constant.
+    pub fn update(&mut self, dt: impl Into<Second>) {
+        let x = self.plant.calculate_x(&self.x, &self.u, dt);
+        self.measure(x);
+    }
+
+    /// Moves to state `x` and takes a noisy measurement of it.
+    pub(super) fn measure(&mut self, x: SVector<f64, STATES>) {
+        self.x = x;
+        self.y = self.plant.calculate_y(&self.x, &self.u)
+            + StateSpaceUtil::make_white_noise_vector(&self.measurement_std_devs);
+    }
+
+    /// Returns the most recent, possibly noisy, measurement.
+    #[must_use]
+    pub const fn get_output(&self) -> &SVector<f64, OUTPUTS> {
+        &self.y
+    }
+
+    #[must_use]
+    pub fn get_output_at(&self, row: usize) -> f64 {
+        self.y[row]
+    }
+
+    pub const fn set_input(&mut self, u: SVector<f64, INPUTS>) {
+        self.u = u;
+    }
+
+    pub fn set_input_at(&mut self, row: usize, value: f64) {
+        self.u[row] = value;
+    }
+
+    /// Limits each input to `[-max_input, max_input]`.
+    pub fn clamp_input(&mut self, max_input: f64) {
+        self.u = self.u.map(|u| u.clamp(-max_input, max_input));
+    }
+
+    #[must_use]
+    pub const fn get_input(&self) -> &SVector<f64, INPUTS> {
+        &self.u
+    }
+
+    #[must_use]
+    pub fn get_input_at(&self, row: usize) -> f64 {
+        self.u[row]
+    }
+
+    /// Moves the system to state `x`, replacing the measured output with a noiseless one.
+    pub fn set_state(&mut self, x: SVector<f64, STATES>) {
+        self.x = x;
+        self.y = self.plant.calculate_y(&self.x, &self.u);
+    }
+
+    #[must_use]
+    pub const fn get_state(&self) -> &SVector<f64, STATES> {
+        &self.x
+    }
+
+    #[must_use]
+    pub fn get_state_at(&self, row: usize) -> f64 {
+        self.x[row]
+    }
+}
+
+/// Returns the current drawn by `gearbox` spinning at `motor_speed` with `voltage` applied.
+///
+/// Current only flows in the direction of the applied voltage; regenerative braking isn't modeled.
+pub(super) fn motor_current_draw(gearbox: &DCMotor, motor_speed: f64, voltage: f64) -> Amp {
+    if voltage == 0.0 {
+        return Amp::new(0.0);
+    }
+    Amp::new(gearbox.get_current(motor_speed, voltage).value() * voltage.signum())
+}
@@ -1 +1,17 @@
+mod battery_sim;
+mod dc_motor_sim;
+mod differential_drivetrain_sim;
+mod elevator_sim;
+mod flywheel_sim;
+mod linear_system_sim;
+mod single_jointed_arm_sim;
+#[cfg(test)]
+mod test;
 
+pub use battery_sim::*;
+pub use dc_motor_sim::*;
+pub use differential_drivetrain_sim::*;
+pub use elevator_sim::*;
+pub use flywheel_sim::*;
+pub use linear_system_sim::*;
+pub use single_jointed_arm_sim::*;
@@ -0,0 +1,178 @@
+use nalgebra::{Vector1, Vector2};
+
+use crate::math::system::{DCMotor, LinearSystemId, NumericalIntegration};
+use crate::math::units::angle::Radian;
+use crate::math::units::angular_velocity::RadianPerSecond;
+use crate::math::units::distance::Meter;
+use crate::math::units::energy::{Amp, Volt};
+use crate::math::units::mass::Kilogram;
+use crate::math::units::moment_of_inertia::KilogramSquareMeter;
+use crate::math::units::time::Second;
+
+use super::battery_sim::BatterySim;
+use super::linear_system_sim::{motor_current_draw, LinearSystemSim};
+
+/// Acceleration due to gravity in m/s².
+const GRAVITY: f64 = 9.8;
+
+/// Simulates an arm rotating about one joint, stopping hard at its angle limits.
+///
+/// Angles are measured from horizontal, so gravity pulls hardest at zero.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SingleJointedArmSim {
+    sim: LinearSystemSim<2, 1, 1>,
+    gearbox: DCMotor,
+    gearing: f64,
+    arm_length: Meter,
+    min_angle: Radian,
+    max_angle: Radian,
+    simulate_gravity: bool,
+}
+
+impl SingleJointedArmSim {
+    /// Creates an arm resting at `min_angle`.
+    ///
+    /// # Panics
+    /// Panics if the moment of inertia, arm length or gearing isn't positive, or if `min_angle`
+    /// is above `max_angle`.
+    #[must_use]
+    pub fn new(
+        gearbox: DCMotor,
+        gearing: f64,
+        moment_of_inertia: impl Into<KilogramSquareMeter>,
+        arm_length: impl Into<Meter>,
+        min_angle: impl Into<Radian>,
+        max_angle: impl Into<Radian>,
+        simulate_gravity: bool,
+    ) -> Self {
+        let arm_length: Meter = arm_length.into();
+        let min_angle: Radian = min_angle.into();
+        let max_angle: Radian = max_angle.into();
+        assert!(
+            arm_length.value() > 0.0,
+            "arm length must be greater than zero"
+        );
+        assert!(
+            min_angle <= max_angle,
+            "min angle must not be above max angle"
+        );
+
+        let plant =
+            LinearSystemId::create_single_jointed_arm_system(gearbox, moment_of_inertia, gearing);
+        let mut sim = Self {
+            sim: LinearSystemSim::new(plant),
+            gearbox,
+            gearing,
+            arm_length,
+            min_angle,
+            max_angle,
+            simulate_gravity,
+        };
+        sim.set_state(min_angle, 0.0);
+        sim
+    }
+
+    /// Returns the moment of inertia of a uniform rod rotating about one end.
+    #[must_use]
+    pub fn estimate_moi(
+        length: impl Into<Meter>,
+        mass: impl Into<Kilogram>,
+    ) -> KilogramSquareMeter {
+        let length = length.into().value();
+        KilogramSquareMeter::new(mass.into().value() * length * length / 3.0)
+    }
+
+    /// Sets the standard deviation of the noise added to the measured angle, in radians.
+    #[must_use]
+    pub const fn with_measurement_std_dev(mut self, std_dev: f64) -> Self {
+        self.sim = self.sim.with_measurement_std_devs(Vector1::new(std_dev));
+        self
+    }
+
+    /// Applies `voltage`, limited to what `battery` can supply.
+    pub fn set_input_voltage(&mut self, voltage: impl Into<Volt>, battery: &BatterySim) {
+        self.sim.set_input(Vector1::new(voltage.into().value()));
+        self.sim
+            .clamp_input(battery.get_available_voltage().value());
+    }
+
+    #[must_use]
+    pub fn get_input_voltage(&self) -> Volt {
+        Volt::new(self.sim.get_input_at(0))
+    }
+
+    /// Moves the arm, clamping the angle to the limits.
+    pub fn set_state(&mut self, angle: impl Into<Radian>, velocity: impl Into<RadianPerSecond>) {
+        let state = Vector2::new(angle.into().value(), velocity.into().value());
+        self.sim.set_state(self.clamp_to_limits(state));
+    }
+
+    /// Advances the simulation by `dt` with the current input held constant.
+    pub fn update(&mut self, dt: impl Into<Second>) {
+        let plant = *self.sim.get_plant();
+        let gravity = if self.simulate_gravity {
+            // the angular acceleration of a uniform rod under gravity
+            -1.5 * GRAVITY / self.arm_length.value()
+        } else {
+            0.0
+        };
+        let state = NumericalIntegration::rkdp(
+            |x, u| plant.get_a() * x + plant.get_b() * u + Vector2::new(0.0, gravity * x[0].cos()),
+            self.sim.get_state(),
+            self.sim.get_input(),
+            dt,
+        );
+        self.sim.measure(self.clamp_to_limits(state));
+    }
+
+    fn clamp_to_limits(&self, state: Vector2<f64>) -> Vector2<f64> {
+        if self.would_hit_lower_limit(state[0]) {
+            Vector2::new(self.min_angle.value(), 0.0)
+        } else if self.would_hit_upper_limit(state[0]) {
+            Vector2::new(self.max_angle.value(), 0.0)
+        } else {
+            state
+        }
+    }
+
+    #[must_use]
+    pub fn would_hit_lower_limit(&self, angle: impl Into<Radian>) -> bool {
+        angle.into() <= self.min_angle
+    }
+
+    #[must_use]
+    pub fn would_hit_upper_limit(&self, angle: impl Into<Radian>) -> bool {
+        angle.into() >= self.max_angle
+    }
+
+    #[must_use]
+    pub fn has_hit_lower_limit(&self) -> bool {
+        self.would_hit_lower_limit(self.get_angle())
+    }
+
+    #[must_use]
+    pub fn has_hit_upper_limit(&self) -> bool {
+        self.would_hit_upper_limit(self.get_angle())
+    }
+
+    /// Returns the measured angle of the arm.
+    #[must_use]
+    pub fn get_angle(&self) -> Radian {
+        Radian::new(self.sim.get_output_at(0))
+    }
+
+    #[must_use]
+    pub fn get_velocity(&self) -> RadianPerSecond {
+        RadianPerSecond::new(self.sim.get_state_at(1))
+    }
+
+    /// Returns the current drawn by the gearbox.
+    #[must_use]
+    pub fn get_current_draw(&self) -> Amp {
+        motor_current_draw(
+            &self.gearbox,
+            self.sim.get_state_at(1) * self.gearing,
+            self.sim.get_input_at(0),
+        )
+    }
+}
@@ -0,0 +1,251 @@
+use crate::math::geometry::Pose2d;
+use crate::math::simulation::{
+    BatterySim, DCMotorSim, DifferentialDrivetrainSim, ElevatorSim, FlywheelSim, LinearSystemSim,
+    SingleJointedArmSim,
+};
+use crate::math::system::{DCMotor, LinearSystemId};
+use crate::math::units::angle::Degree;
+use crate::math::units::energy::Amp;
+use approx::assert_relative_eq;
+use nalgebra::{SVector, Vector1};
+use std::f64::consts::PI;
+
+#[test]
+fn linear_system_sim() {
+    let mut sim = LinearSystemSim::new(LinearSystemId::identify_velocity_system(0.5, 0.1));
+    sim.set_input(Vector1::new(6.0));
+    for _ in 0..200 {
+        sim.update(0.02);
+    }
+    assert_relative_eq!(sim.get_output_at(0), 12.0, epsilon = 1e-6);
+
+    sim.set_state(Vector1::new(3.0));
+    assert_relative_eq!(sim.get_output_at(0), 3.0);
+
+    // noise moves the measurement but not the state
+    let mut sim = sim.with_measurement_std_devs(Vector1::new(0.5));
+    sim.update(0.0);
+    assert_relative_eq!(sim.get_state_at(0), 3.0);
+    assert!((sim.get_output_at(0) - 3.0).abs() > 0.0);
+    assert!((sim.get_output_at(0) - 3.0).abs() < 5.0 * 0.5);
+}
+
+#[test]
+fn flywheel_sim() {
+    let battery = BatterySim::default();
+    let motor = DCMotor::new_neo(2);
+    let mut sim = FlywheelSim::new(motor, 2.0, 0.005);
+
+    sim.set_input_voltage(12.0, &battery);
+    assert_relative_eq!(sim.get_current_draw().value(), motor.stall_current.value());
+
+    for _ in 0..500 {
+        sim.update(0.02);
+    }
+    assert_relative_eq!(
+        sim.get_angular_velocity().value(),
+        motor.kv * 12.0 / 2.0,
+        epsilon = 1e-6
+    );
+    assert_relative_eq!(sim.get_current_draw().value(), 0.0, epsilon = 1e-6);
+
+    sim.set_input_voltage(0.0, &battery);
+    assert_relative_eq!(sim.get_current_draw().value(), 0.0);
+}
+
+#[test]
+fn dc_motor_sim() {
+    let battery = BatterySim::default();
+    let motor = DCMotor::new_falcon500(1);
+    let mut sim = DCMotorSim::new(motor, 5.0, 0.01);
+
+    sim.set_input_voltage(-6.0, &battery);
+    for _ in 0..500 {
+        sim.update(0.02);
+    }
+    assert_relative_eq!(
+        sim.get_angular_velocity().value(),
+        -motor.kv * 6.0 / 5.0,
+        epsilon = 1e-6
+    );
+    assert!(sim.get_angular_position().value() < 0.0);
+
+    sim.set_state(1.0, 0.0);
+    assert_relative_eq!(sim.get_angular_position().value(), 1.0);
+    assert_relative_eq!(sim.get_angular_velocity().value(), 0.0);
+}
+
+#[test]
+fn elevator_sim() {
+    let battery = BatterySim::default();
+    let motor = DCMotor::new_neo(2);
+    let mut sim = ElevatorSim::new(motor, 12.0, 5.0, 0.05, 0.0, 2.0, true);
+    assert!(sim.has_hit_lower_limit());
+
+    // unpowered, the carriage falls back to the bottom
+    sim.set_state(1.0, 0.0);
+    sim.update(0.02);
+    assert!(sim.get_velocity().value() < 0.0);
+    for _ in 0..1000 {
+        sim.update(0.02);
+    }
+    assert!(sim.has_hit_lower_limit());
+    assert_relative_eq!(sim.get_position().value(), 0.0);
+    assert_relative_eq!(sim.get_velocity().value(), 0.0);
+
+    // the voltage that balances gravity at rest holds the carriage still
+    let plant = LinearSystemId::create_elevator_system(motor, 5.0, 0.05, 12.0);
+    sim.set_state(1.0, 0.0);
+    sim.set_input_voltage(9.8 / plant.get_b()[1], &battery);
+    for _ in 0..100 {
+        sim.update(0.02);
+    }
+    assert_relative_eq!(sim.get_position().value(), 1.0, epsilon = 1e-9);
+
+    // full power drives it into the top
+    sim.set_input_voltage(12.0, &battery);
+    for _ in 0..500 {
+        sim.update(0.02);
+    }
+    assert!(sim.has_hit_upper_limit());
+    assert_relative_eq!(sim.get_position().value(), 2.0);
+    assert_relative_eq!(sim.get_velocity().value(), 0.0);
+    assert!(sim.get_current_draw().value() > 0.0);
+}
+
+#[test]
+fn single_jointed_arm_sim() {
+    let battery = BatterySim::default();
+    let motor = DCMotor::new_neo(1);
+    let moi = SingleJointedArmSim::estimate_moi(0.5, 2.0);
+    assert_relative_eq!(moi.value(), 2.0 * 0.25 / 3.0);
+
+    let mut sim = SingleJointedArmSim::new(
+        motor,
+        20.0,
+        moi,
+        0.5,
+        Degree::new(-90.0),
+        Degree::new(90.0),
+        true,
+    );
+    assert!(sim.has_hit_lower_limit());
+
+    // unpowered, a horizontal arm swings down onto the lower stop
+    sim.set_state(0.0, 0.0);
+    sim.update(0.02);
+    assert!(sim.get_velocity().value() < 0.0);
+    for _ in 0..1000 {
+        sim.update(0.02);
+    }
+    assert_relative_eq!(sim.get_angle().value(), -PI / 2.0);
+    assert_relative_eq!(sim.get_velocity().value(), 0.0);
+
+    // without gravity it stays where it is put
+    let mut sim = SingleJointedArmSim::new(
+        motor,
+        20.0,
+        moi,
+        0.5,
+        Degree::new(-90.0),
+        Degree::new(90.0),
+        false,
+    );
+    sim.set_state(0.3, 0.0);
+    sim.update(0.02);
+    assert_relative_eq!(sim.get_angle().value(), 0.3);
+
+    sim.set_input_voltage(12.0, &battery);
+    for _ in 0..500 {
+        sim.update(0.02);
+    }
+    assert!(sim.has_hit_upper_limit());
+    assert_relative_eq!(sim.get_angle().value(), PI / 2.0);
+}
+
+#[test]
+fn differential_drivetrain_sim() {
+    let battery = BatterySim::default();
+    let mut sim = DifferentialDrivetrainSim::new(DCMotor::new_neo(2), 8.0, 6.0, 50.0, 0.0762, 0.7);
+
+    // equal voltages drive straight ahead
+    sim.set_input_voltage(6.0, 6.0, &battery);
+    for _ in 0..100 {
+        sim.update(0.02);
+    }
+    assert!(sim.get_pose().translation.x.value() > 1.0);
+    assert_relative_eq!(sim.get_pose().translation.y.value(), 0.0, epsilon = 1e-9);
+    assert_relative_eq!(sim.get_heading().value.value(), 0.0, epsilon = 1e-9);
+    assert_relative_eq!(
+        sim.get_left_position().value(),
+        sim.get_pose().translation.x.value(),
+        epsilon = 1e-9
+    );
+    assert_relative_eq!(
+        sim.get_left_velocity().value(),
+        sim.get_right_velocity().value(),
+        epsilon = 1e-9
+    );
+    assert!(sim.get_current_draw().value() > 0.0);
+
+    // opposite voltages turn in place
+    sim.set_pose(Pose2d::default());
+    sim.set_input_voltage(-2.0, 2.0, &battery);
+    for _ in 0..10 {
+        sim.update(0.02);
+    }
+    let pose = sim.get_pose();
+    assert!(pose.translation.x.value() > 0.0);
+    assert!(sim.get_heading().value.value() > 0.0);
+    assert_relative_eq!(
+        sim.get_heading().value.value(),
+        (sim.get_right_position().value() - sim.get_left_position().value()) / 0.7,
+        epsilon = 1e-9
+    );
+
+    let mut sim = sim.with_measurement_std_devs(SVector::from([0.01; 7]));
+    sim.update(0.02);
+    assert!(sim.get_pose() != pose);
+}
+
+#[test]
+fn battery_sim() {
+    let voltage =
+        BatterySim::calculate_default_battery_loaded_voltage(&[Amp::new(100.0), Amp::new(50.0)]);
+    assert_relative_eq!(voltage.value(), 9.0);
+
+    let voltage = BatterySim::calculate_loaded_battery_voltage(13.0, 0.1, &[Amp::new(20.0)]);
+    assert_relative_eq!(voltage.value(), 11.0);
+
+    let voltage = BatterySim::calculate_default_battery_loaded_voltage(&[Amp::new(1_000.0)]);
+    assert_relative_eq!(voltage.value(), 0.0);
+}
+
+#[test]
+fn brownout() {
+    let mut sim = FlywheelSim::new(DCMotor::new_neo(1), 1.0, 0.005);
+
+    // mechanisms can't draw more than the battery supplies
+    let battery = BatterySim::new(10.0, 0.02);
+    assert!(!battery.is_browned_out());
+    sim.set_input_voltage(12.0, &battery);
+    assert_relative_eq!(sim.get_input_voltage().value(), 10.0);
+    sim.set_input_voltage(-12.0, &battery);
+    assert_relative_eq!(sim.get_input_voltage().value(), -10.0);
+
+    // four stalled motors sag the battery below the brownout voltage
+    let mut battery = BatterySim::default();
+    let stalled = sim.get_current_draw();
+    battery.update(&[stalled; 4]);
+    assert!(battery.get_voltage() < battery.get_brownout_voltage());
+    assert!(battery.is_browned_out());
+    sim.set_input_voltage(12.0, &battery);
+    assert_relative_eq!(sim.get_input_voltage().value(), 0.0);
+
+    battery.update(&[]);
+    assert_relative_eq!(battery.get_voltage().value(), 12.0);
+    assert!(!battery.is_browned_out());
+
+    let battery = BatterySim::default().with_brownout_voltage(12.5);
+    assert!(battery.is_browned_out());
+}
@@ -0,0 +1,212 @@
+use crate::math::units::angular_velocity::{RadianPerSecond, RotationPerMinute};
+use crate::math::units::energy::{Amp, Ohm, Volt};
+use crate::math::units::torque::NewtonMeter;
+
+/// The physical constants of a brushed or brushless DC motor, or of several identical motors
+/// driving the same mechanism.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DCMotor {
+    pub nominal_voltage: Volt,
+    pub stall_torque: NewtonMeter,
+    pub stall_current: Amp,
+    pub free_current: Amp,
+    pub free_speed: RadianPerSecond,
+    /// Winding resistance.
+    pub r: Ohm,
+    /// Velocity constant in (rad/s)/V.
+    pub kv: f64,
+    /// Torque constant in N·m/A.
+    pub kt: f64,
+}
+
+impl DCMotor {
+    #[must_use]
+    pub fn new(
+        nominal_voltage: impl Into<Volt>,
+        stall_torque: impl Into<NewtonMeter>,
+        stall_current: impl Into<Amp>,
+        free_current: impl Into<Amp>,
+        free_speed: impl Into<RadianPerSecond>,
+        num_motors: u32,
+    ) -> Self {
+        let motors = f64::from(num_motors);
+        let nominal_voltage: Volt = nominal_voltage.into();
+        let stall_torque = NewtonMeter::new(stall_torque.into().value() * motors);
+        let stall_current = Amp::new(stall_current.into().value() * motors);
+        let free_current = Amp::new(free_current.into().value() * motors);
+        let free_speed: RadianPerSecond = free_speed.into();
+
+        let r = nominal_voltage.value() / stall_current.value();
+        Self {
+            nominal_voltage,
+            stall_torque,
+            stall_current,
+            free_current,
+            free_speed,
+            r: Ohm::new(r),
+            kv: free_speed.value() / r.mul_add(-free_current.value(), nominal_voltage.value()),
+            kt: stall_torque.value() / stall_current.value(),
+        }
+    }
+
+    /// Returns the current drawn when spinning at `speed` with `voltage_input` applied.
+    #[must_use]
+    pub fn get_current(
+        &self,
+        speed: impl Into<RadianPerSecond>,
+        voltage_input: impl Into<Volt>,
+    ) -> Amp {
+        let back_emf = speed.into().value() / self.kv;
+        Amp::new((voltage_input.into().value() - back_emf) / self.r.value())
+    }
+
+    /// Returns the current needed to produce `torque`.
+    #[must_use]
+    pub fn get_current_from_torque(&self, torque: impl Into<NewtonMeter>) -> Amp {
+        Amp::new(torque.into().value() / self.kt)
+    }
+
+    /// Returns the torque produced when drawing `current`.
+    #[must_use]
+    pub fn get_torque(&self, current: impl Into<Amp>) -> NewtonMeter {
+        NewtonMeter::new(current.into().value() * self.kt)
+    }
+
+    /// Returns the voltage needed to produce `torque` while spinning at `speed`.
+    #[must_use]
+    pub fn get_voltage(
+        &self,
+        torque: impl Into<NewtonMeter>,
+        speed: impl Into<RadianPerSecond>,
+    ) -> Volt {
+        let resistive = torque.into().value() / self.kt * self.r.value();
+        Volt::new(speed.into().value() / self.kv + resistive)
+    }
+
+    /// Returns the speed reached when producing `torque` with `voltage_input` applied.
+    #[must_use]
+    pub fn get_speed(
+        &self,
+        torque: impl Into<NewtonMeter>,
+        voltage_input: impl Into<Volt>,
+    ) -> RadianPerSecond {
+        let resistive = torque.into().value() / self.kt * self.r.value();
+        RadianPerSecond::new((voltage_input.into().value() - resistive) * self.kv)
+    }
+
+    /// Returns the motor as seen through a gearbox that reduces its speed by `gearbox_reduction`.
+    #[must_use]
+    pub fn with_reduction(&self, gearbox_reduction: f64) -> Self {
+        Self {
+            stall_torque: NewtonMeter::new(self.stall_torque.value() * gearbox_reduction),
+            free_speed: RadianPerSecond::new(self.free_speed.value() / gearbox_reduction),
+            kv: self.kv / gearbox_reduction,
+            kt: self.kt * gearbox_reduction,
+            ..*self
+        }
+    }
+
+    /// Returns a gearbox of CIM motors.
+    #[must_use]
+    pub fn new_cim(num_motors: u32) -> Self {
+        Self::new(
+            12.0,
+            2.42,
+            133.0,
+            2.7,
+            RotationPerMinute::new(5310.0),
+            num_motors,
+        )
+    }
+
+    /// Returns a gearbox of REV NEO motors.
+    #[must_use]
+    pub fn new_neo(num_motors: u32) -> Self {
+        Self::new(
+            12.0,
+            2.6,
+            105.0,
+            1.8,
+            RotationPerMinute::new(5676.0),
+            num_motors,
+        )
+    }
+
+    /// Returns a gearbox of REV NEO 550 motors.
+    #[must_use]
+    pub fn new_neo550(num_motors: u32) -> Self {
+        Self::new(
+            12.0,
+            0.97,
+            100.0,
+            1.4,
+            RotationPerMinute::new(11_000.0),
+            num_motors,
+        )
+    }
+
+    /// Returns a gearbox of REV NEO Vortex motors.
+    #[must_use]
+    pub fn new_neo_vortex(num_motors: u32) -> Self {
+        Self::new(
+            12.0,
+            3.6,
+            211.0,
+            3.6,
+            RotationPerMinute::new(6784.0),
+            num_motors,
+        )
+    }
+
+    /// Returns a gearbox of Falcon 500 motors.
+    #[must_use]
+    pub fn new_falcon500(num_motors: u32) -> Self {
+        Self::new(
+            12.0,
+            4.69,
+            257.0,
+            1.5,
+            RotationPerMinute::new(6380.0),
+            num_motors,
+        )
+    }
+
+    /// Returns a gearbox of Falcon 500 motors using field-oriented control.
+    #[must_use]
+    pub fn new_falcon500_foc(num_motors: u32) -> Self {
+        Self::new(
+            12.0,
+            5.84,
+            304.0,
+            1.5,
+            RotationPerMinute::new(6080.0),
+            num_motors,
+        )
+    }
+
+    /// Returns a gearbox of Kraken X60 motors.
+    #[must_use]
+    pub fn new_kraken_x60(num_motors: u32) -> Self {
+        Self::new(
+            12.0,
+            7.09,
+            366.0,
+            2.0,
+            RotationPerMinute::new(6000.0),
+            num_motors,
+        )
+    }
+
+    /// Returns a gearbox of Kraken X60 motors using field-oriented control.
+    #[must_use]
+    pub fn new_kraken_x60_foc(num_motors: u32) -> Self {
+        Self::new(
+            12.0,
+            9.37,
+            483.0,
+            2.0,
+            RotationPerMinute::new(5800.0),
+            num_motors,
+        )
+    }
+}
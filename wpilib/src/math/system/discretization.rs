@@ -0,0 +1,90 @@
+use nalgebra::{DMatrix, SMatrix};
+
+use crate::math::units::time::Second;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Discretization {}
+
+impl Discretization {
+    /// Discretizes the continuous system matrix `A` over `dt`.
+    #[must_use]
+    pub fn discretize_a<const STATES: usize>(
+        a: &SMatrix<f64, STATES, STATES>,
+        dt: impl Into<Second>,
+    ) -> SMatrix<f64, STATES, STATES> {
+        let mut m = DMatrix::zeros(STATES, STATES);
+        m.copy_from(a);
+        (m * dt.into().value())
+            .exp()
+            .fixed_view::<STATES, STATES>(0, 0)
+            .into_owned()
+    }
+
+    /// Discretizes the continuous system `dx/dt = Ax + Bu` with a zero-order hold over `dt`.
+    #[must_use]
+    pub fn discretize_ab<const STATES: usize, const INPUTS: usize>(
+        a: &SMatrix<f64, STATES, STATES>,
+        b: &SMatrix<f64, STATES, INPUTS>,
+        dt: impl Into<Second>,
+    ) -> (SMatrix<f64, STATES, STATES>, SMatrix<f64, STATES, INPUTS>) {
+        let dt = dt.into().value();
+
+        // M = [A B]
+        //     [0 0]
+        // and exp(M dt) = [A_d B_d]
+        //                 [0   I ]
+        let mut m = DMatrix::zeros(STATES + INPUTS, STATES + INPUTS);
+        m.view_mut((0, 0), (STATES, STATES)).copy_from(a);
+        m.view_mut((0, STATES), (STATES, INPUTS)).copy_from(b);
+        let phi = (m * dt).exp();
+
+        (
+            phi.fixed_view::<STATES, STATES>(0, 0).into_owned(),
+            phi.fixed_view::<STATES, INPUTS>(0, STATES).into_owned(),
+        )
+    }
+
+    /// Discretizes the continuous system matrix `A` and process noise covariance `Q` over `dt`
+    /// using Van Loan's method.
+    #[must_use]
+    pub fn discretize_aq<const STATES: usize>(
+        a: &SMatrix<f64, STATES, STATES>,
+        q: &SMatrix<f64, STATES, STATES>,
+        dt: impl Into<Second>,
+    ) -> (SMatrix<f64, STATES, STATES>, SMatrix<f64, STATES, STATES>) {
+        let dt = dt.into().value();
+
+        // Q is symmetric in theory, but make sure rounding hasn't made it asymmetric
+        let q = (q + q.transpose()) / 2.0;
+
+        // M = [−A  Q ]
+        //     [ 0  Aᵀ]
+        // and exp(M dt) = [−A_d  A_d⁻¹Q_d]
+        //                 [ 0      A_dᵀ  ]
+        let mut m = DMatrix::zeros(2 * STATES, 2 * STATES);
+        m.view_mut((0, 0), (STATES, STATES)).copy_from(&-a);
+        m.view_mut((0, STATES), (STATES, STATES)).copy_from(&q);
+        m.view_mut((STATES, STATES), (STATES, STATES))
+            .copy_from(&a.transpose());
+        let phi = (m * dt).exp();
+
+        let phi12 = phi.fixed_view::<STATES, STATES>(0, STATES).into_owned();
+        let phi22 = phi
+            .fixed_view::<STATES, STATES>(STATES, STATES)
+            .into_owned();
+
+        let discrete_a = phi22.transpose();
+        let discrete_q = discrete_a * phi12;
+
+        (discrete_a, (discrete_q + discrete_q.transpose()) / 2.0)
+    }
+
+    /// Discretizes the continuous measurement noise covariance `R` over `dt`.
+    #[must_use]
+    pub fn discretize_r<const OUTPUTS: usize>(
+        r: &SMatrix<f64, OUTPUTS, OUTPUTS>,
+        dt: impl Into<Second>,
+    ) -> SMatrix<f64, OUTPUTS, OUTPUTS> {
+        r / dt.into().value()
+    }
+}
@@ -0,0 +1,88 @@
+use nalgebra::SMatrix;
+
+use crate::math::units::time::Second;
+
+use super::Discretization;
+
+/// A continuous time linear plant `dx/dt = Ax + Bu`, `y = Cx + Du`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct LinearSystem<const STATES: usize, const INPUTS: usize, const OUTPUTS: usize> {
+    a: SMatrix<f64, STATES, STATES>,
+    b: SMatrix<f64, STATES, INPUTS>,
+    c: SMatrix<f64, OUTPUTS, STATES>,
+    d: SMatrix<f64, OUTPUTS, INPUTS>,
+}
+
+impl<const STATES: usize, const INPUTS: usize, const OUTPUTS: usize>
+    LinearSystem<STATES, INPUTS, OUTPUTS>
+{
+    /// # Panics
+    /// Panics if any element of the system matrices is NaN or infinite.
+    #[must_use]
+    pub fn new(
+        a: SMatrix<f64, STATES, STATES>,
+        b: SMatrix<f64, STATES, INPUTS>,
+        c: SMatrix<f64, OUTPUTS, STATES>,
+        d: SMatrix<f64, OUTPUTS, INPUTS>,
+    ) -> Self {
+        assert!(
+            a.iter().all(|x| x.is_finite()),
+            "Elements of the system matrix A aren't finite"
+        );
+        assert!(
+            b.iter().all(|x| x.is_finite()),
+            "Elements of the input matrix B aren't finite"
+        );
+        assert!(
+            c.iter().all(|x| x.is_finite()),
+            "Elements of the output matrix C aren't finite"
+        );
+        assert!(
+            d.iter().all(|x| x.is_finite()),
+            "Elements of the feedthrough matrix D aren't finite"
+        );
+        Self { a, b, c, d }
+    }
+
+    #[must_use]
+    pub const fn get_a(&self) -> &SMatrix<f64, STATES, STATES> {
+        &self.a
+    }
+
+    #[must_use]
+    pub const fn get_b(&self) -> &SMatrix<f64, STATES, INPUTS> {
+        &self.b
+    }
+
+    #[must_use]
+    pub const fn get_c(&self) -> &SMatrix<f64, OUTPUTS, STATES> {
+        &self.c
+    }
+
+    #[must_use]
+    pub const fn get_d(&self) -> &SMatrix<f64, OUTPUTS, INPUTS> {
+        &self.d
+    }
+
+    /// Returns the state after applying input `u` to state `x` for `dt`.
+    #[must_use]
+    pub fn calculate_x(
+        &self,
+        x: &SMatrix<f64, STATES, 1>,
+        u: &SMatrix<f64, INPUTS, 1>,
+        dt: impl Into<Second>,
+    ) -> SMatrix<f64, STATES, 1> {
+        let (discrete_a, discrete_b) = Discretization::discretize_ab(&self.a, &self.b, dt);
+        discrete_a * x + discrete_b * u
+    }
+
+    /// Returns the output measured at state `x` with input `u`.
+    #[must_use]
+    pub fn calculate_y(
+        &self,
+        x: &SMatrix<f64, STATES, 1>,
+        u: &SMatrix<f64, INPUTS, 1>,
+    ) -> SMatrix<f64, OUTPUTS, 1> {
+        self.c * x + self.d * u
+    }
+}
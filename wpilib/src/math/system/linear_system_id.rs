@@ -0,0 +1,292 @@
+use nalgebra::{Matrix1, Matrix1x2, Matrix2, Matrix2x1, Vector2};
+
+use crate::math::units::distance::Meter;
+use crate::math::units::mass::Kilogram;
+use crate::math::units::moment_of_inertia::KilogramSquareMeter;
+
+use super::{DCMotor, LinearSystem};
+
+/// Factories for common mechanisms' state-space models.
+///
+/// Models are built either from motor physics or from `kV` and `kA` gains identified with
+/// `SysId`, in the same units as the [`feed_forward`](crate::math::controllers::feed_forward)
+/// coefficients (volts per unit of velocity and volts per unit of acceleration).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct LinearSystemId {}
+
+impl LinearSystemId {
+    /// Models an elevator carriage driven by a drum.
+    ///
+    /// States: [position, velocity] in meters and m/s. Inputs: [voltage]. Outputs: [position].
+    ///
+    /// # Panics
+    /// Panics if the mass, drum radius or gearing isn't positive.
+    #[must_use]
+    pub fn create_elevator_system(
+        motor: DCMotor,
+        mass: impl Into<Kilogram>,
+        radius: impl Into<Meter>,
+        gearing: f64,
+    ) -> LinearSystem<2, 1, 1> {
+        let mass = mass.into().value();
+        let radius = radius.into().value();
+        assert!(mass > 0.0, "mass must be greater than zero");
+        assert!(radius > 0.0, "radius must be greater than zero");
+        assert!(gearing > 0.0, "gearing must be greater than zero");
+
+        let r = motor.r.value();
+        LinearSystem::new(
+            Matrix2::new(
+                0.0,
+                1.0,
+                0.0,
+                -gearing.powi(2) * motor.kt / (r * radius.powi(2) * mass * motor.kv),
+            ),
+            Matrix2x1::new(0.0, gearing * motor.kt / (r * radius * mass)),
+            Matrix1x2::new(1.0, 0.0),
+            Matrix1::zeros(),
+        )
+    }
+
+    /// Models a flywheel.
+    ///
+    /// States: [angular velocity] in rad/s. Inputs: [voltage]. Outputs: [angular velocity].
+    ///
+    /// # Panics
+    /// Panics if the moment of inertia or gearing isn't positive.
+    #[must_use]
+    pub fn create_flywheel_system(
+        motor: DCMotor,
+        moment_of_inertia: impl Into<KilogramSquareMeter>,
+        gearing: f64,
+    ) -> LinearSystem<1, 1, 1> {
+        let j = moment_of_inertia.into().value();
+        assert!(j > 0.0, "moment of inertia must be greater than zero");
+        assert!(gearing > 0.0, "gearing must be greater than zero");
+
+        let r = motor.r.value();
+        LinearSystem::new(
+            Matrix1::new(-gearing.powi(2) * motor.kt / (motor.kv * r * j)),
+            Matrix1::new(gearing * motor.kt / (r * j)),
+            Matrix1::new(1.0),
+            Matrix1::zeros(),
+        )
+    }
+
+    /// Models a DC motor driving an inertial load.
+    ///
+    /// States: [angular position, angular velocity] in radians and rad/s. Inputs: [voltage].
+    /// Outputs: [angular position, angular velocity].
+    ///
+    /// # Panics
+    /// Panics if the moment of inertia or gearing isn't positive.
+    #[must_use]
+    pub fn create_dc_motor_system(
+        motor: DCMotor,
+        moment_of_inertia: impl Into<KilogramSquareMeter>,
+        gearing: f64,
+    ) -> LinearSystem<2, 1, 2> {
+        let j = moment_of_inertia.into().value();
+        assert!(j > 0.0, "moment of inertia must be greater than zero");
+        assert!(gearing > 0.0, "gearing must be greater than zero");
+
+        let r = motor.r.value();
+        LinearSystem::new(
+            Matrix2::new(
+                0.0,
+                1.0,
+                0.0,
+                -gearing.powi(2) * motor.kt / (motor.kv * r * j),
+            ),
+            Matrix2x1::new(0.0, gearing * motor.kt / (r * j)),
+            Matrix2::identity(),
+            Vector2::zeros(),
+        )
+    }
+
+    /// Models a DC motor from its identified `kV` (V/(rad/s)) and `kA` (V/(rad/s²)).
+    ///
+    /// States: [angular position, angular velocity]. Inputs: [voltage].
+    /// Outputs: [angular position, angular velocity].
+    ///
+    /// # Panics
+    /// Panics if `kv` is negative or `ka` isn't positive.
+    #[must_use]
+    pub fn create_dc_motor_system_kv_ka(kv: f64, ka: f64) -> LinearSystem<2, 1, 2> {
+        assert!(kv >= 0.0, "kV must be greater than or equal to zero");
+        assert!(ka > 0.0, "kA must be greater than zero");
+
+        LinearSystem::new(
+            Matrix2::new(0.0, 1.0, 0.0, -kv / ka),
+            Matrix2x1::new(0.0, 1.0 / ka),
+            Matrix2::identity(),
+            Vector2::zeros(),
+        )
+    }
+
+    /// Models a differential drivetrain from motor physics.
+    ///
+    /// States: [left velocity, right velocity] in m/s. Inputs: [left voltage, right voltage].
+    /// Outputs: [left velocity, right velocity].
+    ///
+    /// # Panics
+    /// Panics if any physical parameter isn't positive.
+    #[must_use]
+    pub fn create_drivetrain_velocity_system(
+        motor: DCMotor,
+        mass: impl Into<Kilogram>,
+        wheel_radius: impl Into<Meter>,
+        track_width: impl Into<Meter>,
+        moment_of_inertia: impl Into<KilogramSquareMeter>,
+        gearing: f64,
+    ) -> LinearSystem<2, 2, 2> {
+        let mass = mass.into().value();
+        let wheel_radius = wheel_radius.into().value();
+        let rb = track_width.into().value() / 2.0;
+        let j = moment_of_inertia.into().value();
+        assert!(mass > 0.0, "mass must be greater than zero");
+        assert!(wheel_radius > 0.0, "wheel radius must be greater than zero");
+        assert!(rb > 0.0, "track width must be greater than zero");
+        assert!(j > 0.0, "moment of inertia must be greater than zero");
+        assert!(gearing > 0.0, "gearing must be greater than zero");
+
+        let r = motor.r.value();
+        let c1 = -gearing.powi(2) * motor.kt / (motor.kv * r * wheel_radius.powi(2));
+        let c2 = gearing * motor.kt / (r * wheel_radius);
+        let same = 1.0 / mass + rb.powi(2) / j;
+        let opposite = 1.0 / mass - rb.powi(2) / j;
+
+        LinearSystem::new(
+            Matrix2::new(same * c1, opposite * c1, opposite * c1, same * c1),
+            Matrix2::new(same * c2, opposite * c2, opposite * c2, same * c2),
+            Matrix2::identity(),
+            Matrix2::zeros(),
+        )
+    }
+
+    /// Models a single-jointed arm.
+    ///
+    /// States: [angle, angular velocity] in radians and rad/s. Inputs: [voltage]. Outputs: [angle].
+    ///
+    /// # Panics
+    /// Panics if the moment of inertia or gearing isn't positive.
+    #[must_use]
+    pub fn create_single_jointed_arm_system(
+        motor: DCMotor,
+        moment_of_inertia: impl Into<KilogramSquareMeter>,
+        gearing: f64,
+    ) -> LinearSystem<2, 1, 1> {
+        let j = moment_of_inertia.into().value();
+        assert!(j > 0.0, "moment of inertia must be greater than zero");
+        assert!(gearing > 0.0, "gearing must be greater than zero");
+
+        let r = motor.r.value();
+        LinearSystem::new(
+            Matrix2::new(
+                0.0,
+                1.0,
+                0.0,
+                -gearing.powi(2) * motor.kt / (motor.kv * r * j),
+            ),
+            Matrix2x1::new(0.0, gearing * motor.kt / (r * j)),
+            Matrix1x2::new(1.0, 0.0),
+            Matrix1::zeros(),
+        )
+    }
+
+    /// Models a velocity-controlled mechanism from its identified `kV` and `kA`.
+    ///
+    /// States: [velocity]. Inputs: [voltage]. Outputs: [velocity].
+    ///
+    /// # Panics
+    /// Panics if `kv` is negative or `ka` isn't positive.
+    #[must_use]
+    pub fn identify_velocity_system(kv: f64, ka: f64) -> LinearSystem<1, 1, 1> {
+        assert!(kv >= 0.0, "kV must be greater than or equal to zero");
+        assert!(ka > 0.0, "kA must be greater than zero");
+
+        LinearSystem::new(
+            Matrix1::new(-kv / ka),
+            Matrix1::new(1.0 / ka),
+            Matrix1::new(1.0),
+            Matrix1::zeros(),
+        )
+    }
+
+    /// Models a position-controlled mechanism from its identified `kV` and `kA`.
+    ///
+    /// States: [position, velocity]. Inputs: [voltage]. Outputs: [position].
+    ///
+    /// # Panics
+    /// Panics if `kv` is negative or `ka` isn't positive.
+    #[must_use]
+    pub fn identify_position_system(kv: f64, ka: f64) -> LinearSystem<2, 1, 1> {
+        assert!(kv >= 0.0, "kV must be greater than or equal to zero");
+        assert!(ka > 0.0, "kA must be greater than zero");
+
+        LinearSystem::new(
+            Matrix2::new(0.0, 1.0, 0.0, -kv / ka),
+            Matrix2x1::new(0.0, 1.0 / ka),
+            Matrix1x2::new(1.0, 0.0),
+            Matrix1::zeros(),
+        )
+    }
+
+    /// Models a differential drivetrain from its identified linear and angular `kV` and `kA`.
+    ///
+    /// The angular gains are in V/(m/s) and V/(m/s²) of wheel speed.
+    ///
+    /// States: [left velocity, right velocity]. Inputs: [left voltage, right voltage].
+    /// Outputs: [left velocity, right velocity].
+    ///
+    /// # Panics
+    /// Panics if any gain isn't positive.
+    #[must_use]
+    pub fn identify_drivetrain_system(
+        kv_linear: f64,
+        ka_linear: f64,
+        kv_angular: f64,
+        ka_angular: f64,
+    ) -> LinearSystem<2, 2, 2> {
+        assert!(kv_linear > 0.0, "kV linear must be greater than zero");
+        assert!(ka_linear > 0.0, "kA linear must be greater than zero");
+        assert!(kv_angular > 0.0, "kV angular must be greater than zero");
+        assert!(ka_angular > 0.0, "kA angular must be greater than zero");
+
+        let a1 = 0.5 * (-kv_linear / ka_linear - kv_angular / ka_angular);
+        let a2 = 0.5 * (-kv_linear / ka_linear + kv_angular / ka_angular);
+        let b1 = 0.5 * (1.0 / ka_linear + 1.0 / ka_angular);
+        let b2 = 0.5 * (1.0 / ka_linear - 1.0 / ka_angular);
+
+        LinearSystem::new(
+            Matrix2::new(a1, a2, a2, a1),
+            Matrix2::new(b1, b2, b2, b1),
+            Matrix2::identity(),
+            Matrix2::zeros(),
+        )
+    }
+
+    /// Like [`Self::identify_drivetrain_system`], with the angular gains in V/(rad/s) and
+    /// V/(rad/s²) of chassis rotation.
+    ///
+    /// # Panics
+    /// Panics if any gain or the track width isn't positive.
+    #[must_use]
+    pub fn identify_drivetrain_system_track_width(
+        kv_linear: f64,
+        ka_linear: f64,
+        kv_angular: f64,
+        ka_angular: f64,
+        track_width: impl Into<Meter>,
+    ) -> LinearSystem<2, 2, 2> {
+        let track_width = track_width.into().value();
+        assert!(track_width > 0.0, "track width must be greater than zero");
+
+        Self::identify_drivetrain_system(
+            kv_linear,
+            ka_linear,
+            kv_angular * 2.0 / track_width,
+            ka_angular * 2.0 / track_width,
+        )
+    }
+}
@@ -0,0 +1,191 @@
+use std::fmt::Debug;
+
+use nalgebra::SVector;
+
+use crate::math::controllers::{LinearPlantInversionFeedforward, LinearQuadraticRegulator};
+use crate::math::estimator::KalmanTypeFilter;
+use crate::math::units::energy::Volt;
+use crate::math::units::time::Second;
+use crate::math::util::state_space_util::StateSpaceUtil;
+
+use super::LinearSystem;
+
+type ClampFunction<const INPUTS: usize> =
+    Box<dyn Fn(&SVector<f64, INPUTS>) -> SVector<f64, INPUTS> + Send + Sync>;
+
+/// Combines a [`LinearQuadraticRegulator`], a [`LinearPlantInversionFeedforward`] and an observer
+/// to control a linear plant.
+///
+/// Each loop, call [`correct`](Self::correct) with the latest measurement, apply
+/// [`get_u`](Self::get_u) to the plant, then call [`predict`](Self::predict).
+pub struct LinearSystemLoop<
+    const STATES: usize,
+    const INPUTS: usize,
+    const OUTPUTS: usize,
+    Observer: KalmanTypeFilter<STATES, INPUTS, OUTPUTS>,
+> {
+    controller: LinearQuadraticRegulator<STATES, INPUTS>,
+    feedforward: LinearPlantInversionFeedforward<STATES, INPUTS>,
+    observer: Observer,
+    next_r: SVector<f64, STATES>,
+    clamp_function: ClampFunction<INPUTS>,
+}
+
+impl<
+        const STATES: usize,
+        const INPUTS: usize,
+        const OUTPUTS: usize,
+        Observer: KalmanTypeFilter<STATES, INPUTS, OUTPUTS>,
+    > LinearSystemLoop<STATES, INPUTS, OUTPUTS, Observer>
+{
+    /// Creates a loop whose inputs are voltages, scaled down together when any exceeds
+    /// `max_voltage`.
+    #[must_use]
+    pub fn new(
+        plant: &LinearSystem<STATES, INPUTS, OUTPUTS>,
+        controller: LinearQuadraticRegulator<STATES, INPUTS>,
+        observer: Observer,
+        max_voltage: impl Into<Volt>,
+        dt: impl Into<Second>,
+    ) -> Self {
+        let max_voltage = max_voltage.into().value();
+        Self::new_clamped(
+            controller,
+            LinearPlantInversionFeedforward::new(plant, dt),
+            observer,
+            move |u| StateSpaceUtil::desaturate_input_vector(u, max_voltage),
+        )
+    }
+
+    /// Creates a loop that limits its inputs with `clamp_function`.
+    #[must_use]
+    pub fn new_clamped<F>(
+        controller: LinearQuadraticRegulator<STATES, INPUTS>,
+        feedforward: LinearPlantInversionFeedforward<STATES, INPUTS>,
+        observer: Observer,
+        clamp_function: F,
+    ) -> Self
+    where
+        F: Fn(&SVector<f64, INPUTS>) -> SVector<f64, INPUTS> + Send + Sync + 'static,
+    {
+        Self {
+            controller,
+            feedforward,
+            observer,
+            next_r: SVector::zeros(),
+            clamp_function: Box::new(clamp_function),
+        }
+    }
+
+    /// Returns the observer's state estimate.
+    #[must_use]
+    pub fn get_xhat(&self) -> &SVector<f64, STATES> {
+        self.observer.get_xhat()
+    }
+
+    #[must_use]
+    pub fn get_xhat_at(&self, row: usize) -> f64 {
+        self.observer.get_xhat()[row]
+    }
+
+    pub fn set_xhat(&mut self, xhat: SVector<f64, STATES>) {
+        self.observer.set_xhat(xhat);
+    }
+
+    /// Returns the reference the loop is driving toward.
+    #[must_use]
+    pub const fn get_next_r(&self) -> &SVector<f64, STATES> {
+        &self.next_r
+    }
+
+    #[must_use]
+    pub fn get_next_r_at(&self, row: usize) -> f64 {
+        self.next_r[row]
+    }
+
+    pub const fn set_next_r(&mut self, next_r: SVector<f64, STATES>) {
+        self.next_r = next_r;
+    }
+
+    /// Returns the clamped sum of the controller and feedforward inputs.
+    #[must_use]
+    pub fn get_u(&self) -> SVector<f64, INPUTS> {
+        self.clamp_input(&(self.controller.get_u() + self.feedforward.get_uff()))
+    }
+
+    #[must_use]
+    pub fn get_u_at(&self, row: usize) -> f64 {
+        self.get_u()[row]
+    }
+
+    #[must_use]
+    pub const fn get_controller(&self) -> &LinearQuadraticRegulator<STATES, INPUTS> {
+        &self.controller
+    }
+
+    #[must_use]
+    pub const fn get_feedforward(&self) -> &LinearPlantInversionFeedforward<STATES, INPUTS> {
+        &self.feedforward
+    }
+
+    #[must_use]
+    pub const fn get_observer(&self) -> &Observer {
+        &self.observer
+    }
+
+    /// Zeroes the reference and restarts the observer and feedforward from `initial_state`.
+    pub fn reset(&mut self, initial_state: SVector<f64, STATES>) {
+        self.next_r = SVector::zeros();
+        self.controller.reset();
+        self.feedforward.reset(&initial_state);
+        self.observer.set_xhat(initial_state);
+    }
+
+    /// Returns the difference between the reference and the state estimate.
+    #[must_use]
+    pub fn get_error(&self) -> SVector<f64, STATES> {
+        self.controller.get_r() - self.observer.get_xhat()
+    }
+
+    #[must_use]
+    pub fn get_error_at(&self, row: usize) -> f64 {
+        self.get_error()[row]
+    }
+
+    /// Corrects the state estimate with measurement `y`.
+    pub fn correct(&mut self, y: &SVector<f64, OUTPUTS>) {
+        let u = self.get_u();
+        self.observer.correct(&u, y);
+    }
+
+    /// Computes the next input and projects the state estimate forward by `dt` with it applied.
+    pub fn predict(&mut self, dt: impl Into<Second>) {
+        let xhat = *self.observer.get_xhat();
+        let u = self.controller.calculate_with_r(&xhat, &self.next_r)
+            + self.feedforward.calculate(&self.next_r);
+        let u = self.clamp_input(&u);
+        self.observer.predict(&u, dt);
+    }
+
+    #[must_use]
+    pub fn clamp_input(&self, u: &SVector<f64, INPUTS>) -> SVector<f64, INPUTS> {
+        (self.clamp_function)(u)
+    }
+}
+
+impl<
+        const STATES: usize,
+        const INPUTS: usize,
+        const OUTPUTS: usize,
+        Observer: KalmanTypeFilter<STATES, INPUTS, OUTPUTS> + Debug,
+    > Debug for LinearSystemLoop<STATES, INPUTS, OUTPUTS, Observer>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LinearSystemLoop")
+            .field("controller", &self.controller)
+            .field("feedforward", &self.feedforward)
+            .field("observer", &self.observer)
+            .field("next_r", &self.next_r)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,16 @@
+mod dc_motor;
+mod discretization;
+mod linear_system;
+mod linear_system_id;
+mod linear_system_loop;
+mod numerical_integration;
+mod numerical_jacobian;
+mod test;
+
+pub use dc_motor::*;
+pub use discretization::*;
+pub use linear_system::*;
+pub use linear_system_id::*;
+pub use linear_system_loop::*;
+pub use numerical_integration::*;
+pub use numerical_jacobian::*;
@@ -0,0 +1,174 @@
+use nalgebra::SVector;
+
+use crate::math::units::time::Second;
+
+/// Dormand-Prince tableau.
+const RKDP_A: [[f64; 6]; 6] = [
+    [1.0 / 5.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [3.0 / 40.0, 9.0 / 40.0, 0.0, 0.0, 0.0, 0.0],
+    [44.0 / 45.0, -56.0 / 15.0, 32.0 / 9.0, 0.0, 0.0, 0.0],
+    [
+        19_372.0 / 6_561.0,
+        -25_360.0 / 2_187.0,
+        64_448.0 / 6_561.0,
+        -212.0 / 729.0,
+        0.0,
+        0.0,
+    ],
+    [
+        9_017.0 / 3_168.0,
+        -355.0 / 33.0,
+        46_732.0 / 5_247.0,
+        49.0 / 176.0,
+        -5_103.0 / 18_656.0,
+        0.0,
+    ],
+    [
+        35.0 / 384.0,
+        0.0,
+        500.0 / 1_113.0,
+        125.0 / 192.0,
+        -2_187.0 / 6_784.0,
+        11.0 / 84.0,
+    ],
+];
+
+/// Fifth-order solution weights.
+const RKDP_B1: [f64; 7] = [
+    35.0 / 384.0,
+    0.0,
+    500.0 / 1_113.0,
+    125.0 / 192.0,
+    -2_187.0 / 6_784.0,
+    11.0 / 84.0,
+    0.0,
+];
+
+/// Fourth-order solution weights, used to estimate the truncation error.
+const RKDP_B2: [f64; 7] = [
+    5_179.0 / 57_600.0,
+    0.0,
+    7_571.0 / 16_695.0,
+    393.0 / 640.0,
+    -92_097.0 / 339_200.0,
+    187.0 / 2_100.0,
+    1.0 / 40.0,
+];
+
+/// Integrators for nonlinear dynamics `dx/dt = f(x, u)`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct NumericalIntegration {}
+
+impl NumericalIntegration {
+    /// Integrates `dx/dt = f(x, u)` over `dt` with one fourth-order Runge-Kutta step, holding
+    /// `u` constant.
+    #[must_use]
+    pub fn rk4<const STATES: usize, const INPUTS: usize>(
+        f: impl Fn(&SVector<f64, STATES>, &SVector<f64, INPUTS>) -> SVector<f64, STATES>,
+        x: &SVector<f64, STATES>,
+        u: &SVector<f64, INPUTS>,
+        dt: impl Into<Second>,
+    ) -> SVector<f64, STATES> {
+        let h = dt.into().value();
+
+        let k1 = f(x, u);
+        let k2 = f(&(x + k1 * (h / 2.0)), u);
+        let k3 = f(&(x + k2 * (h / 2.0)), u);
+        let k4 = f(&(x + k3 * h), u);
+
+        x + (k1 + k2 * 2.0 + k3 * 2.0 + k4) * (h / 6.0)
+    }
+
+    /// Integrates the time-varying `dy/dt = f(t, y)` from `t` over `dt` with one fourth-order
+    /// Runge-Kutta step.
+    #[must_use]
+    pub fn rk4_time<const STATES: usize>(
+        f: impl Fn(Second, &SVector<f64, STATES>) -> SVector<f64, STATES>,
+        t: impl Into<Second>,
+        y: &SVector<f64, STATES>,
+        dt: impl Into<Second>,
+    ) -> SVector<f64, STATES> {
+        let t: Second = t.into();
+        let dt: Second = dt.into();
+        let h = dt.value();
+        let half_step = t + Second::new(h / 2.0);
+
+        let k1 = f(t, y);
+        let k2 = f(half_step, &(y + k1 * (h / 2.0)));
+        let k3 = f(half_step, &(y + k2 * (h / 2.0)));
+        let k4 = f(t + dt, &(y + k3 * h));
+
+        y + (k1 + k2 * 2.0 + k3 * 2.0 + k4) * (h / 6.0)
+    }
+
+    /// Integrates `dx/dt = f(x, u)` over `dt` with the adaptive Dormand-Prince method, holding
+    /// `u` constant and keeping the truncation error of each step under 1e-6.
+    #[must_use]
+    pub fn rkdp<const STATES: usize, const INPUTS: usize>(
+        f: impl Fn(&SVector<f64, STATES>, &SVector<f64, INPUTS>) -> SVector<f64, STATES>,
+        x: &SVector<f64, STATES>,
+        u: &SVector<f64, INPUTS>,
+        dt: impl Into<Second>,
+    ) -> SVector<f64, STATES> {
+        Self::rkdp_with_max_error(f, x, u, dt, 1e-6)
+    }
+
+    /// Integrates `dx/dt = f(x, u)` over `dt` with the adaptive Dormand-Prince method, holding
+    /// `u` constant and keeping the truncation error of each step under `max_error`.
+    ///
+    /// If the state diverges, by `f` returning NaN or infinity or by the step shrinking until it
+    /// no longer advances time, the last state reached within the error limit is returned instead
+    /// of the one at `dt`.
+    #[must_use]
+    pub fn rkdp_with_max_error<const STATES: usize, const INPUTS: usize>(
+        f: impl Fn(&SVector<f64, STATES>, &SVector<f64, INPUTS>) -> SVector<f64, STATES>,
+        x: &SVector<f64, STATES>,
+        u: &SVector<f64, INPUTS>,
+        dt: impl Into<Second>,
+        max_error: f64,
+    ) -> SVector<f64, STATES> {
+        let dt = dt.into().value();
+        let mut x = *x;
+        let mut elapsed = 0.0;
+        let mut step = dt;
+
+        while elapsed < dt {
+            step = step.min(dt - elapsed);
+
+            let mut k = [SVector::<f64, STATES>::zeros(); 7];
+            k[0] = f(&x, u);
+            for stage in 1..7 {
+                let mut increment = SVector::zeros();
+                for (k_i, a) in k.iter().zip(RKDP_A[stage - 1]).take(stage) {
+                    increment += k_i * a;
+                }
+                k[stage] = f(&(x + increment * step), u);
+            }
+
+            let mut new_x = x;
+            let mut error = SVector::<f64, STATES>::zeros();
+            for (k_i, (b1, b2)) in k.iter().zip(RKDP_B1.iter().zip(RKDP_B2)) {
+                new_x += k_i * (b1 * step);
+                error += k_i * ((b1 - b2) * step);
+            }
+            let truncation_error = error.norm();
+
+            // a diverged state won't recover with a smaller step, and a step too small to advance
+            // time would be retried forever, so either ends the integration at the last step taken
+            if !truncation_error.is_finite() || elapsed + step <= elapsed {
+                return x;
+            }
+
+            // steps with too much error are retried with a smaller step
+            if truncation_error <= max_error {
+                elapsed += step;
+                x = new_x;
+            }
+
+            // aim the next step at the error limit, with some margin
+            step *= 0.9 * (max_error / truncation_error).powf(1.0 / 5.0);
+        }
+
+        x
+    }
+}
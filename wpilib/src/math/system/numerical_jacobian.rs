@@ -0,0 +1,46 @@
+use nalgebra::{SMatrix, SVector};
+
+const EPSILON: f64 = 1e-5;
+
+/// Finite difference approximations of the Jacobians of nonlinear functions.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct NumericalJacobian {}
+
+impl NumericalJacobian {
+    /// Returns the Jacobian of `f` at `x` using central differences.
+    #[must_use]
+    pub fn numerical_jacobian<const ROWS: usize, const COLS: usize>(
+        f: impl Fn(&SVector<f64, COLS>) -> SVector<f64, ROWS>,
+        x: &SVector<f64, COLS>,
+    ) -> SMatrix<f64, ROWS, COLS> {
+        let mut result = SMatrix::<f64, ROWS, COLS>::zeros();
+        for col in 0..COLS {
+            let mut dx_plus = *x;
+            let mut dx_minus = *x;
+            dx_plus[col] += EPSILON;
+            dx_minus[col] -= EPSILON;
+            result.set_column(col, &((f(&dx_plus) - f(&dx_minus)) / (2.0 * EPSILON)));
+        }
+        result
+    }
+
+    /// Returns the Jacobian of `f(x, u)` with respect to `x`.
+    #[must_use]
+    pub fn numerical_jacobian_x<const ROWS: usize, const STATES: usize, const INPUTS: usize>(
+        f: impl Fn(&SVector<f64, STATES>, &SVector<f64, INPUTS>) -> SVector<f64, ROWS>,
+        x: &SVector<f64, STATES>,
+        u: &SVector<f64, INPUTS>,
+    ) -> SMatrix<f64, ROWS, STATES> {
+        Self::numerical_jacobian(|x| f(x, u), x)
+    }
+
+    /// Returns the Jacobian of `f(x, u)` with respect to `u`.
+    #[must_use]
+    pub fn numerical_jacobian_u<const ROWS: usize, const STATES: usize, const INPUTS: usize>(
+        f: impl Fn(&SVector<f64, STATES>, &SVector<f64, INPUTS>) -> SVector<f64, ROWS>,
+        x: &SVector<f64, STATES>,
+        u: &SVector<f64, INPUTS>,
+    ) -> SMatrix<f64, ROWS, INPUTS> {
+        Self::numerical_jacobian(|u| f(x, u), u)
+    }
+}
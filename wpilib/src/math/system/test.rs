@@ -0,0 +1,430 @@
+use crate::math::controllers::LinearQuadraticRegulator;
+use crate::math::estimator::{KalmanFilter, KalmanTypeFilter};
+use crate::math::system::{
+    DCMotor, Discretization, LinearSystem, LinearSystemId, LinearSystemLoop, NumericalIntegration,
+    NumericalJacobian,
+};
+use crate::math::units::angular_velocity::{RadianPerSecond, RotationPerMinute};
+use crate::math::units::time::Second;
+use approx::assert_relative_eq;
+use nalgebra::{Matrix1, Matrix1x2, Matrix2, Matrix2x1, Vector1, Vector2};
+
+#[test]
+fn dc_motor() {
+    let motor = DCMotor::new_neo(2);
+    assert_relative_eq!(motor.stall_current.value(), 210.0);
+    assert_relative_eq!(motor.r.value(), 12.0 / 210.0);
+    assert_relative_eq!(motor.kt, 2.6 / 105.0);
+    assert_relative_eq!(
+        motor.kv,
+        5676.0 / 60.0 * std::f64::consts::TAU / (12.0 - 12.0 / 210.0 * 3.6)
+    );
+}
+
+#[test]
+fn dc_motor_catalogue() {
+    let falcon = DCMotor::new_falcon500(1);
+    assert_relative_eq!(falcon.stall_torque.value(), 4.69);
+    assert_relative_eq!(falcon.stall_current.value(), 257.0);
+    assert_relative_eq!(
+        RotationPerMinute::from(falcon.free_speed).value(),
+        6380.0,
+        epsilon = 1e-9
+    );
+
+    for motor in [
+        DCMotor::new_cim(3),
+        DCMotor::new_neo(1),
+        DCMotor::new_neo550(1),
+        DCMotor::new_neo_vortex(2),
+        DCMotor::new_falcon500_foc(1),
+        DCMotor::new_kraken_x60(2),
+        DCMotor::new_kraken_x60_foc(4),
+    ] {
+        // stalled at nominal voltage
+        assert_relative_eq!(
+            motor.get_current(0.0, motor.nominal_voltage).value(),
+            motor.stall_current.value(),
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(
+            motor.get_torque(motor.stall_current).value(),
+            motor.stall_torque.value(),
+            epsilon = 1e-9
+        );
+
+        // spinning freely at nominal voltage
+        let free_torque = motor.get_torque(motor.free_current);
+        assert_relative_eq!(
+            motor.get_speed(free_torque, motor.nominal_voltage).value(),
+            motor.free_speed.value(),
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(
+            motor.get_voltage(free_torque, motor.free_speed).value(),
+            motor.nominal_voltage.value(),
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(
+            motor.get_current_from_torque(free_torque).value(),
+            motor.free_current.value(),
+            epsilon = 1e-9
+        );
+    }
+}
+
+#[test]
+fn dc_motor_reduction() {
+    let motor = DCMotor::new_kraken_x60(2);
+    let geared = motor.with_reduction(10.0);
+    assert_relative_eq!(
+        geared.stall_torque.value(),
+        motor.stall_torque.value() * 10.0
+    );
+    assert_relative_eq!(geared.free_speed.value(), motor.free_speed.value() / 10.0);
+    assert_eq!(geared.stall_current, motor.stall_current);
+
+    // the same physics seen from the output shaft
+    assert_relative_eq!(
+        geared.get_current(RadianPerSecond::new(20.0), 12.0).value(),
+        motor.get_current(RadianPerSecond::new(200.0), 12.0).value(),
+        epsilon = 1e-9
+    );
+
+    // a reduction is equivalent to gearing in the plant models
+    let direct = LinearSystemId::create_flywheel_system(motor, 0.01, 10.0);
+    let reduced = LinearSystemId::create_flywheel_system(geared, 0.01, 1.0);
+    assert_relative_eq!(*direct.get_a(), *reduced.get_a(), epsilon = 1e-9);
+    assert_relative_eq!(*direct.get_b(), *reduced.get_b(), epsilon = 1e-9);
+}
+
+#[test]
+fn linear_system_id() {
+    let model = LinearSystemId::create_drivetrain_velocity_system(
+        DCMotor::new_neo(4),
+        70.0,
+        0.05,
+        0.8,
+        6.0,
+        6.0,
+    );
+    assert_relative_eq!(
+        *model.get_a(),
+        Matrix2::new(-10.14132, 3.06598, 3.06598, -10.14132),
+        epsilon = 1e-3
+    );
+    assert_relative_eq!(
+        *model.get_b(),
+        Matrix2::new(4.2590, -1.28762, -1.2876, 4.2590),
+        epsilon = 1e-3
+    );
+    assert_eq!(*model.get_c(), Matrix2::identity());
+    assert_eq!(*model.get_d(), Matrix2::zeros());
+
+    let model = LinearSystemId::create_elevator_system(DCMotor::new_neo(2), 5.0, 0.05, 12.0);
+    assert_relative_eq!(
+        *model.get_a(),
+        Matrix2::new(0.0, 1.0, 0.0, -99.05473),
+        epsilon = 1e-3
+    );
+    assert_relative_eq!(*model.get_b(), Matrix2x1::new(0.0, 20.8), epsilon = 1e-3);
+    assert_eq!(*model.get_c(), Matrix1x2::new(1.0, 0.0));
+
+    let model = LinearSystemId::create_flywheel_system(DCMotor::new_neo(2), 0.00032, 1.0);
+    assert_relative_eq!(*model.get_a(), Matrix1::new(-26.87032), epsilon = 1e-3);
+    assert_relative_eq!(*model.get_b(), Matrix1::new(1_354.166_667), epsilon = 1e-3);
+
+    let model = LinearSystemId::create_dc_motor_system(DCMotor::new_neo(2), 0.00032, 1.0);
+    assert_relative_eq!(
+        *model.get_a(),
+        Matrix2::new(0.0, 1.0, 0.0, -26.87032),
+        epsilon = 1e-3
+    );
+    assert_relative_eq!(
+        *model.get_b(),
+        Matrix2x1::new(0.0, 1_354.166_667),
+        epsilon = 1e-3
+    );
+    assert_eq!(*model.get_c(), Matrix2::identity());
+
+    let model = LinearSystemId::identify_position_system(1.0, 0.5);
+    assert_relative_eq!(*model.get_a(), Matrix2::new(0.0, 1.0, 0.0, -2.0));
+    assert_relative_eq!(*model.get_b(), Matrix2x1::new(0.0, 2.0));
+
+    let model = LinearSystemId::identify_velocity_system(1.0, 0.5);
+    assert_relative_eq!(*model.get_a(), Matrix1::new(-2.0));
+    assert_relative_eq!(*model.get_b(), Matrix1::new(2.0));
+
+    let model = LinearSystemId::identify_drivetrain_system(1.0, 0.5, 2.0, 1.0);
+    assert_relative_eq!(*model.get_a(), Matrix2::new(-2.0, 0.0, 0.0, -2.0));
+    assert_relative_eq!(*model.get_b(), Matrix2::new(1.5, 0.5, 0.5, 1.5));
+}
+
+#[test]
+fn linear_system_calculate() {
+    // a velocity system settles where the back-EMF cancels the input
+    let model = LinearSystemId::identify_velocity_system(0.5, 0.1);
+    let mut x = Vector1::new(0.0);
+    for _ in 0..200 {
+        x = model.calculate_x(&x, &Vector1::new(6.0), 0.02);
+    }
+    assert_relative_eq!(x[0], 12.0, epsilon = 1e-6);
+    assert_relative_eq!(model.calculate_y(&x, &Vector1::new(6.0))[0], x[0]);
+
+    let model = LinearSystemId::create_dc_motor_system_kv_ka(0.5, 0.1);
+    let y = model.calculate_y(&Vector2::new(1.0, 2.0), &Vector1::new(0.0));
+    assert_eq!(y, Vector2::new(1.0, 2.0));
+}
+
+#[test]
+fn discretize_ab() {
+    let a = Matrix2::new(0.0, 1.0, 0.0, 0.0);
+    let b = Matrix2x1::new(0.0, 1.0);
+    let (discrete_a, discrete_b) = Discretization::discretize_ab(&a, &b, 1.0);
+
+    assert_relative_eq!(discrete_a, Matrix2::new(1.0, 1.0, 0.0, 1.0), epsilon = 1e-9);
+    assert_relative_eq!(discrete_b, Matrix2x1::new(0.5, 1.0), epsilon = 1e-9);
+}
+
+#[test]
+fn discretize_a() {
+    let a = Matrix2::new(0.0, 1.0, 0.0, 0.0);
+    assert_relative_eq!(
+        Discretization::discretize_a(&a, 0.5),
+        Matrix2::new(1.0, 0.5, 0.0, 1.0),
+        epsilon = 1e-9
+    );
+
+    let a = Matrix1::new(-2.0);
+    assert_relative_eq!(
+        Discretization::discretize_a(&a, 0.3)[0],
+        (-0.6f64).exp(),
+        epsilon = 1e-9
+    );
+}
+
+#[test]
+fn discretize_aq() {
+    // Q_d = ∫₀ᵀ e^(Aτ) Q e^(Aᵀτ) dτ with e^(Aτ) = [1 τ; 0 1]
+    let a = Matrix2::new(0.0, 1.0, 0.0, 0.0);
+    let q = Matrix2::identity();
+    let (discrete_a, discrete_q) = Discretization::discretize_aq(&a, &q, 1.0);
+    assert_relative_eq!(discrete_a, Matrix2::new(1.0, 1.0, 0.0, 1.0), epsilon = 1e-9);
+    assert_relative_eq!(
+        discrete_q,
+        Matrix2::new(4.0 / 3.0, 0.5, 0.5, 1.0),
+        epsilon = 1e-9
+    );
+
+    // Q_d = q (1 − e^(−2T)) / 2 for dx/dt = −x
+    let (_, discrete_q) =
+        Discretization::discretize_aq(&Matrix1::new(-1.0), &Matrix1::new(3.0), 0.2);
+    assert_relative_eq!(
+        discrete_q[0],
+        3.0 * (1.0 - (-0.4f64).exp()) / 2.0,
+        epsilon = 1e-9
+    );
+
+    assert_relative_eq!(
+        Discretization::discretize_r(&Matrix1::new(0.5), 0.02)[0],
+        25.0
+    );
+}
+
+#[test]
+fn rk4() {
+    // dx/dt = x
+    let x = NumericalIntegration::rk4(
+        |x: &Vector1<f64>, _: &Vector1<f64>| *x,
+        &Vector1::new(1.0),
+        &Vector1::new(0.0),
+        0.1,
+    );
+    assert_relative_eq!(x[0], 0.1f64.exp(), epsilon = 1e-6);
+
+    // dx/dt = -x + u settles at u
+    let mut x = Vector1::new(0.0);
+    for _ in 0..100 {
+        x = NumericalIntegration::rk4(|x, u| u - x, &x, &Vector1::new(2.0), 0.1);
+    }
+    assert_relative_eq!(x[0], 2.0 * (1.0 - (-10.0f64).exp()), epsilon = 1e-6);
+
+    // dy/dt = cos(t)
+    let y = NumericalIntegration::rk4_time(
+        |t, _: &Vector1<f64>| Vector1::new(t.value().cos()),
+        0.5,
+        &Vector1::new(0.0),
+        0.1,
+    );
+    assert_relative_eq!(y[0], 0.6f64.sin() - 0.5f64.sin(), epsilon = 1e-8);
+}
+
+#[test]
+fn rkdp() {
+    // dx/dt = x over a step much longer than RK4 could take accurately
+    let x = NumericalIntegration::rkdp(
+        |x: &Vector1<f64>, _: &Vector1<f64>| *x,
+        &Vector1::new(1.0),
+        &Vector1::new(0.0),
+        1.0,
+    );
+    assert_relative_eq!(x[0], 1.0f64.exp(), epsilon = 1e-5);
+
+    // a harmonic oscillator, x'' = −x
+    let x = NumericalIntegration::rkdp_with_max_error(
+        |x: &Vector2<f64>, _: &Vector1<f64>| Vector2::new(x[1], -x[0]),
+        &Vector2::new(1.0, 0.0),
+        &Vector1::new(0.0),
+        std::f64::consts::PI,
+        1e-9,
+    );
+    assert_relative_eq!(x, Vector2::new(-1.0, 0.0), epsilon = 1e-7);
+
+    // agrees with the exact discretization of a linear system
+    let model = LinearSystemId::create_dc_motor_system(DCMotor::new_neo(1), 0.001, 5.0);
+    let x0 = Vector2::new(0.5, -3.0);
+    let u = Vector1::new(6.0);
+    let x = NumericalIntegration::rkdp(|x, u| model.get_a() * x + model.get_b() * u, &x0, &u, 0.02);
+    assert_relative_eq!(x, model.calculate_x(&x0, &u, 0.02), epsilon = 1e-5);
+}
+
+#[test]
+fn rkdp_diverging() {
+    // neither of these may retry a step forever, and both end on the last state accepted
+    let x = NumericalIntegration::rkdp(
+        |_: &Vector1<f64>, _: &Vector1<f64>| Vector1::new(f64::NAN),
+        &Vector1::new(1.0),
+        &Vector1::new(0.0),
+        0.02,
+    );
+    assert_relative_eq!(x[0], 1.0);
+
+    // dx/dt = x² escapes to infinity at t = 1
+    let x = NumericalIntegration::rkdp(
+        |x: &Vector1<f64>, _: &Vector1<f64>| x.component_mul(x),
+        &Vector1::new(1.0),
+        &Vector1::new(0.0),
+        2.0,
+    );
+    assert!(x[0].is_finite());
+    assert!(x[0] > 1e6);
+}
+
+/// An observer that trusts the model between measurements and measurements completely.
+#[derive(Debug)]
+struct PerfectObserver {
+    plant: LinearSystem<1, 1, 1>,
+    xhat: Vector1<f64>,
+    p: Matrix1<f64>,
+}
+
+impl KalmanTypeFilter<1, 1, 1> for PerfectObserver {
+    fn get_p(&self) -> &Matrix1<f64> {
+        &self.p
+    }
+
+    fn set_p(&mut self, p: Matrix1<f64>) {
+        self.p = p;
+    }
+
+    fn get_xhat(&self) -> &Vector1<f64> {
+        &self.xhat
+    }
+
+    fn set_xhat(&mut self, xhat: Vector1<f64>) {
+        self.xhat = xhat;
+    }
+
+    fn reset(&mut self) {
+        self.xhat = Vector1::zeros();
+    }
+
+    fn predict(&mut self, u: &Vector1<f64>, dt: impl Into<Second>) {
+        self.xhat = self.plant.calculate_x(&self.xhat, u, dt);
+    }
+
+    fn correct(&mut self, _u: &Vector1<f64>, y: &Vector1<f64>) {
+        self.xhat = *y;
+    }
+}
+
+#[test]
+fn linear_system_loop() {
+    let plant = LinearSystemId::create_flywheel_system(DCMotor::new_neo(1), 0.002, 1.0);
+    let controller =
+        LinearQuadraticRegulator::new(&plant, Vector1::new(8.0), Vector1::new(12.0), 0.02);
+    let observer = PerfectObserver {
+        plant,
+        xhat: Vector1::zeros(),
+        p: Matrix1::zeros(),
+    };
+    let mut system_loop = LinearSystemLoop::new(&plant, controller, observer, 12.0, 0.02);
+
+    system_loop.reset(Vector1::zeros());
+    system_loop.set_next_r(Vector1::new(200.0));
+
+    let mut x = Vector1::zeros();
+    for _ in 0..100 {
+        system_loop.correct(&plant.calculate_y(&x, &Vector1::zeros()));
+        system_loop.predict(0.02);
+        let u = system_loop.get_u();
+        assert!(u[0].abs() <= 12.0 + 1e-9);
+        x = plant.calculate_x(&x, &u, 0.02);
+    }
+
+    assert_relative_eq!(x[0], 200.0, epsilon = 0.1);
+    assert_relative_eq!(system_loop.get_xhat_at(0), x[0], epsilon = 0.1);
+    assert!(system_loop.get_error_at(0).abs() < 0.1);
+}
+
+#[test]
+fn linear_system_loop_kalman_filter() {
+    let plant = LinearSystemId::create_flywheel_system(DCMotor::new_neo(1), 0.002, 1.0);
+    let controller =
+        LinearQuadraticRegulator::new(&plant, Vector1::new(8.0), Vector1::new(12.0), 0.02);
+    let observer = KalmanFilter::new(plant, Vector1::new(3.0), Vector1::new(0.01), 0.02);
+    let mut system_loop = LinearSystemLoop::new(&plant, controller, observer, 12.0, 0.02);
+
+    system_loop.reset(Vector1::zeros());
+    system_loop.set_next_r(Vector1::new(200.0));
+
+    let mut x = Vector1::zeros();
+    for _ in 0..100 {
+        system_loop.correct(&plant.calculate_y(&x, &Vector1::zeros()));
+        system_loop.predict(0.02);
+        x = plant.calculate_x(&x, &system_loop.get_u(), 0.02);
+    }
+
+    assert_relative_eq!(x[0], 200.0, epsilon = 0.5);
+    assert_relative_eq!(system_loop.get_xhat_at(0), x[0], epsilon = 0.5);
+}
+
+#[test]
+fn numerical_jacobian() {
+    let a = Matrix2::new(1.0, 2.0, -3.0, 4.0);
+    let b = Matrix2x1::new(5.0, -6.0);
+    let f = |x: &Vector2<f64>, u: &Vector1<f64>| a * x + b * u;
+    let x = Vector2::new(0.3, -0.7);
+    let u = Vector1::new(2.0);
+
+    assert_relative_eq!(
+        NumericalJacobian::numerical_jacobian_x(f, &x, &u),
+        a,
+        epsilon = 1e-6
+    );
+    assert_relative_eq!(
+        NumericalJacobian::numerical_jacobian_u(f, &x, &u),
+        b,
+        epsilon = 1e-6
+    );
+
+    let jacobian = NumericalJacobian::numerical_jacobian(
+        |x: &Vector2<f64>| Vector2::new(x[0] * x[1], x[0].sin()),
+        &x,
+    );
+    assert_relative_eq!(
+        jacobian,
+        Matrix2::new(x[1], x[0], x[0].cos(), 0.0),
+        epsilon = 1e-6
+    );
+}
@@ -1 +1,4 @@
+mod test;
+mod trapezoid_profile;
 
+pub use trapezoid_profile::*;
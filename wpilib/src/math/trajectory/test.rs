@@ -0,0 +1,113 @@
+use crate::math::trajectory::{
+    TrapezoidProfile, TrapezoidProfileConstraints, TrapezoidProfileState,
+};
+use approx::assert_relative_eq;
+
+const DT: f64 = 0.01;
+
+#[test]
+fn trapezoid_profile_reaches_goal() {
+    let constraints = TrapezoidProfileConstraints::new(1.75, 0.75);
+    let goal = TrapezoidProfileState::new(3.0, 0.0);
+    let mut profile = TrapezoidProfile::new(constraints);
+
+    let mut state = TrapezoidProfileState::default();
+    for _ in 0..450 {
+        state = profile.calculate(DT, state, goal);
+        assert!(state.velocity.abs() <= constraints.max_velocity + 1e-9);
+    }
+    assert_eq!(state, goal);
+}
+
+#[test]
+fn trapezoid_profile_backwards() {
+    let constraints = TrapezoidProfileConstraints::new(0.75, 0.75);
+    let goal = TrapezoidProfileState::new(-2.0, 0.0);
+    let mut profile = TrapezoidProfile::new(constraints);
+
+    let mut state = TrapezoidProfileState::default();
+    for _ in 0..400 {
+        state = profile.calculate(DT, state, goal);
+        assert!(state.velocity <= 0.0);
+    }
+    assert_eq!(state, goal);
+}
+
+#[test]
+fn trapezoid_profile_top_speed() {
+    let constraints = TrapezoidProfileConstraints::new(0.75, 0.75);
+    let goal = TrapezoidProfileState::new(4.0, 0.0);
+    let mut profile = TrapezoidProfile::new(constraints);
+
+    let mut state = TrapezoidProfileState::default();
+    for _ in 0..200 {
+        state = profile.calculate(DT, state, goal);
+    }
+    assert_relative_eq!(state.velocity, constraints.max_velocity, epsilon = 1e-4);
+
+    for _ in 0..2000 {
+        state = profile.calculate(DT, state, goal);
+    }
+    assert_eq!(state, goal);
+}
+
+#[test]
+fn trapezoid_profile_triangle() {
+    // too short a move to reach full speed accelerates for half the time and decelerates for half
+    let constraints = TrapezoidProfileConstraints::new(10.0, 2.0);
+    let goal = TrapezoidProfileState::new(1.0, 0.0);
+    let mut profile = TrapezoidProfile::new(constraints);
+
+    let midpoint = profile.calculate(1.0 / 2.0f64.sqrt(), TrapezoidProfileState::default(), goal);
+    assert_relative_eq!(midpoint.position, 0.5, epsilon = 1e-9);
+    assert_relative_eq!(midpoint.velocity, 2.0f64.sqrt(), epsilon = 1e-9);
+    assert_relative_eq!(profile.total_time().value(), 2.0f64.sqrt(), epsilon = 1e-9);
+    assert!(!profile.is_finished(1.0));
+    assert!(profile.is_finished(1.5));
+}
+
+#[test]
+fn trapezoid_profile_timing() {
+    let constraints = TrapezoidProfileConstraints::new(0.75, 0.75);
+    let goal = TrapezoidProfileState::new(2.0, 0.0);
+    let mut profile = TrapezoidProfile::new(constraints);
+
+    let mut state = profile.calculate(DT, TrapezoidProfileState::default(), goal);
+    let predicted_time_left = profile.time_left_until(goal.position).value();
+    assert_relative_eq!(
+        predicted_time_left,
+        profile.total_time().value(),
+        epsilon = 1e-6
+    );
+
+    let mut reached_goal = false;
+    for i in 0..400 {
+        state = profile.calculate(DT, state, goal);
+        if !reached_goal && state == goal {
+            assert_relative_eq!(predicted_time_left, f64::from(i) * DT, epsilon = 0.25);
+            reached_goal = true;
+        }
+    }
+    assert!(reached_goal);
+}
+
+#[test]
+fn trapezoid_profile_timing_before_goal() {
+    let constraints = TrapezoidProfileConstraints::new(0.75, 0.75);
+    let goal = TrapezoidProfileState::new(2.0, 0.0);
+    let mut profile = TrapezoidProfile::new(constraints);
+
+    let mut state = profile.calculate(DT, TrapezoidProfileState::default(), goal);
+    let predicted_time_left = profile.time_left_until(1.0).value();
+
+    // the prediction is measured from the start of the profile, one step before the loop
+    let mut reached_target = false;
+    for i in 0..400 {
+        state = profile.calculate(DT, state, goal);
+        if !reached_target && state.position >= 1.0 {
+            assert_relative_eq!(predicted_time_left, f64::from(i + 2) * DT, epsilon = DT);
+            reached_target = true;
+        }
+    }
+    assert!(reached_target);
+}
@@ -0,0 +1,211 @@
+use crate::math::units::time::Second;
+
+/// The largest velocity and acceleration a [`TrapezoidProfile`] may use.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct TrapezoidProfileConstraints {
+    pub max_velocity: f64,
+    pub max_acceleration: f64,
+}
+
+impl TrapezoidProfileConstraints {
+    #[must_use]
+    pub const fn new(max_velocity: f64, max_acceleration: f64) -> Self {
+        Self {
+            max_velocity,
+            max_acceleration,
+        }
+    }
+}
+
+/// A position and velocity along a [`TrapezoidProfile`].
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct TrapezoidProfileState {
+    pub position: f64,
+    pub velocity: f64,
+}
+
+impl TrapezoidProfileState {
+    #[must_use]
+    pub const fn new(position: f64, velocity: f64) -> Self {
+        Self { position, velocity }
+    }
+}
+
+/// A one-dimensional motion profile that accelerates at the maximum rate, cruises at the maximum
+/// velocity and decelerates at the maximum rate to arrive at a goal.
+///
+/// The profile is recomputed from the given current state on every
+/// [`calculate`](Self::calculate), so it can be sampled a short time ahead each loop with the
+/// previous sample as the current state. When the goal is too close to reach full speed the
+/// profile is a triangle instead.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TrapezoidProfile {
+    constraints: TrapezoidProfileConstraints,
+    direction: f64,
+    current: TrapezoidProfileState,
+    end_accel: f64,
+    end_full_speed: f64,
+    end_decel: f64,
+}
+
+impl TrapezoidProfile {
+    #[must_use]
+    pub const fn new(constraints: TrapezoidProfileConstraints) -> Self {
+        Self {
+            constraints,
+            direction: 1.0,
+            current: TrapezoidProfileState::new(0.0, 0.0),
+            end_accel: 0.0,
+            end_full_speed: 0.0,
+            end_decel: 0.0,
+        }
+    }
+
+    /// Returns the state `t` into the profile from `current` to `goal`.
+    #[must_use]
+    pub fn calculate(
+        &mut self,
+        t: impl Into<Second>,
+        current: TrapezoidProfileState,
+        goal: TrapezoidProfileState,
+    ) -> TrapezoidProfileState {
+        let t = t.into().value();
+        let max_velocity = self.constraints.max_velocity;
+        let max_acceleration = self.constraints.max_acceleration;
+
+        // solve a profile that moves forward and flip it back at the end
+        self.direction = if current.position > goal.position {
+            -1.0
+        } else {
+            1.0
+        };
+        self.current = self.direct(current);
+        let goal = self.direct(goal);
+        self.current.velocity = self.current.velocity.min(max_velocity);
+
+        // a nonzero initial or final velocity truncates the profile, so find the parameters of
+        // the full trapezoid that starts and ends at rest
+        let cutoff_begin = self.current.velocity / max_acceleration;
+        let cutoff_dist_begin = cutoff_begin * cutoff_begin * max_acceleration / 2.0;
+        let cutoff_end = goal.velocity / max_acceleration;
+        let cutoff_dist_end = cutoff_end * cutoff_end * max_acceleration / 2.0;
+
+        let full_trapezoid_dist =
+            cutoff_dist_begin + (goal.position - self.current.position) + cutoff_dist_end;
+        let mut acceleration_time = max_velocity / max_acceleration;
+        let mut full_speed_dist =
+            acceleration_time.mul_add(-acceleration_time * max_acceleration, full_trapezoid_dist);
+
+        // the profile never reaches full speed
+        if full_speed_dist < 0.0 {
+            acceleration_time = (full_trapezoid_dist / max_acceleration).sqrt();
+            full_speed_dist = 0.0;
+        }
+
+        self.end_accel = acceleration_time - cutoff_begin;
+        self.end_full_speed = self.end_accel + full_speed_dist / max_velocity;
+        self.end_decel = self.end_full_speed + acceleration_time - cutoff_end;
+
+        let mut result = self.current;
+        if t < self.end_accel {
+            result.velocity += t * max_acceleration;
+            result.position += (self.current.velocity + t * max_acceleration / 2.0) * t;
+        } else if t < self.end_full_speed {
+            result.velocity = max_velocity;
+            result.position += (self.current.velocity + self.end_accel * max_acceleration / 2.0)
+                * self.end_accel
+                + max_velocity * (t - self.end_accel);
+        } else if t <= self.end_decel {
+            let time_left = self.end_decel - t;
+            result.velocity = time_left.mul_add(max_acceleration, goal.velocity);
+            result.position =
+                goal.position - (goal.velocity + time_left * max_acceleration / 2.0) * time_left;
+        } else {
+            result = goal;
+        }
+        self.direct(result)
+    }
+
+    /// Returns the time from the start of the last calculated profile until it reaches `target`.
+    #[must_use]
+    pub fn time_left_until(&self, target: f64) -> Second {
+        let max_acceleration = self.constraints.max_acceleration;
+
+        let position = self.current.position * self.direction;
+        let mut velocity = self.current.velocity * self.direction;
+        let mut end_accel = self.end_accel * self.direction;
+        let mut end_full_speed = self.end_full_speed.mul_add(self.direction, -end_accel);
+
+        if target < position {
+            end_accel = -end_accel;
+            end_full_speed = -end_full_speed;
+            velocity = -velocity;
+        }
+        end_accel = end_accel.max(0.0);
+        end_full_speed = end_full_speed.max(0.0);
+
+        let dist_to_target = (target - position).abs();
+        if dist_to_target < 1e-6 {
+            return Second::new(0.0);
+        }
+
+        let mut accel_dist =
+            (0.5 * max_acceleration * end_accel).mul_add(end_accel, velocity * end_accel);
+        let decel_velocity = if end_accel > 0.0 {
+            (2.0 * max_acceleration)
+                .mul_add(accel_dist, velocity * velocity)
+                .abs()
+                .sqrt()
+        } else {
+            velocity
+        };
+
+        let mut full_speed_dist = self.constraints.max_velocity * end_full_speed;
+        let decel_dist;
+        if accel_dist > dist_to_target {
+            accel_dist = dist_to_target;
+            full_speed_dist = 0.0;
+            decel_dist = 0.0;
+        } else if accel_dist + full_speed_dist > dist_to_target {
+            full_speed_dist = dist_to_target - accel_dist;
+            decel_dist = 0.0;
+        } else {
+            decel_dist = dist_to_target - full_speed_dist - accel_dist;
+        }
+
+        let accel_time = (-velocity
+            + (2.0 * max_acceleration)
+                .mul_add(accel_dist, velocity * velocity)
+                .abs()
+                .sqrt())
+            / max_acceleration;
+        let decel_time = (-decel_velocity
+            + (-2.0 * max_acceleration)
+                .mul_add(decel_dist, decel_velocity * decel_velocity)
+                .abs()
+                .sqrt())
+            / -max_acceleration;
+        let full_speed_time = full_speed_dist / self.constraints.max_velocity;
+
+        Second::new(accel_time + full_speed_time + decel_time)
+    }
+
+    /// Returns the total duration of the last calculated profile.
+    #[must_use]
+    pub fn total_time(&self) -> Second {
+        Second::new(self.end_decel)
+    }
+
+    /// Returns whether the last calculated profile has reached its goal by `t`.
+    #[must_use]
+    pub fn is_finished(&self, t: impl Into<Second>) -> bool {
+        t.into().value() >= self.end_decel
+    }
+
+    fn direct(&self, state: TrapezoidProfileState) -> TrapezoidProfileState {
+        TrapezoidProfileState::new(
+            state.position * self.direction,
+            state.velocity * self.direction,
+        )
+    }
+}
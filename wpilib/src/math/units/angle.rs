@@ -1,59 +1,41 @@
 use crate::math::units::angular_velocity::{
     DegreePerSecond, RadianPerSecond, RotationPerMinute, RotationPerSecond,
 };
+use crate::math::units::dimension::Angle;
 use crate::math::units::time::{Minute, Second};
-use wpilib_macros::{unit, unit_conversion, unit_dimensional_analysis};
+use std::f64::consts::{PI, TAU};
+use wpilib_macros::unit;
 
 crate::crate_namespace!();
 
-unit!(Degree, f64);
-unit!(Radian, f64);
-unit!(Rotation, f64);
-
-unit_conversion!(Degree f64, Radian f64, degree_to_radian);
-unit_conversion!(Degree f64, Rotation f64, degree_to_rotation);
-unit_conversion!(Radian f64, Rotation f64, radian_to_rotation);
-
-unit_dimensional_analysis!(DegreePerSecond * Second = Degree);
-unit_dimensional_analysis!(RadianPerSecond * Second = Radian);
-unit_dimensional_analysis!(RotationPerSecond * Second = Rotation);
-unit_dimensional_analysis!(RotationPerMinute * Minute = Rotation);
-
-#[must_use]
-pub fn degree_to_radian(degree: f64) -> f64 {
-    degree.to_radians()
-}
-#[must_use]
-pub fn degree_to_rotation(degree: f64) -> f64 {
-    degree / 360.0
-}
-#[must_use]
-pub fn radian_to_rotation(radian: f64) -> f64 {
-    degree_to_rotation(radian.to_degrees())
-}
+unit!(Angle: Radian("rad"), Degree("deg", "°") = PI / 180.0, Rotation("rot") = TAU);
 
 impl Degree {
+    /// Returns the average angular velocity of turning this far in `seconds`.
     #[must_use]
-    pub fn per_second(self, seconds: Second) -> DegreePerSecond {
-        DegreePerSecond::new(self.value() * seconds.value())
+    pub fn per_second(self, seconds: impl Into<Second>) -> DegreePerSecond {
+        (self / seconds.into()).into()
     }
 }
 
 impl Radian {
+    /// Returns the average angular velocity of turning this far in `seconds`.
     #[must_use]
-    pub fn per_second(self, seconds: Second) -> RadianPerSecond {
-        RadianPerSecond::new(self.value() * seconds.value())
+    pub fn per_second(self, seconds: impl Into<Second>) -> RadianPerSecond {
+        self / seconds.into()
     }
 }
 
 impl Rotation {
+    /// Returns the average angular velocity of turning this far in `minutes`.
     #[must_use]
-    pub fn per_minute(self, minutes: Second) -> RotationPerMinute {
-        RotationPerMinute::new(self.value() * minutes.value())
+    pub fn per_minute(self, minutes: impl Into<Minute>) -> RotationPerMinute {
+        (self / minutes.into()).into()
     }
 
+    /// Returns the average angular velocity of turning this far in `seconds`.
     #[must_use]
-    pub fn per_second(self, seconds: Second) -> RotationPerSecond {
-        RotationPerSecond::new(self.value() * seconds.value())
+    pub fn per_second(self, seconds: impl Into<Second>) -> RotationPerSecond {
+        (self / seconds.into()).into()
     }
 }
@@ -0,0 +1,10 @@
+use crate::math::units::dimension::AngularJerk;
+use std::f64::consts::{PI, TAU};
+use wpilib_macros::unit;
+crate::crate_namespace!();
+
+unit!(
+    AngularJerk: RadianPerSecondCubed("rad/s³", "rad/s^3"),
+    DegreePerSecondCubed("deg/s³", "deg/s^3") = PI / 180.0,
+    RotationPerSecondCubed("rot/s³", "rot/s^3") = TAU,
+);
@@ -0,0 +1,10 @@
+use crate::math::units::dimension::Area;
+use wpilib_macros::unit;
+crate::crate_namespace!();
+
+unit!(
+    Area: SquareMeter("m²", "m^2"),
+    SquareCentimeter("cm²", "cm^2") = 1e-4,
+    SquareInch("in²", "in^2") = 0.000_645_16,
+    SquareFoot("ft²", "ft^2") = 0.092_903_04,
+);
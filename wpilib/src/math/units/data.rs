@@ -1,76 +1,46 @@
 use crate::math::units::data_rate::{
     BytesPerSecond, GigabytesPerHour, KilobytesPerSecond, MegabytesPerSecond,
 };
-use crate::math::units::time::Second;
-use wpilib_macros::{unit, unit_conversion};
+use crate::math::units::dimension::Data;
+use crate::math::units::measure::{Giga, Kilo, Mega};
+use crate::math::units::time::{Hour, Second};
+use wpilib_macros::unit;
 crate::crate_namespace!();
 
-unit!(Byte, f64);
-unit!(Kilobyte, f64);
-unit!(Megabyte, f64);
-unit!(Gigabyte, f64);
+unit!(Data: Byte("B"));
 
-unit_conversion!(Byte f64, Kilobyte f64, byte_to_kilobyte);
-unit_conversion!(Byte f64, Megabyte f64, byte_to_megabyte);
-unit_conversion!(Byte f64, Gigabyte f64, byte_to_gigabyte);
-unit_conversion!(Kilobyte f64, Megabyte f64, kilobyte_to_megabyte);
-unit_conversion!(Kilobyte f64, Gigabyte f64, kilobyte_to_gigabyte);
-unit_conversion!(Megabyte f64, Gigabyte f64, megabyte_to_gigabyte);
-
-#[must_use]
-pub fn byte_to_kilobyte(byte: f64) -> f64 {
-    byte / 1000.0
-}
-
-#[must_use]
-pub fn byte_to_megabyte(byte: f64) -> f64 {
-    byte / 1_000_000.0
-}
-
-#[must_use]
-pub fn byte_to_gigabyte(byte: f64) -> f64 {
-    byte / 1_000_000_000.0
-}
-
-#[must_use]
-pub fn kilobyte_to_megabyte(kilobyte: f64) -> f64 {
-    kilobyte / 1000.0
-}
-
-#[must_use]
-pub fn kilobyte_to_gigabyte(kilobyte: f64) -> f64 {
-    kilobyte / 1_000_000.0
-}
-
-#[must_use]
-pub fn megabyte_to_gigabyte(megabyte: f64) -> f64 {
-    megabyte / 1000.0
-}
+pub type Kilobyte = Kilo<Byte>;
+pub type Megabyte = Mega<Byte>;
+pub type Gigabyte = Giga<Byte>;
 
 impl Byte {
+    /// Returns the average rate of moving this much data in `seconds`.
     #[must_use]
-    pub fn per_second(self, seconds: Second) -> BytesPerSecond {
-        BytesPerSecond::new(self.value() * seconds.value())
+    pub fn per_second(self, seconds: impl Into<Second>) -> BytesPerSecond {
+        self / seconds.into()
     }
 }
 
 impl Kilobyte {
+    /// Returns the average rate of moving this much data in `seconds`.
     #[must_use]
-    pub fn per_second(self, seconds: Second) -> KilobytesPerSecond {
-        KilobytesPerSecond::new(self.value() * seconds.value())
+    pub fn per_second(self, seconds: impl Into<Second>) -> KilobytesPerSecond {
+        (self / seconds.into()).into()
     }
 }
 
 impl Megabyte {
+    /// Returns the average rate of moving this much data in `seconds`.
     #[must_use]
-    pub fn per_second(self, seconds: Second) -> MegabytesPerSecond {
-        MegabytesPerSecond::new(self.value() * seconds.value())
+    pub fn per_second(self, seconds: impl Into<Second>) -> MegabytesPerSecond {
+        (self / seconds.into()).into()
     }
 }
 
 impl Gigabyte {
+    /// Returns the average rate of moving this much data in `hours`.
     #[must_use]
-    pub fn per_hour(self, seconds: Second) -> GigabytesPerHour {
-        GigabytesPerHour::new(self.value() * seconds.value() * 3600.0)
+    pub fn per_hour(self, hours: impl Into<Hour>) -> GigabytesPerHour {
+        (self / hours.into()).into()
     }
 }
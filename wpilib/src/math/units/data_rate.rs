@@ -1,73 +1,46 @@
 use crate::math::units::data::{Byte, Gigabyte, Kilobyte, Megabyte};
+use crate::math::units::dimension::DataRate;
+use crate::math::units::measure::{Kilo, Mega};
 use crate::math::units::time::Second;
-use wpilib_macros::{unit, unit_conversion};
+use wpilib_macros::unit;
 crate::crate_namespace!();
 
-unit!(BytesPerSecond, f64);
-unit!(KilobytesPerSecond, f64);
-unit!(MegabytesPerSecond, f64);
-unit!(GigabytesPerHour, f64);
+unit!(
+    DataRate: BytesPerSecond("B/s"),
+    GigabytesPerHour("GB/h") = 1e9 / 3600.0,
+);
 
-unit_conversion!(BytesPerSecond f64, KilobytesPerSecond f64, byte_per_second_to_kilobyte_per_second);
-unit_conversion!(BytesPerSecond f64, MegabytesPerSecond f64, byte_per_second_to_megabyte_per_second);
-unit_conversion!(BytesPerSecond f64, GigabytesPerHour f64, byte_per_second_to_gigabyte_per_hour);
-unit_conversion!(KilobytesPerSecond f64, MegabytesPerSecond f64, kilobyte_per_second_to_megabyte_per_second);
-unit_conversion!(KilobytesPerSecond f64, GigabytesPerHour f64, kilobyte_per_second_to_gigabyte_per_hour);
-
-#[must_use]
-pub fn byte_per_second_to_kilobyte_per_second(byte_per_second: f64) -> f64 {
-    byte_per_second / 1000.0
-}
-
-#[must_use]
-pub fn byte_per_second_to_megabyte_per_second(byte_per_second: f64) -> f64 {
-    byte_per_second / 1_000_000.0
-}
-
-#[must_use]
-pub fn byte_per_second_to_gigabyte_per_hour(byte_per_second: f64) -> f64 {
-    byte_per_second * 0.000_036
-}
-
-#[must_use]
-pub fn kilobyte_per_second_to_megabyte_per_second(kilobyte_per_second: f64) -> f64 {
-    kilobyte_per_second / 1000.0
-}
-
-#[must_use]
-pub fn kilobyte_per_second_to_gigabyte_per_hour(kilobyte_per_second: f64) -> f64 {
-    kilobyte_per_second * 3600.0 / 1_000_000.0
-}
-
-#[must_use]
-pub fn megabyte_per_second_to_gigabyte_per_hour(megabyte_per_second: f64) -> f64 {
-    megabyte_per_second * 3600.0 / 1000.0
-}
+pub type KilobytesPerSecond = Kilo<BytesPerSecond>;
+pub type MegabytesPerSecond = Mega<BytesPerSecond>;
 
 impl BytesPerSecond {
+    /// Returns how much data moves at this rate in `seconds`.
     #[must_use]
-    pub fn to_byte(&self, seconds: Second) -> Byte {
-        Byte::new(self.value * seconds.value())
+    pub fn to_byte(&self, seconds: impl Into<Second>) -> Byte {
+        *self * seconds.into()
     }
 }
 
 impl KilobytesPerSecond {
+    /// Returns how much data moves at this rate in `seconds`.
     #[must_use]
-    pub fn to_kilobyte(&self, seconds: Second) -> Kilobyte {
-        Kilobyte::new(self.value * seconds.value())
+    pub fn to_kilobyte(&self, seconds: impl Into<Second>) -> Kilobyte {
+        (*self * seconds.into()).into()
     }
 }
 
 impl MegabytesPerSecond {
+    /// Returns how much data moves at this rate in `seconds`.
     #[must_use]
-    pub fn to_megabyte(&self, seconds: Second) -> Megabyte {
-        Megabyte::new(self.value * seconds.value())
+    pub fn to_megabyte(&self, seconds: impl Into<Second>) -> Megabyte {
+        (*self * seconds.into()).into()
     }
 }
 
 impl GigabytesPerHour {
+    /// Returns how much data moves at this rate in `seconds`.
     #[must_use]
-    pub fn to_gigabyte(&self, seconds: Second) -> Gigabyte {
-        Gigabyte::new(self.value / (3600.0 / seconds.value()))
+    pub fn to_gigabyte(&self, seconds: impl Into<Second>) -> Gigabyte {
+        (*self * seconds.into()).into()
     }
 }
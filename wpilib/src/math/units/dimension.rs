@@ -0,0 +1,163 @@
+use std::marker::PhantomData;
+use std::ops::{Add, Div, Mul, Sub};
+
+use typenum::{Diff, Sum, N1, N2, N3, P1, P2, Z0};
+
+/// The exponents of the base dimensions a quantity is made of, as `typenum` integers.
+///
+/// The base dimensions are length, mass, time, current, temperature, angle and data, so
+/// `Dimension<P1, Z0, N1, Z0, Z0, Z0, Z0>` is a velocity. Multiplying or dividing two
+/// dimensions adds or subtracts their exponents.
+#[derive(Debug)]
+pub struct Dimension<L, M, T, I, K, A, D>(PhantomData<(L, M, T, I, K, A, D)>);
+
+impl<L1, M1, T1, I1, K1, A1, D1, L2, M2, T2, I2, K2, A2, D2>
+    Mul<Dimension<L2, M2, T2, I2, K2, A2, D2>> for Dimension<L1, M1, T1, I1, K1, A1, D1>
+where
+    L1: Add<L2>,
+    M1: Add<M2>,
+    T1: Add<T2>,
+    I1: Add<I2>,
+    K1: Add<K2>,
+    A1: Add<A2>,
+    D1: Add<D2>,
+{
+    type Output = Dimension<
+        Sum<L1, L2>,
+        Sum<M1, M2>,
+        Sum<T1, T2>,
+        Sum<I1, I2>,
+        Sum<K1, K2>,
+        Sum<A1, A2>,
+        Sum<D1, D2>,
+    >;
+
+    fn mul(self, _: Dimension<L2, M2, T2, I2, K2, A2, D2>) -> Self::Output {
+        Dimension(PhantomData)
+    }
+}
+
+impl<L1, M1, T1, I1, K1, A1, D1, L2, M2, T2, I2, K2, A2, D2>
+    Div<Dimension<L2, M2, T2, I2, K2, A2, D2>> for Dimension<L1, M1, T1, I1, K1, A1, D1>
+where
+    L1: Sub<L2>,
+    M1: Sub<M2>,
+    T1: Sub<T2>,
+    I1: Sub<I2>,
+    K1: Sub<K2>,
+    A1: Sub<A2>,
+    D1: Sub<D2>,
+{
+    type Output = Dimension<
+        Diff<L1, L2>,
+        Diff<M1, M2>,
+        Diff<T1, T2>,
+        Diff<I1, I2>,
+        Diff<K1, K2>,
+        Diff<A1, A2>,
+        Diff<D1, D2>,
+    >;
+
+    fn div(self, _: Dimension<L2, M2, T2, I2, K2, A2, D2>) -> Self::Output {
+        Dimension(PhantomData)
+    }
+}
+
+pub type Dimensionless = Dimension<Z0, Z0, Z0, Z0, Z0, Z0, Z0>;
+pub type Length = Dimension<P1, Z0, Z0, Z0, Z0, Z0, Z0>;
+pub type Mass = Dimension<Z0, P1, Z0, Z0, Z0, Z0, Z0>;
+pub type Time = Dimension<Z0, Z0, P1, Z0, Z0, Z0, Z0>;
+pub type Current = Dimension<Z0, Z0, Z0, P1, Z0, Z0, Z0>;
+pub type Temperature = Dimension<Z0, Z0, Z0, Z0, P1, Z0, Z0>;
+pub type Angle = Dimension<Z0, Z0, Z0, Z0, Z0, P1, Z0>;
+pub type Data = Dimension<Z0, Z0, Z0, Z0, Z0, Z0, P1>;
+
+pub type Velocity = Dimension<P1, Z0, N1, Z0, Z0, Z0, Z0>;
+pub type Acceleration = Dimension<P1, Z0, N2, Z0, Z0, Z0, Z0>;
+pub type AngularVelocity = Dimension<Z0, Z0, N1, Z0, Z0, P1, Z0>;
+pub type AngularAcceleration = Dimension<Z0, Z0, N2, Z0, Z0, P1, Z0>;
+pub type Energy = Dimension<P2, P1, N2, Z0, Z0, Z0, Z0>;
+pub type Power = Dimension<P2, P1, N3, Z0, Z0, Z0, Z0>;
+pub type Voltage = Dimension<P2, P1, N3, N1, Z0, Z0, Z0>;
+pub type Resistance = Dimension<P2, P1, N3, N2, Z0, Z0, Z0>;
+/// Energy per angle, so a torque turned through an angle is work done.
+pub type Torque = Dimension<P2, P1, N2, Z0, Z0, N1, Z0>;
+/// Torque per angular acceleration.
+pub type MomentOfInertia = Dimension<P2, P1, Z0, Z0, Z0, N2, Z0>;
+pub type DataRate = Dimension<Z0, Z0, N1, Z0, Z0, Z0, P1>;
+pub type Area = Dimension<P2, Z0, Z0, Z0, Z0, Z0, Z0>;
+pub type Force = Dimension<P1, P1, N2, Z0, Z0, Z0, Z0>;
+pub type Pressure = Dimension<N1, P1, N2, Z0, Z0, Z0, Z0>;
+pub type Frequency = Dimension<Z0, Z0, N1, Z0, Z0, Z0, Z0>;
+pub type AngularJerk = Dimension<Z0, Z0, N3, Z0, Z0, P1, Z0>;
+pub type CurrentRate = Dimension<Z0, Z0, N1, P1, Z0, Z0, Z0>;
+/// Voltage per linear velocity, a feedforward's `k_v`.
+pub type VelocityGain = Dimension<P1, P1, N2, N1, Z0, Z0, Z0>;
+/// Voltage per linear acceleration, a feedforward's `k_a`.
+pub type AccelerationGain = Dimension<P1, P1, N1, N1, Z0, Z0, Z0>;
+/// Voltage per angular velocity, a feedforward's `k_v`.
+pub type AngularVelocityGain = Dimension<P2, P1, N2, N1, Z0, N1, Z0>;
+/// Voltage per angular acceleration, a feedforward's `k_a`.
+pub type AngularAccelerationGain = Dimension<P2, P1, N1, N1, Z0, N1, Z0>;
+/// Angular velocity per voltage, a motor's velocity constant.
+pub type MotorVelocityConstant = Dimension<N2, N1, P2, P1, Z0, P1, Z0>;
+
+/// A scalar quantity in some unit of a [`Dimension`], generated by `unit!`.
+///
+/// A value `x` in the unit is `(x + OFFSET) * SCALE` in the group's coherent SI unit; the offset
+/// is only nonzero for temperatures.
+pub trait Unit: Copy {
+    type Dimension;
+    const SCALE: f64;
+    const OFFSET: f64;
+    /// The symbol the unit is displayed with, such as `m` or `ft/s`.
+    const SYMBOL: &'static str;
+
+    fn from_value(value: f64) -> Self;
+
+    fn into_value(self) -> f64;
+
+    /// Returns the value in the coherent SI unit of the dimension.
+    #[must_use]
+    fn to_si(self) -> f64 {
+        (self.into_value() + Self::OFFSET) * Self::SCALE
+    }
+
+    /// Converts to any unit of the same dimension.
+    #[must_use]
+    #[allow(clippy::float_cmp)]
+    fn to<U: Unit<Dimension = Self::Dimension>>(self) -> U {
+        if U::SCALE == Self::SCALE && U::OFFSET == Self::OFFSET {
+            U::from_value(self.into_value())
+        } else {
+            U::from_si(self.to_si())
+        }
+    }
+}
+
+/// Something that can be built from a value in a coherent SI unit, which is every [`Unit`] and
+/// `f64` for dimensionless quantities.
+pub trait FromSi {
+    fn from_si(value: f64) -> Self;
+}
+
+impl FromSi for f64 {
+    fn from_si(value: f64) -> Self {
+        value
+    }
+}
+
+impl<U: Unit> FromSi for U {
+    fn from_si(value: f64) -> Self {
+        Self::from_value(value / Self::SCALE - Self::OFFSET)
+    }
+}
+
+/// The coherent SI unit of a [`Dimension`], which products and quotients of units are given in.
+pub trait SiUnit {
+    type Unit: FromSi;
+}
+
+impl SiUnit for Dimensionless {
+    type Unit = f64;
+}
@@ -1,35 +1,27 @@
+use crate::math::units::dimension::{Current, CurrentRate, Energy, Power, Resistance, Voltage};
 use crate::math::units::time::Second;
-use wpilib_macros::{unit, unit_conversion, unit_dimensional_analysis};
+use wpilib_macros::unit;
 crate::crate_namespace!();
 
-unit!(Joule, f64);
-unit!(Volt, f64);
-unit!(Amp, f64);
-unit!(Watt, f64);
-unit!(WattHour, f64);
-unit!(Ohm, f64);
-
-unit_conversion!(Joule f64, WattHour f64, joule_to_watt_hour);
-
-#[must_use]
-pub fn joule_to_watt_hour(joule: f64) -> f64 {
-    joule / 3600.0
-}
-
-unit_dimensional_analysis!(Volt * Amp = Watt);
-
-unit_dimensional_analysis!(Watt * Second = Joule);
+unit!(Energy: Joule("J"), WattHour("Wh") = 3600.0);
+unit!(Voltage: Volt("V"));
+unit!(Current: Amp("A"));
+unit!(CurrentRate: AmpPerSecond("A/s"));
+unit!(Power: Watt("W"));
+unit!(Resistance: Ohm("Ω", "ohm"));
 
 impl Watt {
+    /// Returns the energy delivered at this power over `seconds`.
     #[must_use]
-    pub fn to_watt_hour(&self, seconds: &Second) -> WattHour {
-        WattHour::new(self.value / (3600.0 / seconds.value()))
+    pub fn to_watt_hour(&self, seconds: impl Into<Second>) -> WattHour {
+        (*self * seconds.into()).into()
     }
 }
 
 impl WattHour {
+    /// Returns the average power of delivering this energy over `seconds`.
     #[must_use]
-    pub fn to_watt(&self, seconds: &Second) -> Watt {
-        Watt::new(self.value * (3600.0 / seconds.value()))
+    pub fn to_watt(&self, seconds: impl Into<Second>) -> Watt {
+        *self / seconds.into()
     }
 }
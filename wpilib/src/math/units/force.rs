@@ -0,0 +1,6 @@
+use crate::math::units::dimension::Force;
+use wpilib_macros::unit;
+crate::crate_namespace!();
+
+// a pound of force is the standard weight of a pound, 0.45359237 kg * 9.80665 m/s²
+unit!(Force: Newton("N"), PoundForce("lbf") = 4.448_221_615_260_5);
@@ -0,0 +1,17 @@
+use std::ops::Div;
+
+use crate::math::units::dimension::Frequency;
+use crate::math::units::time::Second;
+use wpilib_macros::unit;
+crate::crate_namespace!();
+
+unit!(Frequency: Hertz("Hz"));
+
+/// A dimensionless amount per unit of time, such as the rate a unitless error changes at.
+impl Div<Second> for f64 {
+    type Output = Hertz;
+
+    fn div(self, rhs: Second) -> Hertz {
+        Hertz::new(self / rhs.value())
+    }
+}
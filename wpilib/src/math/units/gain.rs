@@ -0,0 +1,28 @@
+use crate::math::units::dimension::{
+    AccelerationGain, AngularAccelerationGain, AngularVelocityGain, MotorVelocityConstant,
+    VelocityGain,
+};
+use std::f64::consts::TAU;
+use wpilib_macros::unit;
+crate::crate_namespace!();
+
+unit!(
+    VelocityGain: VoltSecondPerMeter("V·s/m", "V*s/m"),
+    VoltSecondPerFoot("V·s/ft", "V*s/ft") = 1.0 / 0.3048,
+);
+unit!(
+    AccelerationGain: VoltSecondSquaredPerMeter("V·s²/m", "V*s^2/m"),
+    VoltSecondSquaredPerFoot("V·s²/ft", "V*s^2/ft") = 1.0 / 0.3048,
+);
+unit!(
+    AngularVelocityGain: VoltSecondPerRadian("V·s/rad", "V*s/rad"),
+    VoltSecondPerRotation("V·s/rot", "V*s/rot") = 1.0 / TAU,
+);
+unit!(
+    AngularAccelerationGain: VoltSecondSquaredPerRadian("V·s²/rad", "V*s^2/rad"),
+    VoltSecondSquaredPerRotation("V·s²/rot", "V*s^2/rot") = 1.0 / TAU,
+);
+unit!(
+    MotorVelocityConstant: RadianPerSecondPerVolt("rad/s/V"),
+    RotationPerMinutePerVolt("rpm/V") = TAU / 60.0,
+);
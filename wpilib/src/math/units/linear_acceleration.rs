@@ -0,0 +1,8 @@
+use crate::math::units::dimension::Acceleration;
+use wpilib_macros::unit;
+crate::crate_namespace!();
+
+unit!(
+    Acceleration: MeterPerSecondSquared("m/s²", "m/s^2"),
+    FeetPerSecondSquared("ft/s²", "ft/s^2") = 0.3048,
+);
@@ -0,0 +1,474 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
+};
+use std::str::FromStr;
+
+use approx::{AbsDiffEq, RelativeEq, UlpsEq};
+use nalgebra::SimdValue;
+use num::{FromPrimitive, ToPrimitive, Zero};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use simba::scalar::SubsetOf;
+use typenum::{Prod, Quot};
+
+use super::dimension::{FromSi, SiUnit, Unit};
+use super::parse::{ParseUnitError, UnitVisitor};
+
+/// The SI prefixes a [`Measure`] can be displayed and parsed with, by their power of ten.
+const PREFIXES: [(i8, &str); 8] = [
+    (9, "G"),
+    (6, "M"),
+    (3, "k"),
+    (-2, "c"),
+    (-3, "m"),
+    (-6, "µ"),
+    (-6, "u"),
+    (-9, "n"),
+];
+
+/// Returns `10^exponent`, exact for every exponent an `f64` can hold exactly.
+#[must_use]
+pub const fn pow10(exponent: i8) -> f64 {
+    let mut value = 1.0;
+    let mut i = 0;
+    while i < exponent.unsigned_abs() {
+        value *= 10.0;
+        i += 1;
+    }
+    if exponent < 0 {
+        1.0 / value
+    } else {
+        value
+    }
+}
+
+/// Returns the symbol of the SI prefix for `10^exponent`, if it has one.
+#[must_use]
+pub const fn prefix_symbol(exponent: i8) -> Option<&'static str> {
+    let mut i = 0;
+    while i < PREFIXES.len() {
+        if PREFIXES[i].0 == exponent {
+            return Some(PREFIXES[i].1);
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Splits an SI prefix off the end of `number`, returning the rest and the prefix's exponent.
+pub(super) fn strip_prefix(number: &str) -> Option<(&str, i8)> {
+    PREFIXES.iter().find_map(|(exponent, symbol)| {
+        number
+            .strip_suffix(symbol)
+            .map(|number| (number, *exponent))
+    })
+}
+
+/// A quantity in the unit `U` scaled by the SI prefix `10^PREFIX`, so `Measure<Meter, 3>` is a
+/// kilometer and `Measure<Byte, 6>` a megabyte.
+///
+/// It converts, compares and does arithmetic with every unit of `U`'s dimension like the units
+/// `unit!` generates, and displays and parses with the prefix in front of `U`'s symbol.
+///
+/// Like those units, it can be stored in nalgebra vectors and matrices and added and subtracted
+/// within them, but matrix products and decompositions need the matrix
+/// [cast](nalgebra::Matrix::cast) to `f64` first.
+pub struct Measure<U, const PREFIX: i8 = 0> {
+    value: f64,
+    unit: PhantomData<U>,
+}
+
+pub type Giga<U> = Measure<U, 9>;
+pub type Mega<U> = Measure<U, 6>;
+pub type Kilo<U> = Measure<U, 3>;
+pub type Centi<U> = Measure<U, -2>;
+pub type Milli<U> = Measure<U, -3>;
+pub type Micro<U> = Measure<U, -6>;
+pub type Nano<U> = Measure<U, -9>;
+
+impl<U: Unit, const PREFIX: i8> Measure<U, PREFIX> {
+    #[inline]
+    #[must_use]
+    pub const fn new(value: f64) -> Self {
+        Self {
+            value,
+            unit: PhantomData,
+        }
+    }
+    #[inline]
+    #[must_use]
+    pub const fn value(&self) -> f64 {
+        self.value
+    }
+    #[inline]
+    pub const fn set(&mut self, value: f64) {
+        self.value = value;
+    }
+    #[inline]
+    #[must_use]
+    pub fn map(&self, f: impl FnOnce(f64) -> f64) -> Self {
+        Self::new(f(self.value))
+    }
+    /// Returns the quantity in `U` without a prefix.
+    #[inline]
+    #[must_use]
+    pub fn unprefixed(self) -> U {
+        self.to()
+    }
+}
+
+impl<U, const PREFIX: i8> Clone for Measure<U, PREFIX> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<U, const PREFIX: i8> Copy for Measure<U, PREFIX> {}
+
+impl<U: Unit, const PREFIX: i8> Unit for Measure<U, PREFIX> {
+    type Dimension = U::Dimension;
+    const SCALE: f64 = U::SCALE * pow10(PREFIX);
+    const OFFSET: f64 = U::OFFSET / pow10(PREFIX);
+    /// The symbol of `U`, which the prefix is displayed in front of.
+    const SYMBOL: &'static str = U::SYMBOL;
+    #[inline]
+    fn from_value(value: f64) -> Self {
+        Self::new(value)
+    }
+    #[inline]
+    fn into_value(self) -> f64 {
+        self.value
+    }
+}
+
+impl<U: Unit, const PREFIX: i8> fmt::Debug for Measure<U, PREFIX> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Measure({self})")
+    }
+}
+
+impl<U: Unit, const PREFIX: i8> fmt::Display for Measure<U, PREFIX> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // a prefix without a symbol is displayed in the unprefixed unit instead
+        if let Some(prefix) = prefix_symbol(PREFIX) {
+            fmt::Display::fmt(&self.value, f)?;
+            write!(f, " {prefix}{}", U::SYMBOL)
+        } else {
+            fmt::Display::fmt(&self.unprefixed().into_value(), f)?;
+            write!(f, " {}", U::SYMBOL)
+        }
+    }
+}
+
+/// Parses a bare number in this measure, or a number followed by the symbol, with or without a
+/// prefix, of any unit of the same dimension.
+impl<U: Unit + FromStr<Err = ParseUnitError>, const PREFIX: i8> FromStr for Measure<U, PREFIX> {
+    type Err = ParseUnitError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.trim().parse::<f64>().map_or_else(
+            |_| s.parse::<U>().map(Unit::to),
+            |value| Ok(Self::new(value)),
+        )
+    }
+}
+
+impl<U: Unit, const PREFIX: i8, R: Unit<Dimension = U::Dimension>> Add<R> for Measure<U, PREFIX> {
+    type Output = Self;
+    #[inline]
+    fn add(self, rhs: R) -> Self::Output {
+        Self::new(self.value + rhs.to::<Self>().value)
+    }
+}
+
+impl<U: Unit, const PREFIX: i8, R: Unit<Dimension = U::Dimension>> AddAssign<R>
+    for Measure<U, PREFIX>
+{
+    #[inline]
+    fn add_assign(&mut self, rhs: R) {
+        self.value += rhs.to::<Self>().value;
+    }
+}
+
+impl<U: Unit, const PREFIX: i8, R: Unit<Dimension = U::Dimension>> Sub<R> for Measure<U, PREFIX> {
+    type Output = Self;
+    #[inline]
+    fn sub(self, rhs: R) -> Self::Output {
+        Self::new(self.value - rhs.to::<Self>().value)
+    }
+}
+
+impl<U: Unit, const PREFIX: i8, R: Unit<Dimension = U::Dimension>> SubAssign<R>
+    for Measure<U, PREFIX>
+{
+    #[inline]
+    fn sub_assign(&mut self, rhs: R) {
+        self.value -= rhs.to::<Self>().value;
+    }
+}
+
+impl<U: Unit, const PREFIX: i8> Rem for Measure<U, PREFIX> {
+    type Output = Self;
+    #[inline]
+    fn rem(self, rhs: Self) -> Self::Output {
+        Self::new(self.value % rhs.value)
+    }
+}
+
+impl<U: Unit, const PREFIX: i8> RemAssign for Measure<U, PREFIX> {
+    #[inline]
+    fn rem_assign(&mut self, rhs: Self) {
+        self.value %= rhs.value;
+    }
+}
+
+impl<U: Unit, const PREFIX: i8> Neg for Measure<U, PREFIX> {
+    type Output = Self;
+    #[inline]
+    fn neg(self) -> Self::Output {
+        Self::new(-self.value)
+    }
+}
+
+//multiply and divide by any unit, giving the coherent SI unit of the resulting dimension
+impl<U: Unit, const PREFIX: i8, R: Unit> Mul<R> for Measure<U, PREFIX>
+where
+    U::Dimension: Mul<R::Dimension>,
+    Prod<U::Dimension, R::Dimension>: SiUnit,
+{
+    type Output = <Prod<U::Dimension, R::Dimension> as SiUnit>::Unit;
+    #[inline]
+    fn mul(self, rhs: R) -> Self::Output {
+        FromSi::from_si(self.to_si() * rhs.to_si())
+    }
+}
+
+impl<U: Unit, const PREFIX: i8, R: Unit> Div<R> for Measure<U, PREFIX>
+where
+    U::Dimension: Div<R::Dimension>,
+    Quot<U::Dimension, R::Dimension>: SiUnit,
+{
+    type Output = <Quot<U::Dimension, R::Dimension> as SiUnit>::Unit;
+    #[inline]
+    fn div(self, rhs: R) -> Self::Output {
+        FromSi::from_si(self.to_si() / rhs.to_si())
+    }
+}
+
+impl<U: Unit, const PREFIX: i8> Mul<f64> for Measure<U, PREFIX> {
+    type Output = Self;
+    #[inline]
+    fn mul(self, rhs: f64) -> Self::Output {
+        Self::new(self.value * rhs)
+    }
+}
+
+impl<U: Unit, const PREFIX: i8> Mul<Measure<U, PREFIX>> for f64 {
+    type Output = Measure<U, PREFIX>;
+    #[inline]
+    fn mul(self, rhs: Measure<U, PREFIX>) -> Self::Output {
+        rhs * self
+    }
+}
+
+impl<U: Unit, const PREFIX: i8> MulAssign<f64> for Measure<U, PREFIX> {
+    #[inline]
+    fn mul_assign(&mut self, rhs: f64) {
+        self.value *= rhs;
+    }
+}
+
+impl<U: Unit, const PREFIX: i8> Div<f64> for Measure<U, PREFIX> {
+    type Output = Self;
+    #[inline]
+    fn div(self, rhs: f64) -> Self::Output {
+        Self::new(self.value / rhs)
+    }
+}
+
+impl<U: Unit, const PREFIX: i8> DivAssign<f64> for Measure<U, PREFIX> {
+    #[inline]
+    fn div_assign(&mut self, rhs: f64) {
+        self.value /= rhs;
+    }
+}
+
+//compare with any unit of the same dimension
+impl<U: Unit, const PREFIX: i8, R: Unit<Dimension = U::Dimension>> PartialEq<R>
+    for Measure<U, PREFIX>
+{
+    fn eq(&self, other: &R) -> bool {
+        self.value == other.to::<Self>().value
+    }
+}
+
+impl<U: Unit, const PREFIX: i8, R: Unit<Dimension = U::Dimension>> PartialOrd<R>
+    for Measure<U, PREFIX>
+{
+    fn partial_cmp(&self, other: &R) -> Option<Ordering> {
+        self.value.partial_cmp(&other.to::<Self>().value)
+    }
+}
+
+impl<U: Unit, const PREFIX: i8> From<f64> for Measure<U, PREFIX> {
+    #[inline]
+    fn from(value: f64) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<U: Unit, const PREFIX: i8> From<Measure<U, PREFIX>> for f64 {
+    #[inline]
+    fn from(value: Measure<U, PREFIX>) -> Self {
+        value.value
+    }
+}
+
+impl<U: Unit, const PREFIX: i8> Zero for Measure<U, PREFIX> {
+    fn zero() -> Self {
+        Self::new(0.0)
+    }
+    fn is_zero(&self) -> bool {
+        self.value.is_zero()
+    }
+}
+
+impl<U: Unit, const PREFIX: i8> ToPrimitive for Measure<U, PREFIX> {
+    fn to_i64(&self) -> Option<i64> {
+        self.value.to_i64()
+    }
+    fn to_u64(&self) -> Option<u64> {
+        self.value.to_u64()
+    }
+    fn to_f64(&self) -> Option<f64> {
+        Some(self.value)
+    }
+}
+
+impl<U: Unit, const PREFIX: i8> FromPrimitive for Measure<U, PREFIX> {
+    fn from_i64(n: i64) -> Option<Self> {
+        f64::from_i64(n).map(Self::new)
+    }
+    fn from_u64(n: u64) -> Option<Self> {
+        f64::from_u64(n).map(Self::new)
+    }
+    fn from_f64(n: f64) -> Option<Self> {
+        Some(Self::new(n))
+    }
+}
+
+impl<U: Unit, const PREFIX: i8> Serialize for Measure<U, PREFIX> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.value.serialize(serializer)
+    }
+}
+
+impl<'de, U: Unit + FromStr<Err = ParseUnitError>, const PREFIX: i8> Deserialize<'de>
+    for Measure<U, PREFIX>
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(UnitVisitor::<Self>::new())
+        } else {
+            f64::deserialize(deserializer).map(Self::new)
+        }
+    }
+}
+
+//let measures be compared approximately and stored in nalgebra matrices, which can add them but
+//not multiply them, as a measure isn't a field
+impl<U: Unit, const PREFIX: i8> AbsDiffEq for Measure<U, PREFIX> {
+    type Epsilon = f64;
+    fn default_epsilon() -> Self::Epsilon {
+        f64::default_epsilon()
+    }
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+        self.value.abs_diff_eq(&other.value, epsilon)
+    }
+}
+
+impl<U: Unit, const PREFIX: i8> RelativeEq for Measure<U, PREFIX> {
+    fn default_max_relative() -> Self::Epsilon {
+        f64::default_max_relative()
+    }
+    fn relative_eq(
+        &self,
+        other: &Self,
+        epsilon: Self::Epsilon,
+        max_relative: Self::Epsilon,
+    ) -> bool {
+        self.value.relative_eq(&other.value, epsilon, max_relative)
+    }
+}
+
+impl<U: Unit, const PREFIX: i8> UlpsEq for Measure<U, PREFIX> {
+    fn default_max_ulps() -> u32 {
+        f64::default_max_ulps()
+    }
+    fn ulps_eq(&self, other: &Self, epsilon: Self::Epsilon, max_ulps: u32) -> bool {
+        self.value.ulps_eq(&other.value, epsilon, max_ulps)
+    }
+}
+
+impl<U: Unit, const PREFIX: i8> SubsetOf<f64> for Measure<U, PREFIX> {
+    fn to_superset(&self) -> f64 {
+        self.value
+    }
+    fn from_superset_unchecked(element: &f64) -> Self {
+        Self::new(*element)
+    }
+    fn is_in_subset(_: &f64) -> bool {
+        true
+    }
+}
+
+impl<U: Unit, const PREFIX: i8> SubsetOf<Measure<U, PREFIX>> for f64 {
+    fn to_superset(&self) -> Measure<U, PREFIX> {
+        Measure::new(*self)
+    }
+    fn from_superset_unchecked(element: &Measure<U, PREFIX>) -> Self {
+        element.value
+    }
+    fn is_in_subset(_: &Measure<U, PREFIX>) -> bool {
+        true
+    }
+}
+
+impl<U: Unit + 'static, const PREFIX: i8> SimdValue for Measure<U, PREFIX> {
+    type Element = Self;
+    type SimdBool = bool;
+
+    #[inline]
+    fn lanes() -> usize {
+        1
+    }
+    #[inline]
+    fn splat(val: Self::Element) -> Self {
+        val
+    }
+    #[inline]
+    fn extract(&self, _: usize) -> Self::Element {
+        *self
+    }
+    #[inline]
+    unsafe fn extract_unchecked(&self, _: usize) -> Self::Element {
+        *self
+    }
+    #[inline]
+    fn replace(&mut self, _: usize, val: Self::Element) {
+        *self = val;
+    }
+    #[inline]
+    unsafe fn replace_unchecked(&mut self, _: usize, val: Self::Element) {
+        *self = val;
+    }
+    #[inline]
+    fn select(self, cond: Self::SimdBool, other: Self) -> Self {
+        if cond {
+            self
+        } else {
+            other
+        }
+    }
+}
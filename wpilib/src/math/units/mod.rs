@@ -1,13 +1,24 @@
 pub mod angle;
 pub mod angular_acceleration;
+pub mod angular_jerk;
 pub mod angular_velocity;
+pub mod area;
 pub mod data;
 pub mod data_rate;
+pub mod dimension;
 pub mod distance;
 pub mod energy;
+pub mod force;
+pub mod frequency;
+pub mod gain;
+pub mod linear_acceleration;
 pub mod linear_velocity;
 pub mod mass;
+pub mod measure;
 pub mod moment_of_inertia;
+pub mod parse;
+pub mod pressure;
+pub mod tagged;
 pub mod temperature;
 mod test;
 pub mod time;
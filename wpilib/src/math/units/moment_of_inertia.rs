@@ -1,12 +1,9 @@
-use wpilib_macros::{unit, unit_conversion};
+use crate::math::units::dimension::MomentOfInertia;
+use wpilib_macros::unit;
 crate::crate_namespace!();
 
-unit!(KilogramSquareMeter, f64);
-unit!(PoundSquareFoot, f64);
-
-unit_conversion!(KilogramSquareMeter f64, PoundSquareFoot f64, kilogram_square_meter_to_pound_square_foot);
-
-#[must_use]
-pub fn kilogram_square_meter_to_pound_square_foot(kilogram_square_meter: f64) -> f64 {
-    kilogram_square_meter * 0.204_816_143_622_5
-}
+// a pound square foot is 0.45359237 kg * (0.3048 m)^2
+unit!(
+    MomentOfInertia: KilogramSquareMeter("kg·m²", "kg*m^2"),
+    PoundSquareFoot("lb·ft²", "lb*ft^2") = 0.042_140_110_093_804_8,
+);
@@ -0,0 +1,93 @@
+use std::fmt;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use serde::de::{Error, Visitor};
+use thiserror::Error;
+
+use super::dimension::Unit;
+use super::measure::{pow10, strip_prefix};
+
+/// Why a string like `"12 in"` couldn't be parsed as a unit.
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+pub enum ParseUnitError {
+    #[error("{0:?} doesn't have a number before its unit")]
+    InvalidNumber(String),
+    #[error("{0:?} isn't a number followed by a unit of the same dimension")]
+    UnknownUnit(String),
+}
+
+/// Parses `s` as a number followed by `symbol`, or returns `None` if `s` doesn't end in `symbol`
+/// as a whole word.
+///
+/// With `prefixed`, which unit groups pass for their coherent SI unit, the symbol may also have an
+/// SI prefix directly in front of it, like the `k` of `"1.5 km"`. Other symbols can't, so the `m`
+/// left over from matching the `in` of `"3 min"` isn't taken for milli.
+///
+/// # Errors
+/// Returns [`ParseUnitError::InvalidNumber`] if the symbol matches but what's in front of it isn't
+/// a number.
+pub fn parse_symbol(s: &str, symbol: &str, prefixed: bool) -> Option<Result<f64, ParseUnitError>> {
+    let number = s.strip_suffix(symbol)?;
+    let (number, exponent) = if number.ends_with(char::is_alphabetic) {
+        // the symbol is only the end of a longer word, which is fine if the rest is a prefix
+        strip_prefix(number).filter(|_| prefixed)?
+    } else {
+        (number, 0)
+    };
+    let value = number
+        .trim()
+        .parse::<f64>()
+        .map_err(|_| ParseUnitError::InvalidNumber(s.to_owned()));
+    // dividing by an exact power of ten rounds better than multiplying by its inverse
+    Some(value.map(|value| {
+        if exponent < 0 {
+            value / pow10(-exponent)
+        } else {
+            value * pow10(exponent)
+        }
+    }))
+}
+
+/// Deserializes a unit from either a bare number in the unit or a string like `"12 in"`.
+#[derive(Debug)]
+pub struct UnitVisitor<U>(PhantomData<U>);
+
+impl<U> UnitVisitor<U> {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<U> Default for UnitVisitor<U> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<U: Unit + FromStr<Err = ParseUnitError>> Visitor<'_> for UnitVisitor<U> {
+    type Value = U;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a number or a string like \"1.5 {}\"", U::SYMBOL)
+    }
+
+    fn visit_f64<E: Error>(self, value: f64) -> Result<U, E> {
+        Ok(U::from_value(value))
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn visit_i64<E: Error>(self, value: i64) -> Result<U, E> {
+        Ok(U::from_value(value as f64))
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn visit_u64<E: Error>(self, value: u64) -> Result<U, E> {
+        Ok(U::from_value(value as f64))
+    }
+
+    fn visit_str<E: Error>(self, value: &str) -> Result<U, E> {
+        value.parse().map_err(E::custom)
+    }
+}
@@ -0,0 +1,12 @@
+use crate::math::units::dimension::Pressure;
+use crate::math::units::measure::Kilo;
+use wpilib_macros::unit;
+crate::crate_namespace!();
+
+// a psi is a pound of force on a square inch
+unit!(
+    Pressure: Pascal("Pa"),
+    Psi("psi") = 6_894.757_293_168_361,
+);
+
+pub type Kilopascal = Kilo<Pascal>;
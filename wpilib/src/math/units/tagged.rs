@@ -0,0 +1,31 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serializer};
+
+use super::parse::ParseUnitError;
+
+/// Serializes a unit as a string with its symbol, like `"12 in"`, rather than a bare number.
+///
+/// Use with `#[serde(with = "wpilib::math::units::tagged")]` on a unit field.
+///
+/// # Errors
+/// Returns the serializer's error if it can't write a string.
+pub fn serialize<U: Display, S: Serializer>(value: &U, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
+/// Deserializes a unit from a string with the symbol of any unit of the same dimension.
+///
+/// # Errors
+/// Returns an error if the value isn't a string or doesn't parse as the unit.
+pub fn deserialize<'de, U, D>(deserializer: D) -> Result<U, D::Error>
+where
+    U: FromStr<Err = ParseUnitError>,
+    D: Deserializer<'de>,
+{
+    String::deserialize(deserializer)?
+        .parse()
+        .map_err(D::Error::custom)
+}
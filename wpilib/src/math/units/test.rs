@@ -1,18 +1,48 @@
-use crate::{
-    math::units::distance::{Feet, Meter},
-    math::units::linear_velocity::MeterPerSecond,
-    math::units::time::Second,
+use crate::math::units::{
+    angle::{Degree, Radian, Rotation},
+    angular_acceleration::{
+        DegreePerSecondSquared, RadianPerSecondSquared, RotationPerMinuteSquared,
+        RotationPerSecondSquared,
+    },
+    angular_jerk::{DegreePerSecondCubed, RadianPerSecondCubed, RotationPerSecondCubed},
+    angular_velocity::{DegreePerSecond, RadianPerSecond, RotationPerMinute, RotationPerSecond},
+    area::{SquareCentimeter, SquareFoot, SquareInch, SquareMeter},
+    data::{Byte, Gigabyte, Kilobyte, Megabyte},
+    data_rate::{BytesPerSecond, GigabytesPerHour, KilobytesPerSecond, MegabytesPerSecond},
+    dimension::Unit,
+    distance::{Centimeter, Feet, Inch, Meter},
+    energy::{Amp, AmpPerSecond, Joule, Ohm, Volt, Watt, WattHour},
+    force::{Newton, PoundForce},
+    frequency::Hertz,
+    gain::{
+        RadianPerSecondPerVolt, RotationPerMinutePerVolt, VoltSecondPerFoot, VoltSecondPerMeter,
+        VoltSecondPerRadian, VoltSecondPerRotation, VoltSecondSquaredPerFoot,
+        VoltSecondSquaredPerMeter, VoltSecondSquaredPerRadian, VoltSecondSquaredPerRotation,
+    },
+    linear_acceleration::{FeetPerSecondSquared, MeterPerSecondSquared},
+    linear_velocity::{FeetPerSecond, KilometerPerHour, MeterPerSecond, MilePerHour},
+    mass::{Gram, Kilogram, Ounce, Pound},
+    measure::{Kilo, Measure, Micro, Milli, Nano},
+    moment_of_inertia::{KilogramSquareMeter, PoundSquareFoot},
+    parse::ParseUnitError,
+    pressure::{Kilopascal, Pascal, Psi},
+    temperature::{Celsius, Fahrenheit, Kelvin},
+    time::{Hour, Microsecond, Millisecond, Minute, Second},
+    torque::{FootPound, InchPound, KilogramMeter, NewtonCentimeter, NewtonMeter},
 };
+use approx::assert_relative_eq;
+use nalgebra::{Matrix2, Vector2, Vector3};
+use serde::{Deserialize, Serialize};
 
 #[test]
 fn conversion() {
-    let meter = Meter::new(1.0);
-    let feet = Feet::new(3.28084);
+    let feet = Feet::new(1.0);
+    let meter = Meter::new(0.3048);
     assert_eq!(feet, meter);
     assert_eq!(meter, feet);
     let combined = feet + meter;
-    assert_eq!(combined, Feet::new(6.56168));
-    assert_eq!(combined, Meter::new(2.0));
+    assert_eq!(combined, Feet::new(2.0));
+    assert_eq!(combined, Meter::new(0.6096));
     assert!(combined > feet);
 }
 
@@ -23,3 +53,477 @@ fn dim_analysis() {
     let meter_per_second: MeterPerSecond = meter / second;
     assert_eq!(meter_per_second, MeterPerSecond::new(1.0));
 }
+
+#[test]
+fn derived_units() {
+    // products and quotients come out in SI whatever units went in
+    let speed: MeterPerSecond = Feet::new(15.0) / Minute::new(0.25);
+    assert_relative_eq!(speed.value(), 0.3048);
+    let power: Watt = Volt::new(12.0) * Amp::new(2.5);
+    assert_relative_eq!(power.value(), 30.0);
+    let energy: Joule = power * Hour::new(1.0);
+    assert_relative_eq!(energy.to::<WattHour>().value(), 30.0);
+    let ratio: f64 = Inch::new(6.0) / Feet::new(2.0);
+    assert_relative_eq!(ratio, 0.25);
+    assert_relative_eq!(
+        MilePerHour::from(speed * 2.0).value(),
+        1.363_636_363_636_363_6
+    );
+}
+
+#[test]
+fn offset_units() {
+    assert_relative_eq!(Kelvin::from(Celsius::new(-40.0)).value(), 233.15);
+    assert_relative_eq!(
+        Fahrenheit::from(Celsius::new(-40.0)).value(),
+        -40.0,
+        epsilon = 1e-9
+    );
+    assert_relative_eq!(
+        Celsius::from(Fahrenheit::new(212.0)).value(),
+        100.0,
+        epsilon = 1e-9
+    );
+    assert_relative_eq!(Rotation::from(Degree::new(450.0)).value(), 1.25);
+}
+
+/// Checks that every unit of a group converts to every other and back, agreeing on the SI value.
+macro_rules! conversion_suite {
+    ($name:ident: $units:tt) => {
+        #[test]
+        fn $name() {
+            conversion_suite!(@each $units; $units);
+        }
+    };
+    (@each [$($from:ty),+ $(,)?]; $units:tt) => {
+        $(conversion_suite!(@pair $from; $units);)+
+    };
+    (@pair $from:ty; [$($to:ty),+ $(,)?]) => {
+        $(
+            let value = <$from>::new(1.25);
+            let there: $to = value.to();
+            let back: $from = there.to();
+            assert_relative_eq!(back.value(), value.value(), max_relative = 1e-12);
+            assert_relative_eq!(there.to_si(), value.to_si(), max_relative = 1e-12);
+            assert_eq!(there, value);
+        )+
+    };
+}
+
+conversion_suite!(angle_conversions: [Radian, Degree, Rotation]);
+conversion_suite!(angular_velocity_conversions: [
+    RadianPerSecond,
+    DegreePerSecond,
+    RotationPerSecond,
+    RotationPerMinute,
+]);
+conversion_suite!(angular_acceleration_conversions: [
+    RadianPerSecondSquared,
+    DegreePerSecondSquared,
+    RotationPerSecondSquared,
+    RotationPerMinuteSquared,
+]);
+conversion_suite!(data_conversions: [Byte, Kilobyte, Megabyte, Gigabyte]);
+conversion_suite!(data_rate_conversions: [
+    BytesPerSecond,
+    KilobytesPerSecond,
+    MegabytesPerSecond,
+    GigabytesPerHour,
+]);
+conversion_suite!(distance_conversions: [Meter, Feet, Inch, Centimeter]);
+conversion_suite!(energy_conversions: [Joule, WattHour]);
+conversion_suite!(linear_acceleration_conversions: [MeterPerSecondSquared, FeetPerSecondSquared]);
+conversion_suite!(linear_velocity_conversions: [
+    MeterPerSecond,
+    KilometerPerHour,
+    MilePerHour,
+    FeetPerSecond,
+]);
+conversion_suite!(mass_conversions: [Kilogram, Gram, Pound, Ounce]);
+conversion_suite!(moment_of_inertia_conversions: [KilogramSquareMeter, PoundSquareFoot]);
+conversion_suite!(temperature_conversions: [Kelvin, Celsius, Fahrenheit]);
+conversion_suite!(time_conversions: [Second, Hour, Minute, Millisecond, Microsecond]);
+conversion_suite!(torque_conversions: [
+    NewtonMeter,
+    NewtonCentimeter,
+    KilogramMeter,
+    FootPound,
+    InchPound,
+]);
+conversion_suite!(angular_jerk_conversions: [
+    RadianPerSecondCubed,
+    DegreePerSecondCubed,
+    RotationPerSecondCubed,
+]);
+conversion_suite!(area_conversions: [SquareMeter, SquareCentimeter, SquareInch, SquareFoot]);
+conversion_suite!(force_conversions: [Newton, PoundForce]);
+conversion_suite!(pressure_conversions: [Pascal, Kilopascal, Psi]);
+conversion_suite!(velocity_gain_conversions: [VoltSecondPerMeter, VoltSecondPerFoot]);
+conversion_suite!(acceleration_gain_conversions: [
+    VoltSecondSquaredPerMeter,
+    VoltSecondSquaredPerFoot,
+]);
+conversion_suite!(angular_velocity_gain_conversions: [
+    VoltSecondPerRadian,
+    VoltSecondPerRotation,
+]);
+conversion_suite!(angular_acceleration_gain_conversions: [
+    VoltSecondSquaredPerRadian,
+    VoltSecondSquaredPerRotation,
+]);
+conversion_suite!(motor_velocity_constant_conversions: [
+    RadianPerSecondPerVolt,
+    RotationPerMinutePerVolt,
+]);
+
+#[test]
+fn conversion_factors() {
+    assert_relative_eq!(Inch::from(Feet::new(1.0)).value(), 12.0);
+    assert_relative_eq!(MilePerHour::from(FeetPerSecond::new(22.0)).value(), 15.0);
+    assert_relative_eq!(
+        KilometerPerHour::from(MeterPerSecond::new(10.0)).value(),
+        36.0
+    );
+    assert_relative_eq!(Ounce::from(Pound::new(1.0)).value(), 16.0);
+    assert_relative_eq!(Degree::from(Rotation::new(1.0)).value(), 360.0);
+    assert_relative_eq!(
+        RotationPerMinute::from(RotationPerSecond::new(1.0)).value(),
+        60.0
+    );
+    assert_relative_eq!(Millisecond::new(1.5).to::<Microsecond>().value(), 1500.0);
+    assert_relative_eq!(Megabyte::new(1.0).to::<Gigabyte>().value(), 1e-3);
+    assert_relative_eq!(
+        Fahrenheit::from(Celsius::new(100.0)).value(),
+        212.0,
+        epsilon = 1e-9
+    );
+    assert_relative_eq!(InchPound::from(FootPound::new(1.0)).value(), 12.0);
+    assert_relative_eq!(NewtonMeter::from(KilogramMeter::new(1.0)).value(), 9.806_65);
+    assert_relative_eq!(
+        PoundSquareFoot::from(KilogramSquareMeter::new(1.0)).value(),
+        23.730_360_404_231_8,
+        max_relative = 1e-12
+    );
+}
+
+#[test]
+fn dimensional_identities() {
+    // each binding only compiles if the product or quotient has the expected dimension
+    let velocity: MeterPerSecond = Feet::new(10.0) / Millisecond::new(500.0);
+    assert_relative_eq!(velocity.value(), 6.096);
+    let acceleration: MeterPerSecondSquared = velocity / Second::new(2.0);
+    assert_relative_eq!(acceleration.value(), 3.048);
+    let distance: Meter = acceleration * Second::new(2.0) * Second::new(1.0);
+    assert_relative_eq!(distance.value(), 6.096);
+    let angle: Radian = DegreePerSecond::new(90.0) * Second::new(2.0);
+    assert_relative_eq!(angle.value(), std::f64::consts::PI);
+    let angular_acceleration: RadianPerSecondSquared =
+        RotationPerMinute::new(60.0) / Second::new(1.0);
+    assert_relative_eq!(angular_acceleration.value(), std::f64::consts::TAU);
+    let torque: NewtonMeter = KilogramSquareMeter::new(2.0) * angular_acceleration;
+    assert_relative_eq!(torque.value(), 2.0 * std::f64::consts::TAU);
+    let work: Joule = torque * Rotation::new(0.5);
+    assert_relative_eq!(
+        work.value(),
+        2.0 * std::f64::consts::PI * std::f64::consts::TAU
+    );
+    let power: Watt = Volt::new(12.0) * Amp::new(10.0);
+    let resistance: Ohm = Volt::new(12.0) / Amp::new(10.0);
+    assert_relative_eq!(resistance.value(), 1.2);
+    let energy: Joule = power * Minute::new(1.0);
+    assert_relative_eq!(energy.value(), 7200.0);
+    let rate: BytesPerSecond = Megabyte::new(3.0) / Minute::new(1.0);
+    assert_relative_eq!(rate.value(), 50_000.0);
+    let ratio: f64 = Hour::new(1.0) / Second::new(90.0);
+    assert_relative_eq!(ratio, 40.0);
+}
+
+#[test]
+fn helpers() {
+    assert_relative_eq!(Meter::new(10.0).per_second(4.0).value(), 2.5);
+    assert_relative_eq!(Feet::new(10.0).per_second(4.0).value(), 2.5);
+    assert_relative_eq!(Inch::new(24.0).to_feet_per_second(2.0).value(), 1.0);
+    assert_relative_eq!(Centimeter::new(50.0).to_meter_per_second(2.0).value(), 0.25);
+    assert_relative_eq!(Degree::new(90.0).per_second(2.0).value(), 45.0);
+    assert_relative_eq!(Radian::new(3.0).per_second(2.0).value(), 1.5);
+    assert_relative_eq!(Rotation::new(30.0).per_minute(2.0).value(), 15.0);
+    assert_relative_eq!(Rotation::new(3.0).per_second(2.0).value(), 1.5);
+    assert_relative_eq!(Byte::new(100.0).per_second(4.0).value(), 25.0);
+    assert_relative_eq!(Kilobyte::new(100.0).per_second(4.0).value(), 25.0);
+    assert_relative_eq!(Megabyte::new(100.0).per_second(4.0).value(), 25.0);
+    assert_relative_eq!(Gigabyte::new(100.0).per_hour(4.0).value(), 25.0);
+    assert_relative_eq!(BytesPerSecond::new(25.0).to_byte(4.0).value(), 100.0);
+    assert_relative_eq!(
+        KilobytesPerSecond::new(25.0).to_kilobyte(4.0).value(),
+        100.0
+    );
+    assert_relative_eq!(
+        MegabytesPerSecond::new(25.0).to_megabyte(4.0).value(),
+        100.0
+    );
+    assert_relative_eq!(GigabytesPerHour::new(36.0).to_gigabyte(100.0).value(), 1.0);
+    assert_relative_eq!(Watt::new(60.0).to_watt_hour(1800.0).value(), 30.0);
+    assert_relative_eq!(WattHour::new(30.0).to_watt(1800.0).value(), 60.0);
+    assert_relative_eq!(MilePerHour::new(15.0).to_feet(2.0).value(), 44.0);
+    assert_relative_eq!(KilometerPerHour::new(36.0).to_meters(2.0).value(), 20.0);
+}
+
+#[test]
+fn display() {
+    assert_eq!(Inch::new(12.0).to_string(), "12 in");
+    assert_eq!(format!("{:.2}", FeetPerSecond::new(3.5)), "3.50 ft/s");
+    assert_eq!(Celsius::new(-4.0).to_string(), "-4 °C");
+    assert_eq!(RotationPerMinute::new(6000.0).to_string(), "6000 rpm");
+    assert_eq!(format!("{:?}", Meter::new(1.0)), "Meter(1)");
+}
+
+#[test]
+fn parse() {
+    assert_relative_eq!("12 in".parse::<Inch>().expect("parses").value(), 12.0);
+    assert_relative_eq!("12in".parse::<Meter>().expect("parses").value(), 0.3048);
+    assert_relative_eq!(
+        " 3.5 ft/s "
+            .parse::<MeterPerSecond>()
+            .expect("parses")
+            .value(),
+        1.0668
+    );
+    assert_relative_eq!("25 cm".parse::<Meter>().expect("parses").value(), 0.25);
+    assert_relative_eq!("1e3 m".parse::<Centimeter>().expect("parses").value(), 1e5);
+    assert_relative_eq!("3 min".parse::<Second>().expect("parses").value(), 180.0);
+    assert_relative_eq!("250ms".parse::<Second>().expect("parses").value(), 0.25);
+    assert_relative_eq!(
+        "-40 degF".parse::<Celsius>().expect("parses").value(),
+        -40.0,
+        epsilon = 1e-9
+    );
+    assert_relative_eq!(
+        "90 °".parse::<Radian>().expect("parses").value(),
+        std::f64::consts::FRAC_PI_2
+    );
+    // a bare number is in the unit being parsed
+    assert_relative_eq!("2.5".parse::<Feet>().expect("parses").value(), 2.5);
+
+    assert_eq!(
+        "12 ft/s".parse::<Meter>(),
+        Err(ParseUnitError::UnknownUnit("12 ft/s".to_owned()))
+    );
+    assert_eq!(
+        "twelve in".parse::<Meter>(),
+        Err(ParseUnitError::InvalidNumber("twelve in".to_owned()))
+    );
+    assert_eq!(
+        "in".parse::<Meter>(),
+        Err(ParseUnitError::InvalidNumber("in".to_owned()))
+    );
+    // a prefix only goes in front of the coherent unit's symbol, so this isn't 3 milli-inches
+    assert_eq!(
+        "3 min".parse::<Meter>(),
+        Err(ParseUnitError::UnknownUnit("3 min".to_owned()))
+    );
+    assert_eq!(
+        "3 kft".parse::<Meter>(),
+        Err(ParseUnitError::UnknownUnit("3 kft".to_owned()))
+    );
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Config {
+    wheel_diameter: Inch,
+    #[serde(with = "crate::math::units::tagged")]
+    max_speed: MeterPerSecond,
+}
+
+#[test]
+fn serde() {
+    let config = Config {
+        wheel_diameter: Inch::new(4.0),
+        max_speed: MeterPerSecond::new(4.5),
+    };
+    let json = serde_json::to_string(&config).expect("serializes");
+    assert_eq!(json, r#"{"wheel_diameter":4.0,"max_speed":"4.5 m/s"}"#);
+    assert_eq!(
+        serde_json::from_str::<Config>(&json).expect("deserializes"),
+        config
+    );
+
+    // bare numbers and strings in any unit of the dimension both deserialize
+    let config: Config =
+        serde_json::from_str(r#"{"wheel_diameter":"10.16 cm","max_speed":"15 ft/s"}"#)
+            .expect("deserializes");
+    assert_relative_eq!(config.wheel_diameter.value(), 4.0, epsilon = 1e-12);
+    assert_relative_eq!(config.max_speed.value(), 4.572);
+    assert_relative_eq!(
+        serde_json::from_str::<Second>("20")
+            .expect("deserializes")
+            .value(),
+        20.0
+    );
+    assert!(serde_json::from_str::<Second>(r#""20 m""#).is_err());
+}
+
+#[test]
+fn force_and_pressure() {
+    let weight: Newton = Kilogram::new(0.453_592_37) * MeterPerSecondSquared::new(9.806_65);
+    assert_relative_eq!(PoundForce::from(weight).value(), 1.0);
+    assert_relative_eq!(
+        Psi::from(PoundForce::new(1.0) / SquareInch::new(1.0)).value(),
+        1.0
+    );
+    // a 60 psi cylinder with a 1.5 in bore
+    let bore = Inch::new(1.5);
+    let area: SquareMeter = bore * bore * (std::f64::consts::PI / 4.0);
+    let force: Newton = Psi::new(60.0) * area;
+    assert_relative_eq!(
+        PoundForce::from(force).value(),
+        60.0 * std::f64::consts::PI * 1.5 * 1.5 / 4.0,
+        max_relative = 1e-12
+    );
+    let acceleration: MeterPerSecondSquared = force / Kilogram::new(5.0);
+    assert_relative_eq!(acceleration.value(), force.value() / 5.0);
+    let power: Watt = force * MeterPerSecond::new(2.0);
+    assert_relative_eq!(power.value(), force.value() * 2.0);
+    assert_relative_eq!(
+        Kilopascal::from(Psi::new(1.0)).value(),
+        6.894_757_293_168_361
+    );
+}
+
+#[test]
+fn torque_lever_arm() {
+    let torque = NewtonMeter::from_force(PoundForce::new(10.0), Inch::new(6.0));
+    assert_relative_eq!(InchPound::from(torque).value(), 60.0, max_relative = 1e-12);
+    assert_relative_eq!(
+        PoundForce::from(torque.force_at(Feet::new(1.0))).value(),
+        5.0,
+        max_relative = 1e-12
+    );
+    let angular_acceleration: RadianPerSecondSquared = torque / KilogramSquareMeter::new(0.5);
+    assert_relative_eq!(angular_acceleration.value(), torque.value() * 2.0);
+}
+
+#[test]
+fn rates_and_gains() {
+    assert_relative_eq!(Hertz::new(50.0) * Millisecond::new(20.0), 1.0);
+    let current: Amp = AmpPerSecond::new(40.0) * Second::new(0.25);
+    assert_relative_eq!(current.value(), 10.0);
+    let angular_acceleration: RadianPerSecondSquared =
+        RadianPerSecondCubed::new(3.0) * Second::new(2.0);
+    assert_relative_eq!(angular_acceleration.value(), 6.0);
+
+    // strongly typed feedforward gains
+    let k_v: VoltSecondPerMeter = Volt::new(12.0) / FeetPerSecond::new(10.0);
+    let k_a: VoltSecondSquaredPerMeter = Volt::new(1.0) / MeterPerSecondSquared::new(4.0);
+    let voltage: Volt = k_v * MeterPerSecond::new(2.0) + k_a * MeterPerSecondSquared::new(2.0);
+    assert_relative_eq!(voltage.value(), (12.0_f64 / 3.048).mul_add(2.0, 0.5));
+    let k_v: VoltSecondPerRotation = (Volt::new(12.0) / RotationPerMinute::new(6000.0)).into();
+    assert_relative_eq!(k_v.value(), 0.12);
+    let k_a: VoltSecondSquaredPerRadian = Volt::new(1.0) / RadianPerSecondSquared::new(10.0);
+    assert_relative_eq!((k_a * RadianPerSecondSquared::new(5.0)).value(), 0.5);
+    let motor_k_v: RotationPerMinutePerVolt =
+        (RotationPerMinute::new(5676.0) / Volt::new(12.0)).into();
+    assert_relative_eq!(motor_k_v.value(), 473.0);
+    assert_relative_eq!(
+        (motor_k_v * Volt::new(12.0)).value(),
+        RadianPerSecond::from(RotationPerMinute::new(5676.0)).value()
+    );
+}
+
+#[test]
+fn measure_prefixes() {
+    let kilometer = Kilo::<Meter>::new(1.5);
+    assert_eq!(kilometer, Meter::new(1500.0));
+    assert_eq!(Meter::from(kilometer), Meter::new(1500.0));
+    assert_relative_eq!(Kilo::<Meter>::from(Feet::new(1000.0)).value(), 0.3048);
+    assert_relative_eq!(kilometer.to::<Centimeter>().value(), 150_000.0);
+    assert_relative_eq!(Milli::<Meter>::new(250.0).unprefixed().value(), 0.25);
+    assert_eq!(Measure::<Meter>::new(2.0), Meter::new(2.0));
+
+    // arithmetic mixes prefixes and units of the same dimension
+    let total = kilometer + Meter::new(500.0) - Centimeter::new(100.0);
+    assert_relative_eq!(total.value(), 1.999);
+    assert!(Milli::<Second>::new(999.0) < Second::new(1.0));
+    let speed: MeterPerSecond = kilometer / Minute::new(1.0);
+    assert_relative_eq!(speed.value(), 25.0);
+    let distance: Meter = Kilo::<MeterPerSecond>::new(1.0) * Micro::<Second>::new(2.0);
+    assert_relative_eq!(distance.value(), 2e-3);
+    assert_relative_eq!((-kilometer * 2.0).value(), -3.0);
+
+    assert_relative_eq!(Kilo::<Meter>::SCALE, 1e3);
+    assert_relative_eq!(Nano::<Second>::SCALE, 1e-9);
+}
+
+#[test]
+fn measure_display_and_parse() {
+    assert_eq!(Kilo::<Meter>::new(3.0).to_string(), "3 km");
+    assert_eq!(Micro::<Second>::new(2.5).to_string(), "2.5 µs");
+    assert_eq!(Measure::<Byte, 4>::new(1.0).to_string(), "10000 B");
+    assert_eq!(format!("{:.1}", Kilobyte::new(1.25)), "1.2 kB");
+    assert_eq!(
+        format!("{:?}", Megabyte::new(2.0)),
+        "Measure(2 MB)".to_owned()
+    );
+
+    assert_relative_eq!("3 km".parse::<Meter>().expect("parses").value(), 3000.0);
+    assert_relative_eq!("250 ms".parse::<Second>().expect("parses").value(), 0.25);
+    assert_relative_eq!("4 us".parse::<Millisecond>().expect("parses").value(), 4e-3);
+    assert_relative_eq!("1 MB".parse::<Kilobyte>().expect("parses").value(), 1000.0);
+    assert_relative_eq!(
+        "2 ft".parse::<Kilo<Meter>>().expect("parses").value(),
+        6.096e-4
+    );
+    assert_relative_eq!("12".parse::<Kilo<Meter>>().expect("parses").value(), 12.0);
+    assert_eq!(
+        "3 kft/s".parse::<Kilo<Meter>>(),
+        Err(ParseUnitError::UnknownUnit("3 kft/s".to_owned()))
+    );
+
+    let json = serde_json::to_string(&Kilo::<Meter>::new(1.5)).expect("serializes");
+    assert_eq!(json, "1.5");
+    assert_relative_eq!(
+        serde_json::from_str::<Kilo<Meter>>(r#""500 m""#)
+            .expect("deserializes")
+            .value(),
+        0.5
+    );
+}
+
+#[test]
+fn nalgebra_vectors() {
+    let a = Vector2::new(Meter::new(1.0), Meter::new(2.0));
+    let b = Vector2::new(Meter::new(0.5), Meter::new(-1.0));
+    assert_eq!(a + b, Vector2::new(Meter::new(1.5), Meter::new(1.0)));
+    assert_eq!(a - b, Vector2::new(Meter::new(0.5), Meter::new(3.0)));
+    assert_eq!(-a, Vector2::new(Meter::new(-1.0), Meter::new(-2.0)));
+    assert_eq!(
+        Vector2::<Meter>::zeros(),
+        Vector2::new(Meter::new(0.0), Meter::new(0.0))
+    );
+
+    // casting in and out of floats keeps the value in the unit
+    let raw: Vector2<f64> = a.cast();
+    assert_eq!(raw, Vector2::new(1.0, 2.0));
+    let feet: Vector3<Feet> = Vector3::new(1.0, 2.0, 3.0).cast();
+    assert_relative_eq!(
+        feet,
+        Vector3::new(Feet::new(1.0), Feet::new(2.0), Feet::new(3.0))
+    );
+    let kilometers = Vector2::new(Kilo::<Meter>::new(1.0), Kilo::<Meter>::new(2.0));
+    assert_eq!(
+        kilometers.map(Meter::from),
+        Vector2::new(Meter::new(1000.0), Meter::new(2000.0))
+    );
+
+    let matrix = Matrix2::new(
+        Second::new(1.0),
+        Second::new(2.0),
+        Second::new(3.0),
+        Second::new(4.0),
+    );
+    assert_relative_eq!(matrix.transpose()[(0, 1)], Second::new(3.0));
+    assert_relative_eq!(
+        Vector2::new(Radian::new(1.0), Radian::new(2.0)),
+        Vector2::new(Radian::new(1.0), Radian::new(2.0 + 1e-12)),
+        epsilon = 1e-9
+    );
+}
@@ -1,50 +1,33 @@
-use wpilib_macros::{unit, unit_conversion};
+use crate::math::units::angle::Radian;
+use crate::math::units::dimension::Torque;
+use crate::math::units::distance::Meter;
+use crate::math::units::force::Newton;
+use wpilib_macros::unit;
 crate::crate_namespace!();
 
-unit!(NewtonMeter, f64);
-unit!(NewtonCentimeter, f64);
-unit!(KilogramMeter, f64);
-unit!(FootPound, f64);
-unit!(InchPound, f64);
+// a kilogram meter is the torque of a kilogram's standard weight on a meter's lever, and the
+// others are a pound of force on a foot or an inch
+unit!(
+    Torque: NewtonMeter("N·m", "N*m"),
+    NewtonCentimeter("N·cm", "N*cm") = 0.01,
+    KilogramMeter("kgf·m", "kgf*m") = 9.806_65,
+    FootPound("ft·lb", "ft*lb") = 1.355_817_948_331_400_4,
+    InchPound("in·lb", "in*lb") = 0.112_984_829_027_616_7,
+);
 
-unit_conversion!(NewtonMeter f64, NewtonCentimeter f64, newton_meter_to_newton_centimeter);
-unit_conversion!(NewtonMeter f64, KilogramMeter f64, newton_meter_to_kilogram_meter);
-unit_conversion!(NewtonMeter f64, FootPound f64, newton_meter_to_foot_pound);
-unit_conversion!(NewtonMeter f64, InchPound f64, newton_meter_to_inch_pound);
-unit_conversion!(NewtonCentimeter f64, KilogramMeter f64, newton_centimeter_to_kilogram_meter);
-unit_conversion!(NewtonCentimeter f64, FootPound f64, newton_centimeter_to_foot_pound);
-unit_conversion!(NewtonCentimeter f64, InchPound f64, newton_centimeter_to_inch_pound);
-unit_conversion!(KilogramMeter f64, FootPound f64, kilogram_meter_to_foot_pound);
+impl NewtonMeter {
+    /// Returns the torque of `force` applied perpendicular to the end of `lever_arm`.
+    ///
+    /// A torque is energy per angle turned, so the product of a force and a length is a
+    /// [`Joule`](crate::math::units::energy::Joule) rather than a torque without this.
+    #[must_use]
+    pub fn from_force(force: impl Into<Newton>, lever_arm: impl Into<Meter>) -> Self {
+        force.into() * lever_arm.into() / Radian::new(1.0)
+    }
 
-#[must_use]
-pub fn newton_meter_to_newton_centimeter(newton_meter: f64) -> f64 {
-    newton_meter * 100.0
-}
-#[must_use]
-pub fn newton_meter_to_kilogram_meter(newton_meter: f64) -> f64 {
-    newton_meter * 0.101_972
-}
-#[must_use]
-pub fn newton_meter_to_foot_pound(newton_meter: f64) -> f64 {
-    newton_meter * 0.737_562
-}
-#[must_use]
-pub fn newton_meter_to_inch_pound(newton_meter: f64) -> f64 {
-    newton_meter * 8.85075
-}
-#[must_use]
-pub fn newton_centimeter_to_kilogram_meter(newton_centimeter: f64) -> f64 {
-    newton_meter_to_kilogram_meter(newton_centimeter / 100.0)
-}
-#[must_use]
-pub fn newton_centimeter_to_foot_pound(newton_centimeter: f64) -> f64 {
-    newton_meter_to_foot_pound(newton_centimeter / 100.0)
-}
-#[must_use]
-pub fn newton_centimeter_to_inch_pound(newton_centimeter: f64) -> f64 {
-    newton_meter_to_inch_pound(newton_centimeter / 100.0)
-}
-#[must_use]
-pub fn kilogram_meter_to_foot_pound(kilogram_meter: f64) -> f64 {
-    kilogram_meter * 7.23301
+    /// Returns the force this torque applies perpendicular to the end of `lever_arm`.
+    #[must_use]
+    pub fn force_at(self, lever_arm: impl Into<Meter>) -> Newton {
+        self * Radian::new(1.0) / lever_arm.into()
+    }
 }
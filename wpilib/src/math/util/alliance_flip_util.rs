@@ -0,0 +1,170 @@
+use std::f64::consts::PI;
+
+use crate::apriltag::{AprilTagFieldLayout, AprilTagFields};
+use crate::math::geometry::{Pose2d, Pose3d, Rotation2d, Rotation3d, Translation2d, Translation3d};
+use crate::math::kinematics::ChassisSpeeds;
+use crate::math::units::distance::Meter;
+use crate::robots::{self, Alliance};
+
+/// How the red half of a field relates to the blue half.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum FieldSymmetry {
+    /// The red half is the blue half rotated 180° about the center of the field.
+    Rotational,
+    /// The red half is the blue half mirrored across the center line.
+    Mirrored,
+}
+
+/// Flips blue alliance coordinates to the equivalent red alliance coordinates.
+///
+/// Coordinates always use the blue alliance origin; flipping twice returns the original value.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AllianceFlipUtil {
+    pub symmetry: FieldSymmetry,
+    pub field_length: Meter,
+    pub field_width: Meter,
+}
+
+impl AllianceFlipUtil {
+    #[must_use]
+    pub fn new(
+        symmetry: FieldSymmetry,
+        field_length: impl Into<Meter>,
+        field_width: impl Into<Meter>,
+    ) -> Self {
+        Self {
+            symmetry,
+            field_length: field_length.into(),
+            field_width: field_width.into(),
+        }
+    }
+
+    /// Uses the dimensions of `layout`.
+    #[must_use]
+    pub const fn new_layout(symmetry: FieldSymmetry, layout: &AprilTagFieldLayout) -> Self {
+        Self {
+            symmetry,
+            field_length: layout.get_field_length(),
+            field_width: layout.get_field_width(),
+        }
+    }
+
+    /// Uses the dimensions and symmetry of one of the bundled fields.
+    #[must_use]
+    pub fn new_field(field: AprilTagFields) -> Self {
+        Self::new_layout(field.symmetry(), &AprilTagFieldLayout::load_field(field))
+    }
+
+    #[must_use]
+    pub fn flip<T: Flippable>(&self, value: &T) -> T {
+        value.flip(self)
+    }
+
+    /// Flips `value` if `alliance` is red.
+    #[must_use]
+    pub fn flip_for<T: Flippable + Copy>(&self, value: &T, alliance: Option<Alliance>) -> T {
+        if Self::should_flip(alliance) {
+            self.flip(value)
+        } else {
+            *value
+        }
+    }
+
+    /// Flips `value` if the alliance last [set](robots::set_alliance) is red.
+    #[must_use]
+    pub fn apply<T: Flippable + Copy>(&self, value: &T) -> T {
+        self.flip_for(value, robots::get_alliance())
+    }
+
+    /// Whether coordinates authored for blue need flipping for `alliance`.
+    ///
+    /// An unknown alliance is treated as blue.
+    #[must_use]
+    pub fn should_flip(alliance: Option<Alliance>) -> bool {
+        alliance.is_some_and(|alliance| alliance.is_red())
+    }
+}
+
+/// A field coordinate that can be flipped to the other alliance's side.
+pub trait Flippable: Sized {
+    #[must_use]
+    fn flip(&self, flipper: &AllianceFlipUtil) -> Self;
+}
+
+impl Flippable for Translation2d {
+    fn flip(&self, flipper: &AllianceFlipUtil) -> Self {
+        match flipper.symmetry {
+            FieldSymmetry::Rotational => {
+                Self::new(flipper.field_length - self.x, flipper.field_width - self.y)
+            }
+            FieldSymmetry::Mirrored => Self::new(flipper.field_length - self.x, self.y),
+        }
+    }
+}
+
+impl Flippable for Rotation2d {
+    fn flip(&self, flipper: &AllianceFlipUtil) -> Self {
+        match flipper.symmetry {
+            FieldSymmetry::Rotational => self.plus(&Self::new(PI)),
+            FieldSymmetry::Mirrored => Self::new(PI).minus(self),
+        }
+    }
+}
+
+impl Flippable for Pose2d {
+    fn flip(&self, flipper: &AllianceFlipUtil) -> Self {
+        Self::new(self.translation.flip(flipper), self.rotation.flip(flipper))
+    }
+}
+
+impl Flippable for Translation3d {
+    fn flip(&self, flipper: &AllianceFlipUtil) -> Self {
+        let planar = Translation2d::new(self.x, self.y).flip(flipper);
+        Self::new(planar.x, planar.y, self.z)
+    }
+}
+
+impl Flippable for Rotation3d {
+    fn flip(&self, flipper: &AllianceFlipUtil) -> Self {
+        let half_turn = Self::new(0.0, 0.0, PI);
+        match flipper.symmetry {
+            FieldSymmetry::Rotational => self.rotate_by(&half_turn),
+            FieldSymmetry::Mirrored => {
+                // reflect across the center line, then turn around so the rotation stays proper
+                let mut mirrored = self.get_quaternion();
+                mirrored.y = -mirrored.y;
+                mirrored.z = -mirrored.z;
+                half_turn.rotate_by(&Self::new_quaternion(mirrored))
+            }
+        }
+    }
+}
+
+impl Flippable for Pose3d {
+    fn flip(&self, flipper: &AllianceFlipUtil) -> Self {
+        Self::new_trans_rot(self.translation.flip(flipper), self.rotation.flip(flipper))
+    }
+}
+
+/// Flips field-relative speeds.
+impl Flippable for ChassisSpeeds {
+    fn flip(&self, flipper: &AllianceFlipUtil) -> Self {
+        match flipper.symmetry {
+            FieldSymmetry::Rotational => Self::new(-self.vx, -self.vy, self.omega),
+            FieldSymmetry::Mirrored => Self::new(-self.vx, self.vy, -self.omega),
+        }
+    }
+}
+
+/// Flips every sample of a path or trajectory.
+impl<T: Flippable> Flippable for Vec<T> {
+    fn flip(&self, flipper: &AllianceFlipUtil) -> Self {
+        self.iter().map(|value| value.flip(flipper)).collect()
+    }
+}
+
+impl<T: Flippable + Copy, const N: usize> Flippable for [T; N] {
+    fn flip(&self, flipper: &AllianceFlipUtil) -> Self {
+        self.map(|value| value.flip(flipper))
+    }
+}
@@ -0,0 +1,119 @@
+use nalgebra::{DMatrix, SMatrix};
+
+use super::state_space_util::StateSpaceUtil;
+
+/// How many doubling steps are taken before giving up on convergence.
+const MAX_ITERATIONS: usize = 1_000;
+
+/// Solver for the discrete algebraic Riccati equation
+/// `AᵀXA − X − AᵀXB(BᵀXB + R)⁻¹BᵀXA + Q = 0`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Dare {}
+
+impl Dare {
+    /// Returns the stabilizing solution `X` of the DARE, found with the structure-preserving
+    /// doubling algorithm.
+    ///
+    /// # Panics
+    /// Panics if `R` isn't symmetric positive definite, if `Q` isn't symmetric positive
+    /// semidefinite, if (A, B) isn't stabilizable or (A, C) isn't detectable where `Q = CᵀC`, or if
+    /// the iteration doesn't converge.
+    #[must_use]
+    pub fn dare<const STATES: usize, const INPUTS: usize>(
+        a: &SMatrix<f64, STATES, STATES>,
+        b: &SMatrix<f64, STATES, INPUTS>,
+        q: &SMatrix<f64, STATES, STATES>,
+        r: &SMatrix<f64, INPUTS, INPUTS>,
+    ) -> SMatrix<f64, STATES, STATES> {
+        assert!(is_symmetric(r), "R must be symmetric positive definite");
+        let r_inverse = r
+            .cholesky()
+            .expect("R must be symmetric positive definite")
+            .inverse();
+        let q_root = square_root(q).expect("Q must be symmetric positive semidefinite");
+        assert!(
+            StateSpaceUtil::is_stabilizable(a, b),
+            "(A, B) must be stabilizable"
+        );
+        assert!(
+            StateSpaceUtil::is_detectable(a, &q_root),
+            "(A, C) must be detectable where Q = CᵀC"
+        );
+
+        let mut a_k = *a;
+        let mut g_k = b * r_inverse * b.transpose();
+        let mut h_k1 = *q;
+        for _ in 0..MAX_ITERATIONS {
+            let h_k = h_k1;
+
+            let w_inverse = (SMatrix::<f64, STATES, STATES>::identity() + g_k * h_k)
+                .try_inverse()
+                .expect("the DARE iteration must not break down");
+            let v_1 = w_inverse * a_k;
+            let v_2 = (w_inverse * g_k.transpose()).transpose();
+
+            g_k += a_k * v_2 * a_k.transpose();
+            h_k1 = h_k + v_1.transpose() * h_k * a_k;
+            a_k *= v_1;
+
+            assert!(
+                h_k1.iter().all(|value| value.is_finite()),
+                "the DARE iteration must not diverge"
+            );
+            if (h_k1 - h_k).norm() <= 1e-10 * h_k1.norm() {
+                return h_k1;
+            }
+        }
+        panic!("the DARE iteration must converge within {MAX_ITERATIONS} steps");
+    }
+
+    /// Returns the stabilizing solution of the DARE with the cross-term `N`,
+    /// `AᵀXA − X − (AᵀXB + N)(BᵀXB + R)⁻¹(BᵀXA + Nᵀ) + Q = 0`.
+    ///
+    /// # Panics
+    /// Panics under the same conditions as [`dare`](Self::dare), with `A − BR⁻¹Nᵀ` in place of
+    /// `A` and `Q − NR⁻¹Nᵀ` in place of `Q`.
+    #[must_use]
+    pub fn dare_with_cross_term<const STATES: usize, const INPUTS: usize>(
+        a: &SMatrix<f64, STATES, STATES>,
+        b: &SMatrix<f64, STATES, INPUTS>,
+        q: &SMatrix<f64, STATES, STATES>,
+        r: &SMatrix<f64, INPUTS, INPUTS>,
+        n: &SMatrix<f64, STATES, INPUTS>,
+    ) -> SMatrix<f64, STATES, STATES> {
+        let r_inverse = r
+            .cholesky()
+            .expect("R must be symmetric positive definite")
+            .inverse();
+
+        // the cross-term can be folded into A and Q
+        Self::dare(
+            &(a - b * r_inverse * n.transpose()),
+            b,
+            &(q - n * r_inverse * n.transpose()),
+            r,
+        )
+    }
+}
+
+fn is_symmetric<const N: usize>(m: &SMatrix<f64, N, N>) -> bool {
+    (m - m.transpose()).norm() <= 1e-10 * m.norm()
+}
+
+/// Returns `C` with `Q = CᵀC`, or `None` if `Q` isn't symmetric positive semidefinite.
+fn square_root<const STATES: usize>(
+    q: &SMatrix<f64, STATES, STATES>,
+) -> Option<SMatrix<f64, STATES, STATES>> {
+    if !is_symmetric(q) {
+        return None;
+    }
+    let eigen = DMatrix::from_column_slice(STATES, STATES, q.as_slice()).symmetric_eigen();
+    // allow eigenvalues that are only negative from rounding
+    let tolerance = 1e-10 * eigen.eigenvalues.amax().max(1.0);
+    if eigen.eigenvalues.iter().any(|value| *value < -tolerance) {
+        return None;
+    }
+    let c = DMatrix::from_diagonal(&eigen.eigenvalues.map(|value| value.max(0.0).sqrt()))
+        * eigen.eigenvectors.transpose();
+    Some(SMatrix::from_column_slice(c.as_slice()))
+}
@@ -0,0 +1,62 @@
+use crate::math::geometry::{Pose2d, Pose3d, Rotation2d, Rotation3d, Translation2d, Translation3d};
+
+use super::math_util::MathUtil;
+
+/// A value that can be linearly interpolated toward another value of the same type.
+pub trait Interpolatable: Copy {
+    /// Returns the value `t` of the way from `self` to `end_value`, with `t` clamped to `[0, 1]`.
+    #[must_use]
+    fn interpolate(&self, end_value: &Self, t: f64) -> Self;
+}
+
+impl Interpolatable for f64 {
+    fn interpolate(&self, end_value: &Self, t: f64) -> Self {
+        MathUtil::interpolate(*self, *end_value, t)
+    }
+}
+
+impl Interpolatable for Rotation2d {
+    fn interpolate(&self, end_value: &Self, t: f64) -> Self {
+        Self::interpolate(self, end_value, t)
+    }
+}
+
+impl Interpolatable for Translation2d {
+    fn interpolate(&self, end_value: &Self, t: f64) -> Self {
+        Self::interpolate(self, end_value, t)
+    }
+}
+
+impl Interpolatable for Pose2d {
+    fn interpolate(&self, end_value: &Self, t: f64) -> Self {
+        Self::interpolate(self, end_value, t)
+    }
+}
+
+impl Interpolatable for Rotation3d {
+    fn interpolate(&self, end_value: &Self, t: f64) -> Self {
+        Self::interpolate(self, *end_value, t)
+    }
+}
+
+impl Interpolatable for Translation3d {
+    fn interpolate(&self, end_value: &Self, t: f64) -> Self {
+        Self::interpolate(self, *end_value, t)
+    }
+}
+
+impl Interpolatable for Pose3d {
+    fn interpolate(&self, end_value: &Self, t: f64) -> Self {
+        Self::interpolate(self, end_value, t)
+    }
+}
+
+impl<T: Interpolatable, const N: usize> Interpolatable for [T; N] {
+    fn interpolate(&self, end_value: &Self, t: f64) -> Self {
+        let mut values = *self;
+        for (value, end_value) in values.iter_mut().zip(end_value.iter()) {
+            *value = value.interpolate(end_value, t);
+        }
+        values
+    }
+}
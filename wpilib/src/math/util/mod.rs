@@ -1 +1,8 @@
+pub mod alliance_flip_util;
+pub mod dare;
+pub mod interpolatable;
 pub mod math_util;
+pub mod state_space_util;
+pub mod time_interpolatable_buffer;
+
+mod test;
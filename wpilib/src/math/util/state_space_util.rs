@@ -0,0 +1,131 @@
+use nalgebra::{Complex, DMatrix, SMatrix, SVector};
+use parking_lot::Mutex;
+
+static NOISE_STATE: Mutex<u64> = Mutex::new(0x853c_49e6_748f_ea9b);
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct StateSpaceUtil {}
+
+impl StateSpaceUtil {
+    /// Creates a cost matrix from the largest acceptable excursion of each element using Bryson's
+    /// rule, `1 / tolerance²` on the diagonal.
+    ///
+    /// An infinite tolerance means the element doesn't matter and gives it zero cost.
+    #[must_use]
+    pub fn make_cost_matrix<const N: usize>(tolerances: &SVector<f64, N>) -> SMatrix<f64, N, N> {
+        SMatrix::from_diagonal(&tolerances.map(|tolerance| {
+            if tolerance.is_infinite() {
+                0.0
+            } else {
+                1.0 / (tolerance * tolerance)
+            }
+        }))
+    }
+
+    /// Creates a covariance matrix from the standard deviation of each element.
+    #[must_use]
+    pub fn make_covariance_matrix<const N: usize>(
+        std_devs: &SVector<f64, N>,
+    ) -> SMatrix<f64, N, N> {
+        SMatrix::from_diagonal(&std_devs.map(|std_dev| std_dev * std_dev))
+    }
+
+    /// Scales `u` down so that no element's magnitude exceeds `max_magnitude`, keeping the
+    /// direction of the input.
+    #[must_use]
+    pub fn desaturate_input_vector<const INPUTS: usize>(
+        u: &SVector<f64, INPUTS>,
+        max_magnitude: f64,
+    ) -> SVector<f64, INPUTS> {
+        let largest = u.amax();
+        if largest > max_magnitude {
+            u * (max_magnitude / largest)
+        } else {
+            *u
+        }
+    }
+
+    /// Clamps each element of `u` between the matching elements of `u_min` and `u_max`.
+    #[must_use]
+    pub fn clamp_input_max_magnitude<const INPUTS: usize>(
+        u: &SVector<f64, INPUTS>,
+        u_min: &SVector<f64, INPUTS>,
+        u_max: &SVector<f64, INPUTS>,
+    ) -> SVector<f64, INPUTS> {
+        u.zip_zip_map(u_min, u_max, f64::clamp)
+    }
+
+    /// Returns whether the discrete system (A, B) is stabilizable, meaning every mode of `A` that
+    /// isn't already stable can be controlled through `B`, by the Popov-Belevitch-Hautus test.
+    #[must_use]
+    pub fn is_stabilizable<const STATES: usize, const INPUTS: usize>(
+        a: &SMatrix<f64, STATES, STATES>,
+        b: &SMatrix<f64, STATES, INPUTS>,
+    ) -> bool {
+        let eigenvalues =
+            DMatrix::from_column_slice(STATES, STATES, a.as_slice()).complex_eigenvalues();
+        eigenvalues.iter().all(|eigenvalue| {
+            if eigenvalue.norm_sqr() < 1.0 {
+                return true;
+            }
+            // [λI − A, B] must have full row rank for the mode λ to be controllable
+            let mut pbh = DMatrix::<Complex<f64>>::zeros(STATES, STATES + INPUTS);
+            for row in 0..STATES {
+                for column in 0..STATES {
+                    pbh[(row, column)] = Complex::from(-a[(row, column)]);
+                }
+                pbh[(row, row)] += eigenvalue;
+                for column in 0..INPUTS {
+                    pbh[(row, STATES + column)] = Complex::from(b[(row, column)]);
+                }
+            }
+            let singular_values = pbh.singular_values();
+            let tolerance = 1e-9 * singular_values.max().max(1.0);
+            singular_values
+                .iter()
+                .filter(|value| **value > tolerance)
+                .count()
+                == STATES
+        })
+    }
+
+    /// Returns whether the discrete system (A, C) is detectable, meaning every mode of `A` that
+    /// isn't already stable shows up in the outputs `C`.
+    #[must_use]
+    pub fn is_detectable<const STATES: usize, const OUTPUTS: usize>(
+        a: &SMatrix<f64, STATES, STATES>,
+        c: &SMatrix<f64, OUTPUTS, STATES>,
+    ) -> bool {
+        Self::is_stabilizable(&a.transpose(), &c.transpose())
+    }
+
+    /// Reseeds the generator behind [`make_white_noise_vector`](Self::make_white_noise_vector),
+    /// making simulated measurement noise repeatable.
+    pub fn set_noise_seed(seed: u64) {
+        *NOISE_STATE.lock() = seed;
+    }
+
+    /// Returns a vector of independent gaussian samples with the given standard deviations.
+    #[must_use]
+    pub fn make_white_noise_vector<const N: usize>(std_devs: &SVector<f64, N>) -> SVector<f64, N> {
+        let mut state = NOISE_STATE.lock();
+        std_devs.map(|std_dev| {
+            if std_dev == 0.0 {
+                return 0.0;
+            }
+            // Box-Muller transform
+            let u1 = Self::next_uniform(&mut state);
+            let u2 = Self::next_uniform(&mut state);
+            std_dev * (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos()
+        })
+    }
+
+    /// Returns a uniform sample in `(0, 1)` from a 64-bit LCG.
+    #[allow(clippy::cast_precision_loss)]
+    fn next_uniform(state: &mut u64) -> f64 {
+        *state = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        ((*state >> 11) as f64 + 0.5) / (1u64 << 53) as f64
+    }
+}
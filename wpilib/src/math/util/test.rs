@@ -0,0 +1,345 @@
+use crate::apriltag::{AprilTagFieldLayout, AprilTagFields};
+use crate::math::geometry::{Pose2d, Pose3d, Rotation2d, Rotation3d, Translation2d};
+use crate::math::units::angle::Degree;
+use crate::math::util::alliance_flip_util::{AllianceFlipUtil, FieldSymmetry};
+use crate::math::util::dare::Dare;
+use crate::math::util::interpolatable::Interpolatable;
+use crate::math::util::state_space_util::StateSpaceUtil;
+use crate::math::util::time_interpolatable_buffer::TimeInterpolatableBuffer;
+use crate::robots::{self, Alliance};
+use approx::assert_relative_eq;
+use nalgebra::{Matrix1, Matrix2, Matrix3, Vector2, Vector3};
+use std::f64::consts::PI;
+
+#[test]
+fn interpolatable() {
+    assert_relative_eq!(2.0.interpolate(&4.0, 0.25), 2.5);
+    assert_relative_eq!(2.0.interpolate(&4.0, 1.5), 4.0);
+
+    let rotation = Interpolatable::interpolate(
+        &Rotation2d::new(Degree::new(170.0)),
+        &Rotation2d::new(Degree::new(-170.0)),
+        0.5,
+    );
+    assert_relative_eq!(rotation.cos, -1.0, epsilon = 1e-9);
+
+    let translation = Interpolatable::interpolate(
+        &Translation2d::new(0.0, 0.0),
+        &Translation2d::new(2.0, 4.0),
+        0.5,
+    );
+    assert_eq!(translation, Translation2d::new(1.0, 2.0));
+
+    let start = Pose3d::new_xyz_rot(0.0, 0.0, 0.0, Rotation3d::default());
+    let end = Pose3d::new_xyz_rot(2.0, 0.0, 1.0, Rotation3d::default());
+    let pose = Interpolatable::interpolate(&start, &end, 0.5);
+    assert_relative_eq!(pose.translation.x.value(), 1.0, epsilon = 1e-9);
+    assert_relative_eq!(pose.translation.z.value(), 0.5, epsilon = 1e-9);
+    assert_eq!(Interpolatable::interpolate(&start, &end, -1.0), start);
+}
+
+#[test]
+fn time_interpolatable_buffer() {
+    let mut buffer = TimeInterpolatableBuffer::new(10.0);
+    assert_eq!(buffer.get_sample(1.0), None);
+
+    buffer.add_sample(0.0, Rotation2d::new(0.0));
+    buffer.add_sample(1.0, Rotation2d::new(1.0));
+    assert_relative_eq!(
+        buffer.get_sample(0.5).unwrap().value.value(),
+        0.5,
+        epsilon = 1e-9
+    );
+    assert_relative_eq!(
+        buffer.get_sample(-1.0).unwrap().value.value(),
+        0.0,
+        epsilon = 1e-9
+    );
+    assert_relative_eq!(
+        buffer.get_sample(2.0).unwrap().value.value(),
+        1.0,
+        epsilon = 1e-9
+    );
+
+    // samples with the same timestamp are replaced
+    buffer.add_sample(1.0, Rotation2d::new(0.5));
+    assert_eq!(buffer.get_internal_buffer().len(), 2);
+
+    // samples older than the history length are dropped
+    buffer.add_sample(10.5, Rotation2d::new(1.0));
+    assert_eq!(buffer.get_internal_buffer().len(), 2);
+    assert_relative_eq!(buffer.get_internal_buffer()[0].0.value(), 1.0);
+
+    buffer.clear();
+    assert_eq!(buffer.get_sample(1.0), None);
+}
+
+#[test]
+fn time_interpolatable_pose_buffer() {
+    let mut buffer = TimeInterpolatableBuffer::new(10.0);
+    buffer.add_sample(0.0, Pose2d::new_xy_rot(0.0, 0.0, Rotation2d::default()));
+    buffer.add_sample(
+        1.0,
+        Pose2d::new_xy_rot(1.0, 1.0, Rotation2d::new(Degree::new(90.0))),
+    );
+
+    // halfway along the arc rather than the straight line between the poses
+    let sample = buffer.get_sample(0.5).unwrap();
+    assert_relative_eq!(sample.translation.x.value(), 0.5_f64.sqrt(), epsilon = 1e-9);
+    assert_relative_eq!(
+        sample.translation.y.value(),
+        1.0 - 0.5_f64.sqrt(),
+        epsilon = 1e-9
+    );
+    assert_relative_eq!(
+        Degree::from(sample.rotation.value).value(),
+        45.0,
+        epsilon = 1e-9
+    );
+}
+
+#[test]
+fn alliance_flip_rotational() {
+    let flipper = AllianceFlipUtil::new(FieldSymmetry::Rotational, 16.0, 8.0);
+
+    let pose = Pose2d::new_xy_rot(1.0, 2.0, Rotation2d::new(Degree::new(30.0)));
+    let flipped = flipper.flip(&pose);
+    assert_relative_eq!(flipped.translation.x.value(), 15.0);
+    assert_relative_eq!(flipped.translation.y.value(), 6.0);
+    assert_relative_eq!(flipped.rotation.cos, -(PI / 6.0).cos(), epsilon = 1e-9);
+    assert_relative_eq!(flipped.rotation.sin, -(PI / 6.0).sin(), epsilon = 1e-9);
+
+    let pose = Pose3d::new_xyz_rot(1.0, 2.0, 3.0, Rotation3d::new(0.0, 0.0, Degree::new(30.0)));
+    let flipped = flipper.flip(&pose);
+    assert_relative_eq!(flipped.translation.x.value(), 15.0);
+    assert_relative_eq!(flipped.translation.y.value(), 6.0);
+    assert_relative_eq!(flipped.translation.z.value(), 3.0);
+    assert_relative_eq!(
+        Degree::from(flipped.rotation.get_z()).value(),
+        -150.0,
+        epsilon = 1e-9
+    );
+}
+
+#[test]
+fn alliance_flip_mirrored() {
+    let flipper = AllianceFlipUtil::new_field(AprilTagFields::Crescendo2024);
+    assert_eq!(flipper.symmetry, FieldSymmetry::Mirrored);
+
+    let pose = Pose2d::new_xy_rot(1.0, 2.0, Rotation2d::new(Degree::new(30.0)));
+    let flipped = flipper.flip(&pose);
+    assert_relative_eq!(flipped.translation.x.value(), 16.541 - 1.0);
+    assert_relative_eq!(flipped.translation.y.value(), 2.0);
+    assert_relative_eq!(flipped.rotation.cos, -(PI / 6.0).cos(), epsilon = 1e-9);
+    assert_relative_eq!(flipped.rotation.sin, (PI / 6.0).sin(), epsilon = 1e-9);
+
+    // a pitched-up tag still points up after mirroring
+    let rotation = Rotation3d::new(0.0, Degree::new(-20.0), Degree::new(30.0));
+    let flipped = flipper.flip(&rotation);
+    assert_relative_eq!(flipped.get_x().value(), 0.0, epsilon = 1e-9);
+    assert_relative_eq!(Degree::from(flipped.get_y()).value(), -20.0, epsilon = 1e-9);
+    assert_relative_eq!(Degree::from(flipped.get_z()).value(), 150.0, epsilon = 1e-9);
+
+    // the blue speaker tag flips onto the red speaker tag
+    let layout = AprilTagFieldLayout::load_field(AprilTagFields::Crescendo2024);
+    let blue_speaker = layout.get_tag_pose(7).expect("tag 7 exists");
+    let red_speaker = layout.get_tag_pose(4).expect("tag 4 exists");
+    let flipped = flipper.flip(&blue_speaker);
+    assert_relative_eq!(
+        flipped.translation.x.value(),
+        red_speaker.translation.x.value(),
+        epsilon = 1e-3
+    );
+    assert_relative_eq!(
+        flipped.translation.y.value(),
+        red_speaker.translation.y.value()
+    );
+    assert_relative_eq!(
+        flipped.rotation.q.angle_to(&red_speaker.rotation.q),
+        0.0,
+        epsilon = 1e-9
+    );
+
+    // flipping twice is a no-op
+    let path = vec![pose, Pose2d::new_xy_rot(3.0, 1.0, Rotation2d::new(1.0))];
+    let round_trip = flipper.flip(&flipper.flip(&path));
+    for (original, round_trip) in path.iter().zip(&round_trip) {
+        assert_relative_eq!(
+            original.translation.x.value(),
+            round_trip.translation.x.value(),
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(
+            original.rotation.sin,
+            round_trip.rotation.sin,
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(
+            original.rotation.cos,
+            round_trip.rotation.cos,
+            epsilon = 1e-9
+        );
+    }
+}
+
+#[test]
+fn alliance_flip_follows_alliance() {
+    let flipper = AllianceFlipUtil::new(FieldSymmetry::Rotational, 16.0, 8.0);
+    let translation = Translation2d::new(1.0, 2.0);
+
+    assert_eq!(flipper.flip_for(&translation, None), translation);
+    assert_eq!(
+        flipper.flip_for(&translation, Some(Alliance::Blue)),
+        translation
+    );
+    assert_eq!(
+        flipper.flip_for(&translation, Some(Alliance::Red)),
+        Translation2d::new(15.0, 6.0)
+    );
+
+    robots::set_alliance(Some(Alliance::Red));
+    assert_eq!(flipper.apply(&translation), Translation2d::new(15.0, 6.0));
+    robots::set_alliance(None);
+    assert_eq!(flipper.apply(&translation), translation);
+}
+
+#[test]
+fn white_noise() {
+    let std_devs = Vector2::new(0.0, 2.0);
+    let samples: Vec<Vector2<f64>> = (0..10_000)
+        .map(|_| StateSpaceUtil::make_white_noise_vector(&std_devs))
+        .collect();
+
+    assert!(samples.iter().all(|sample| sample[0] == 0.0));
+    let mean = samples.iter().map(|sample| sample[1]).sum::<f64>() / 10_000.0;
+    let variance = samples
+        .iter()
+        .map(|sample| (sample[1] - mean).powi(2))
+        .sum::<f64>()
+        / 10_000.0;
+    assert!(mean.abs() < 0.1);
+    assert_relative_eq!(variance.sqrt(), 2.0, epsilon = 0.1);
+}
+
+#[test]
+fn dare() {
+    let a = Matrix2::new(1.0, 1.0, 0.0, 1.0);
+    let b = Vector2::new(0.0, 1.0);
+    let q = Matrix2::new(1.0, 0.0, 0.0, 0.0);
+    let r = Matrix1::new(0.3);
+
+    let x = Dare::dare(&a, &b, &q, &r);
+    let gain = (b.transpose() * x * b + r)
+        .try_inverse()
+        .expect("R is positive definite")
+        * b.transpose()
+        * x
+        * a;
+    let residual = a.transpose() * x * a - x - a.transpose() * x * b * gain + q;
+    assert_relative_eq!(residual, Matrix2::zeros(), epsilon = 1e-9);
+    assert_relative_eq!(x, x.transpose(), epsilon = 1e-9);
+
+    // Q − NR⁻¹Nᵀ has to stay positive semidefinite
+    let q = Matrix2::identity();
+    let n = Vector2::new(0.1, 0.2);
+    let x = Dare::dare_with_cross_term(&a, &b, &q, &r, &n);
+    let gain = (b.transpose() * x * b + r)
+        .try_inverse()
+        .expect("R is positive definite")
+        * (b.transpose() * x * a + n.transpose());
+    let residual = a.transpose() * x * a - x - (a.transpose() * x * b + n) * gain + q;
+    assert_relative_eq!(residual, Matrix2::zeros(), epsilon = 1e-9);
+}
+
+#[test]
+#[should_panic(expected = "(A, B) must be stabilizable")]
+fn dare_unstabilizable() {
+    let _ = Dare::dare(
+        &Matrix1::new(2.0),
+        &Matrix1::new(0.0),
+        &Matrix1::new(1.0),
+        &Matrix1::new(1.0),
+    );
+}
+
+#[test]
+#[should_panic(expected = "(A, C) must be detectable where Q = CᵀC")]
+fn dare_undetectable() {
+    // the unstable second state is never penalized
+    let _ = Dare::dare(
+        &Matrix2::new(1.0, 0.0, 0.0, 2.0),
+        &Matrix2::identity(),
+        &Matrix2::new(1.0, 0.0, 0.0, 0.0),
+        &Matrix2::identity(),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Q must be symmetric positive semidefinite")]
+fn dare_indefinite_q() {
+    let _ = Dare::dare(
+        &Matrix1::new(1.0),
+        &Matrix1::new(1.0),
+        &Matrix1::new(-1.0),
+        &Matrix1::new(1.0),
+    );
+}
+
+#[test]
+fn state_space_util() {
+    let cost = StateSpaceUtil::make_cost_matrix(&Vector3::new(0.5, 2.0, f64::INFINITY));
+    assert_eq!(cost, Matrix3::from_diagonal(&Vector3::new(4.0, 0.25, 0.0)));
+
+    let covariance = StateSpaceUtil::make_covariance_matrix(&Vector2::new(0.5, 3.0));
+    assert_eq!(covariance, Matrix2::new(0.25, 0.0, 0.0, 9.0));
+
+    let u = StateSpaceUtil::desaturate_input_vector(&Vector2::new(24.0, -6.0), 12.0);
+    assert_eq!(u, Vector2::new(12.0, -3.0));
+    let u = StateSpaceUtil::desaturate_input_vector(&Vector2::new(-24.0, 6.0), 12.0);
+    assert_eq!(u, Vector2::new(-12.0, 3.0));
+    let u = StateSpaceUtil::desaturate_input_vector(&Vector2::new(6.0, 3.0), 12.0);
+    assert_eq!(u, Vector2::new(6.0, 3.0));
+
+    let u = StateSpaceUtil::clamp_input_max_magnitude(
+        &Vector2::new(24.0, -24.0),
+        &Vector2::new(-12.0, -6.0),
+        &Vector2::new(12.0, 6.0),
+    );
+    assert_eq!(u, Vector2::new(12.0, -6.0));
+
+    // a double integrator is stabilizable through its acceleration but not its position alone
+    let a = Matrix2::new(1.0, 0.02, 0.0, 1.0);
+    assert!(StateSpaceUtil::is_stabilizable(
+        &a,
+        &Vector2::new(0.0, 0.02)
+    ));
+    assert!(!StateSpaceUtil::is_stabilizable(
+        &a,
+        &Vector2::new(0.02, 0.0)
+    ));
+    // stable modes don't need to be controlled
+    assert!(StateSpaceUtil::is_stabilizable(
+        &Matrix2::new(0.5, 0.0, 0.0, 1.0),
+        &Vector2::new(0.0, 1.0)
+    ));
+    // complex eigenvalues on the unit circle, a rotation
+    let (sin, cos) = 0.1_f64.sin_cos();
+    let rotation = Matrix2::new(cos, -sin, sin, cos);
+    assert!(StateSpaceUtil::is_stabilizable(
+        &rotation,
+        &Vector2::new(1.0, 0.0)
+    ));
+    assert!(!StateSpaceUtil::is_stabilizable(
+        &rotation,
+        &Vector2::zeros()
+    ));
+
+    assert!(StateSpaceUtil::is_detectable(
+        &a,
+        &Vector2::new(1.0, 0.0).transpose()
+    ));
+    assert!(!StateSpaceUtil::is_detectable(
+        &a,
+        &Vector2::new(0.0, 1.0).transpose()
+    ));
+}
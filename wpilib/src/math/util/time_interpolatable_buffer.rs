@@ -0,0 +1,68 @@
+use crate::math::units::time::Second;
+
+use super::interpolatable::Interpolatable;
+
+/// A bounded history of timestamped samples that can be queried between samples.
+///
+/// Samples older than the history length (relative to the newest sample) are dropped.
+#[derive(Clone, Debug)]
+pub struct TimeInterpolatableBuffer<T: Interpolatable> {
+    history: Second,
+    samples: Vec<(Second, T)>,
+}
+
+impl<T: Interpolatable> TimeInterpolatableBuffer<T> {
+    #[must_use]
+    pub fn new(history: impl Into<Second>) -> Self {
+        Self {
+            history: history.into(),
+            samples: Vec::new(),
+        }
+    }
+
+    /// Adds a sample, replacing any sample with the same timestamp.
+    pub fn add_sample(&mut self, time: impl Into<Second>, sample: T) {
+        let time = time.into();
+        let index = self.samples.partition_point(|(t, _)| *t < time);
+        match self.samples.get_mut(index) {
+            Some(entry) if entry.0 == time => entry.1 = sample,
+            _ => self.samples.insert(index, (time, sample)),
+        }
+        self.clean_up(time);
+    }
+
+    fn clean_up(&mut self, time: Second) {
+        let cutoff = time - self.history;
+        let expired = self.samples.partition_point(|(t, _)| *t < cutoff);
+        self.samples.drain(..expired);
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    /// Samples the buffer at `time`, clamping to the oldest and newest samples.
+    ///
+    /// Returns `None` if the buffer is empty.
+    #[must_use]
+    pub fn get_sample(&self, time: impl Into<Second>) -> Option<T> {
+        let time = time.into();
+        let index = self.samples.partition_point(|(t, _)| *t < time);
+        if index == 0 {
+            return self.samples.first().map(|(_, sample)| *sample);
+        }
+        if index == self.samples.len() {
+            return self.samples.last().map(|(_, sample)| *sample);
+        }
+        let (lower_time, lower) = &self.samples[index - 1];
+        let (upper_time, upper) = &self.samples[index];
+        let t = (time - *lower_time) / (*upper_time - *lower_time);
+        Some(lower.interpolate(upper, t))
+    }
+
+    /// Returns the samples in the buffer, oldest first.
+    #[must_use]
+    pub fn get_internal_buffer(&self) -> &[(Second, T)] {
+        &self.samples
+    }
+}
@@ -0,0 +1,268 @@
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use futures_util::{SinkExt, StreamExt};
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::HeaderValue;
+use tokio_tungstenite::tungstenite::Message;
+
+use super::instance::{NetworkTableInstance, NetworkTablesError, TopicUpdate};
+use super::protocol::{
+    connection_error, decode_binary, decode_text, encode_binary, encode_text, from_msgpack,
+    into_msgpack, now_micros, spawn_runtime, topic_type, BinaryMessage, ControlMessage,
+    SubscriptionOptions, SUBPROTOCOLS, TIME_SYNC_ID,
+};
+use super::value::NetworkTableValue;
+
+/// The name the client identifies itself to the server with.
+const CLIENT_NAME: &str = "wpilib-rs";
+
+/// How long the client waits to try again after failing to reach the server or losing it.
+const RECONNECT_DELAY: Duration = Duration::from_millis(500);
+
+impl NetworkTableInstance {
+    /// Connects to an NT4 server as a client on a background thread, mirroring every
+    /// topic of this instance to the server and every topic of the server to this instance.
+    ///
+    /// The client keeps trying to reach the server for as long as the program runs, connecting
+    /// again whenever the connection is lost; the instance is only
+    /// [connected](Self::is_connected) while it's open.
+    ///
+    /// # Errors
+    /// Returns an error if the background thread couldn't be started. Failing to reach the
+    /// server, or losing the connection, is logged by the thread instead.
+    pub fn start_client(&self, address: SocketAddr) -> Result<(), NetworkTablesError> {
+        let instance = self.clone();
+        spawn_runtime("NetworkTables client", async move {
+            loop {
+                if let Err(err) = run_client(address, &instance).await {
+                    tracing::debug!("NetworkTables client isn't connected to {address}: {err}");
+                }
+                tokio::time::sleep(RECONNECT_DELAY).await;
+            }
+        })
+    }
+}
+
+/// A topic the server announced.
+struct ServerTopic {
+    name: String,
+    type_string: String,
+}
+
+/// A topic published from here.
+struct PublishedTopic {
+    pubuid: i64,
+    type_string: String,
+    /// The last value sent, so the server sending it back isn't taken for a new one.
+    last_sent: Option<NetworkTableValue>,
+}
+
+/// The state of the connection to the server.
+struct Client<'a> {
+    instance: &'a NetworkTableInstance,
+    link: usize,
+    /// The topics the server announced, by their ids.
+    server_topics: HashMap<i64, ServerTopic>,
+    published: HashMap<String, PublishedTopic>,
+    /// What to add to this program's clock to get the server's.
+    time_offset: i64,
+    /// Messages waiting to be sent to the server.
+    outgoing: Vec<Message>,
+}
+
+/// Runs one connection to the server, until it's lost.
+async fn run_client(
+    address: SocketAddr,
+    instance: &NetworkTableInstance,
+) -> Result<(), NetworkTablesError> {
+    let mut request = format!("ws://{address}/nt/{CLIENT_NAME}")
+        .into_client_request()
+        .map_err(connection_error)?;
+    request.headers_mut().insert(
+        "Sec-WebSocket-Protocol",
+        HeaderValue::from_str(&SUBPROTOCOLS.join(", ")).map_err(connection_error)?,
+    );
+    let (mut socket, _) = tokio_tungstenite::connect_async(request)
+        .await
+        .map_err(connection_error)?;
+
+    // only linked once connected, and unlinked again when this returns, so the instance is only
+    // connected while the socket is; the link starts with every topic, so the server gets them
+    // all again after reconnecting
+    let mut link = instance.add_link();
+    let mut client = Client {
+        instance,
+        link: link.id,
+        server_topics: HashMap::new(),
+        published: HashMap::new(),
+        time_offset: 0,
+        outgoing: Vec::new(),
+    };
+    // every topic of the server, with every value rather than only the latest
+    client
+        .outgoing
+        .push(Message::Text(encode_text(&[ControlMessage::Subscribe {
+            topics: vec![String::new()],
+            subuid: 0,
+            options: SubscriptionOptions {
+                prefix: true,
+                all: true,
+            },
+        }])));
+    // the server answers with its time, which the timestamps of values sent are offset to
+    let mut frame = Vec::new();
+    encode_binary(
+        &mut frame,
+        BinaryMessage {
+            id: TIME_SYNC_ID,
+            timestamp: 0,
+            type_index: topic_type("int"),
+            value: rmpv::Value::from(now_micros()),
+        },
+    );
+    client.outgoing.push(Message::Binary(frame));
+
+    loop {
+        for message in client.outgoing.drain(..) {
+            socket.send(message).await.map_err(connection_error)?;
+        }
+        tokio::select! {
+            message = socket.next() => {
+                let Some(message) = message else {
+                    let closed = String::from("the server closed the connection");
+                    return Err(NetworkTablesError::Connection(closed));
+                };
+                match message.map_err(connection_error)? {
+                    Message::Text(text) => {
+                        for message in decode_text(&text) {
+                            client.handle_control(message);
+                        }
+                    }
+                    Message::Binary(frame) => {
+                        for message in decode_binary(&frame) {
+                            client.handle_value(message);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            update = link.updates.recv() => {
+                // the link is only closed by dropping it, which this still holds
+                let Some(update) = update else {
+                    return Ok(());
+                };
+                client.handle_update(update);
+            }
+        }
+    }
+}
+
+impl Client<'_> {
+    fn handle_control(&mut self, message: ControlMessage) {
+        match message {
+            ControlMessage::Announce {
+                name,
+                id,
+                type_string,
+                ..
+            } => {
+                self.instance.receive(
+                    &TopicUpdate::Announce {
+                        topic: name.clone(),
+                        type_string: type_string.clone(),
+                    },
+                    self.link,
+                );
+                self.server_topics
+                    .insert(id, ServerTopic { name, type_string });
+            }
+            ControlMessage::Unannounce { id, .. } => {
+                self.server_topics.remove(&id);
+            }
+            // only clients send the rest
+            _ => {}
+        }
+    }
+
+    /// Takes in a value from the server, or its answer to the clock synchronization.
+    fn handle_value(&mut self, message: BinaryMessage) {
+        if message.id == TIME_SYNC_ID {
+            // half the round trip has passed since the server read its clock
+            let sent = message.value.as_i64().unwrap_or_default();
+            let now = now_micros();
+            self.time_offset = message.timestamp + (now - sent) / 2 - now;
+            return;
+        }
+        let Some(topic) = self.server_topics.get(&message.id) else {
+            return;
+        };
+        let Some(value) = from_msgpack(message.value, &topic.type_string) else {
+            tracing::warn!("Ignoring a value of the wrong type for {}", topic.name);
+            return;
+        };
+        // the server may send a topic published from here back, which is only the value already
+        // set rather than a new one
+        if let Some(published) = self.published.get_mut(&topic.name) {
+            if published.last_sent.take().is_some_and(|sent| sent == value) {
+                return;
+            }
+        }
+        self.instance.receive(
+            &TopicUpdate::Value {
+                topic: topic.name.clone(),
+                value,
+            },
+            self.link,
+        );
+    }
+
+    /// Passes a change made to the instance on to the server, publishing the topic first if it
+    /// hasn't been from here yet.
+    fn handle_update(&mut self, update: TopicUpdate) {
+        let (topic, type_string, value) = match update {
+            TopicUpdate::Announce { topic, type_string } => (topic, type_string, None),
+            TopicUpdate::Value { topic, value } => {
+                // a topic the server announced first is only published once it's set here
+                let Some(type_string) = self.instance.get_topic_type(&topic) else {
+                    return;
+                };
+                (topic, type_string, Some(value))
+            }
+        };
+        if !self.published.contains_key(&topic) {
+            let pubuid = i64::try_from(self.published.len()).unwrap_or(i64::MAX);
+            // the type string goes out as is, so structs keep their schema's name
+            self.outgoing
+                .push(Message::Text(encode_text(&[ControlMessage::Publish {
+                    name: topic.clone(),
+                    pubuid,
+                    type_string: type_string.clone(),
+                    properties: serde_json::Map::new(),
+                }])));
+            self.published.insert(
+                topic.clone(),
+                PublishedTopic {
+                    pubuid,
+                    type_string,
+                    last_sent: None,
+                },
+            );
+        }
+        if let (Some(value), Some(published)) = (value, self.published.get_mut(&topic)) {
+            published.last_sent = Some(value.clone());
+            let mut frame = Vec::new();
+            encode_binary(
+                &mut frame,
+                BinaryMessage {
+                    id: published.pubuid,
+                    timestamp: now_micros() + self.time_offset,
+                    type_index: topic_type(&published.type_string),
+                    value: into_msgpack(value),
+                },
+            );
+            self.outgoing.push(Message::Binary(frame));
+        }
+    }
+}
@@ -0,0 +1,496 @@
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use thiserror::Error;
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+
+use super::value::{NetworkTableValue, TopicType};
+
+static DEFAULT_INSTANCE: Lazy<NetworkTableInstance> = Lazy::new(NetworkTableInstance::new);
+
+/// The id the next connection is linked to an instance with.
+static NEXT_LINK: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Debug, Error)]
+pub enum NetworkTablesError {
+    #[error("topic {topic:?} was published as {published} but used as {requested}")]
+    TypeMismatch {
+        topic: String,
+        published: String,
+        requested: String,
+    },
+    #[error("failed to connect to the server: {0}")]
+    Connection(String),
+}
+
+/// A change to a topic, as passed between an instance and its connections.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum TopicUpdate {
+    Announce {
+        topic: String,
+        type_string: String,
+    },
+    Value {
+        topic: String,
+        value: NetworkTableValue,
+    },
+}
+
+#[derive(Debug)]
+struct TopicState {
+    type_string: String,
+    value: Option<NetworkTableValue>,
+}
+
+#[derive(Debug, Default)]
+struct Store {
+    topics: HashMap<String, TopicState>,
+    subscribers: Vec<(String, Sender<NetworkTableValue>)>,
+    /// The connections to servers and clients, each of which sends the updates it's passed on
+    /// over its websocket.
+    links: Vec<(usize, UnboundedSender<TopicUpdate>)>,
+}
+
+impl Store {
+    fn announce(&mut self, topic: &str, type_string: &str) -> Result<bool, NetworkTablesError> {
+        match self.topics.get(topic) {
+            Some(state) if state.type_string == type_string => Ok(false),
+            Some(state) => Err(NetworkTablesError::TypeMismatch {
+                topic: topic.to_owned(),
+                published: state.type_string.clone(),
+                requested: type_string.to_owned(),
+            }),
+            None => {
+                self.topics.insert(
+                    topic.to_owned(),
+                    TopicState {
+                        type_string: type_string.to_owned(),
+                        value: None,
+                    },
+                );
+                Ok(true)
+            }
+        }
+    }
+
+    fn set(&mut self, topic: &str, value: &NetworkTableValue) {
+        if let Some(state) = self.topics.get_mut(topic) {
+            state.value = Some(value.clone());
+        }
+        // subscribers that were dropped are forgotten the first time a send fails
+        self.subscribers.retain(|(subscribed, sender)| {
+            subscribed != topic || sender.send(value.clone()).is_ok()
+        });
+    }
+
+    /// Returns every topic as the updates that would recreate it somewhere else.
+    fn snapshot(&self) -> Vec<TopicUpdate> {
+        let mut updates = Vec::new();
+        for (topic, state) in &self.topics {
+            updates.push(TopicUpdate::Announce {
+                topic: topic.clone(),
+                type_string: state.type_string.clone(),
+            });
+            if let Some(value) = &state.value {
+                updates.push(TopicUpdate::Value {
+                    topic: topic.clone(),
+                    value: value.clone(),
+                });
+            }
+        }
+        updates
+    }
+}
+
+/// A set of topics, which can be served to NT4 clients like dashboards with
+/// [`start_server`](Self::start_server) or mirrored with a remote NT4 server with
+/// [`start_client`](Self::start_client).
+///
+/// Cloning an instance gives another handle to the same topics.
+#[derive(Clone, Debug)]
+pub struct NetworkTableInstance {
+    store: Arc<Mutex<Store>>,
+}
+
+impl Default for NetworkTableInstance {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NetworkTableInstance {
+    /// Creates an instance with no topics that isn't linked to anything.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            store: Arc::new(Mutex::new(Store::default())),
+        }
+    }
+
+    /// Returns the instance shared by the whole robot program, which telemetry is published to.
+    ///
+    /// [`run_robot`](crate::robots::run_robot) serves it as an NT4 server on the address set
+    /// with [`set_networktables_address`](crate::robots::set_networktables_address), port 5810
+    /// on every interface by default, like the roboRIO does. Setting no address leaves it
+    /// unserved.
+    #[must_use]
+    pub fn get_default() -> Self {
+        DEFAULT_INSTANCE.clone()
+    }
+
+    #[must_use]
+    pub fn get_table(&self, path: &str) -> NetworkTable {
+        NetworkTable {
+            instance: self.clone(),
+            path: normalize_path(path),
+        }
+    }
+
+    #[must_use]
+    pub fn get_topic<T: TopicType>(&self, name: &str) -> Topic<T> {
+        Topic {
+            instance: self.clone(),
+            name: normalize_path(name),
+            r#type: PhantomData,
+        }
+    }
+
+    /// Returns the names of every topic, sorted.
+    #[must_use]
+    pub fn get_topic_names(&self) -> Vec<String> {
+        let mut names = self.store.lock().topics.keys().cloned().collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    /// Returns the type string a topic was published with, or `None` if it hasn't been.
+    #[must_use]
+    pub fn get_topic_type(&self, name: &str) -> Option<String> {
+        self.store
+            .lock()
+            .topics
+            .get(&normalize_path(name))
+            .map(|state| state.type_string.clone())
+    }
+
+    /// Returns whether the instance is connected to a server, or has a client connected to it.
+    #[must_use]
+    pub fn is_connected(&self) -> bool {
+        !self.store.lock().links.is_empty()
+    }
+
+    /// Links a connection to the instance until the returned link is dropped. The link's updates
+    /// start with every topic that already exists.
+    pub(super) fn add_link(&self) -> Link {
+        let (sender, updates) = tokio::sync::mpsc::unbounded_channel();
+        let id = NEXT_LINK.fetch_add(1, Ordering::Relaxed);
+        let mut store = self.store.lock();
+        for update in store.snapshot() {
+            // the receiver is still in hand, so this can't fail
+            let _ = sender.send(update);
+        }
+        store.links.push((id, sender));
+        drop(store);
+        Link {
+            instance: self.clone(),
+            id,
+            updates,
+        }
+    }
+
+    /// Applies an update that arrived over the link `origin`, passing it on to every other link.
+    pub(super) fn receive(&self, update: &TopicUpdate, origin: usize) {
+        let result = match update {
+            TopicUpdate::Announce { topic, type_string } => {
+                self.store.lock().announce(topic, type_string)
+            }
+            TopicUpdate::Value { topic, value } => {
+                self.store.lock().set(topic, value);
+                Ok(true)
+            }
+        };
+        match result {
+            Ok(true) => self.forward(update, Some(origin)),
+            Ok(false) => {}
+            Err(err) => tracing::warn!("Ignoring an update from a connection: {err}"),
+        }
+    }
+
+    pub(super) fn announce(
+        &self,
+        topic: &str,
+        type_string: &str,
+    ) -> Result<(), NetworkTablesError> {
+        let announced = self.store.lock().announce(topic, type_string)?;
+        if announced {
+            self.forward(
+                &TopicUpdate::Announce {
+                    topic: topic.to_owned(),
+                    type_string: type_string.to_owned(),
+                },
+                None,
+            );
+        }
+        Ok(())
+    }
+
+    pub(super) fn set_value(&self, topic: &str, value: NetworkTableValue) {
+        self.store.lock().set(topic, &value);
+        self.forward(
+            &TopicUpdate::Value {
+                topic: topic.to_owned(),
+                value,
+            },
+            None,
+        );
+    }
+
+    pub(super) fn get_value(&self, topic: &str) -> Option<NetworkTableValue> {
+        self.store
+            .lock()
+            .topics
+            .get(topic)
+            .and_then(|state| state.value.clone())
+    }
+
+    pub(super) fn subscribe(&self, topic: &str) -> Receiver<NetworkTableValue> {
+        let (sender, receiver) = mpsc::channel();
+        self.store
+            .lock()
+            .subscribers
+            .push((topic.to_owned(), sender));
+        receiver
+    }
+
+    /// Sends an update to every link but the one it came from, forgetting links whose
+    /// connection has closed.
+    fn forward(&self, update: &TopicUpdate, origin: Option<usize>) {
+        self.store
+            .lock()
+            .links
+            .retain(|(id, sender)| Some(*id) == origin || sender.send(update.clone()).is_ok());
+    }
+}
+
+/// A connection's link to an instance, which passes it every change made to the instance's topics
+/// for as long as the connection is open.
+#[derive(Debug)]
+pub(super) struct Link {
+    instance: NetworkTableInstance,
+    pub(super) id: usize,
+    pub(super) updates: UnboundedReceiver<TopicUpdate>,
+}
+
+impl Drop for Link {
+    fn drop(&mut self) {
+        self.instance
+            .store
+            .lock()
+            .links
+            .retain(|(id, _)| *id != self.id);
+    }
+}
+
+/// Returns `path` with a leading slash and without a trailing one.
+fn normalize_path(path: &str) -> String {
+    format!("/{}", path.trim_matches('/'))
+}
+
+/// A group of topics under a common path, like `/SmartDashboard`.
+#[derive(Clone, Debug)]
+pub struct NetworkTable {
+    instance: NetworkTableInstance,
+    path: String,
+}
+
+impl NetworkTable {
+    #[must_use]
+    pub fn get_path(&self) -> &str {
+        &self.path
+    }
+
+    #[must_use]
+    pub fn get_sub_table(&self, name: &str) -> Self {
+        self.instance.get_table(&self.child(name))
+    }
+
+    #[must_use]
+    pub fn get_topic<T: TopicType>(&self, name: &str) -> Topic<T> {
+        self.instance.get_topic(&self.child(name))
+    }
+
+    /// Publishes `value` to the topic `name` in this table, creating the topic if needed.
+    ///
+    /// # Errors
+    /// Returns an error if the topic was already published with another type.
+    pub fn put<T: TopicType>(&self, name: &str, value: T) -> Result<(), NetworkTablesError> {
+        self.get_topic(name).publish()?.set(value);
+        Ok(())
+    }
+
+    /// Returns the value of the topic `name` in this table, or `None` if it hasn't been set or
+    /// holds another type.
+    #[must_use]
+    pub fn get<T: TopicType>(&self, name: &str) -> Option<T> {
+        self.instance
+            .get_value(&self.child(name))
+            .and_then(|value| T::from_value(&value))
+    }
+
+    #[must_use]
+    pub fn contains_key(&self, name: &str) -> bool {
+        self.instance.get_topic_type(&self.child(name)).is_some()
+    }
+
+    /// Returns the names of the topics directly in this table, sorted.
+    #[must_use]
+    pub fn get_keys(&self) -> Vec<String> {
+        let prefix = format!("{}/", self.path.trim_end_matches('/'));
+        self.instance
+            .get_topic_names()
+            .into_iter()
+            .filter_map(|topic| {
+                topic
+                    .strip_prefix(&prefix)
+                    .filter(|key| !key.contains('/'))
+                    .map(str::to_owned)
+            })
+            .collect()
+    }
+
+    fn child(&self, name: &str) -> String {
+        format!(
+            "{}/{}",
+            self.path.trim_end_matches('/'),
+            name.trim_matches('/')
+        )
+    }
+}
+
+/// A topic of values of type `T`, which can be published and subscribed to.
+#[derive(Debug)]
+pub struct Topic<T> {
+    instance: NetworkTableInstance,
+    name: String,
+    r#type: PhantomData<T>,
+}
+
+impl<T> Clone for Topic<T> {
+    fn clone(&self) -> Self {
+        Self {
+            instance: self.instance.clone(),
+            name: self.name.clone(),
+            r#type: PhantomData,
+        }
+    }
+}
+
+impl<T: TopicType> Topic<T> {
+    #[must_use]
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns whether the topic has been published, here or by a connection.
+    #[must_use]
+    pub fn exists(&self) -> bool {
+        self.instance.get_topic_type(&self.name).is_some()
+    }
+
+    /// Announces the topic with `T`'s type, along with the schemas of any structs it holds.
+    ///
+    /// # Errors
+    /// Returns an error if the topic was already published with another type.
+    pub fn publish(&self) -> Result<Publisher<T>, NetworkTablesError> {
+        for (type_name, schema) in T::schemas() {
+            let topic = format!("/.schema/struct:{type_name}");
+            self.instance.announce(&topic, "structschema")?;
+            if self.instance.get_value(&topic).is_none() {
+                self.instance
+                    .set_value(&topic, NetworkTableValue::Raw(schema.as_bytes().to_vec()));
+            }
+        }
+        self.instance.announce(&self.name, &T::type_string())?;
+        Ok(Publisher {
+            topic: self.clone(),
+        })
+    }
+
+    /// Subscribes to the topic, whether or not it's been published yet.
+    #[must_use]
+    pub fn subscribe(&self) -> Subscriber<T> {
+        Subscriber {
+            receiver: self.instance.subscribe(&self.name),
+            topic: self.clone(),
+        }
+    }
+}
+
+/// Sets the value of a topic, for this instance and every server and client connected to it.
+#[derive(Debug)]
+pub struct Publisher<T> {
+    topic: Topic<T>,
+}
+
+impl<T> Clone for Publisher<T> {
+    fn clone(&self) -> Self {
+        Self {
+            topic: self.topic.clone(),
+        }
+    }
+}
+
+impl<T: TopicType> Publisher<T> {
+    pub fn set(&self, value: T) {
+        self.topic
+            .instance
+            .set_value(&self.topic.name, value.into_value());
+    }
+
+    #[must_use]
+    pub const fn get_topic(&self) -> &Topic<T> {
+        &self.topic
+    }
+}
+
+/// Reads the value of a topic and queues every value it's set to.
+#[derive(Debug)]
+pub struct Subscriber<T> {
+    topic: Topic<T>,
+    receiver: Receiver<NetworkTableValue>,
+}
+
+impl<T: TopicType> Subscriber<T> {
+    /// Returns the latest value, or `None` if it hasn't been set or holds another type.
+    #[must_use]
+    pub fn get(&self) -> Option<T> {
+        self.topic
+            .instance
+            .get_value(&self.topic.name)
+            .and_then(|value| T::from_value(&value))
+    }
+
+    #[must_use]
+    pub fn get_or(&self, default: T) -> T {
+        self.get().unwrap_or(default)
+    }
+
+    /// Returns every value set since the last call, oldest first, skipping any of another type.
+    #[must_use]
+    pub fn read_queue(&self) -> Vec<T> {
+        self.receiver
+            .try_iter()
+            .filter_map(|value| T::from_value(&value))
+            .collect()
+    }
+
+    #[must_use]
+    pub const fn get_topic(&self) -> &Topic<T> {
+        &self.topic
+    }
+}
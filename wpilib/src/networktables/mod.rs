@@ -0,0 +1,22 @@
+mod client;
+mod instance;
+mod protocol;
+mod server;
+mod structs;
+mod telemetry;
+#[cfg(test)]
+mod test;
+mod value;
+
+pub use instance::NetworkTable;
+pub use instance::NetworkTableInstance;
+pub use instance::NetworkTablesError;
+pub use instance::Publisher;
+pub use instance::Subscriber;
+pub use instance::Topic;
+pub use structs::WpiStruct;
+pub use telemetry::RobotTelemetry;
+#[cfg(feature = "command")]
+pub use telemetry::SchedulerTelemetry;
+pub use value::NetworkTableValue;
+pub use value::TopicType;
@@ -0,0 +1,258 @@
+use std::future::Future;
+use std::time::Instant;
+
+use once_cell::sync::Lazy;
+use rmpv::Value;
+use serde::{Deserialize, Serialize};
+use serde_json::Map;
+
+use super::instance::NetworkTablesError;
+use super::value::NetworkTableValue;
+
+/// The websocket subprotocols of NT 4.1 and 4.0, in order of preference.
+pub(super) const SUBPROTOCOLS: [&str; 2] = [
+    "v4.1.networktables.first.wpi.edu",
+    "networktables.first.wpi.edu",
+];
+
+/// The id binary messages used to synchronize clocks are sent with, instead of a topic's.
+pub(super) const TIME_SYNC_ID: i64 = -1;
+
+static START: Lazy<Instant> = Lazy::new(Instant::now);
+
+/// Returns the microseconds since this program first asked, which is the server's clock.
+#[allow(clippy::cast_possible_truncation)]
+pub(super) fn now_micros() -> i64 {
+    START.elapsed().as_micros() as i64
+}
+
+/// A message of a text frame, which holds a JSON array of them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "method", content = "params", rename_all = "lowercase")]
+pub(super) enum ControlMessage {
+    Publish {
+        name: String,
+        pubuid: i64,
+        #[serde(rename = "type")]
+        type_string: String,
+        #[serde(default)]
+        properties: Map<String, serde_json::Value>,
+    },
+    Unpublish {
+        pubuid: i64,
+    },
+    Subscribe {
+        topics: Vec<String>,
+        subuid: i64,
+        #[serde(default)]
+        options: SubscriptionOptions,
+    },
+    Unsubscribe {
+        subuid: i64,
+    },
+    Announce {
+        name: String,
+        id: i64,
+        #[serde(rename = "type")]
+        type_string: String,
+        /// Set only in the announcement answering the receiver's own publish.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pubuid: Option<i64>,
+        #[serde(default)]
+        properties: Map<String, serde_json::Value>,
+    },
+    Unannounce {
+        name: String,
+        id: i64,
+    },
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(super) struct SubscriptionOptions {
+    /// Whether the topics are prefixes of the names to match rather than whole names.
+    #[serde(default)]
+    pub(super) prefix: bool,
+    /// Whether to send every value rather than only the latest.
+    #[serde(default)]
+    pub(super) all: bool,
+}
+
+/// Writes the text frame for `messages`.
+pub(super) fn encode_text(messages: &[ControlMessage]) -> String {
+    // the messages are plain data, which always serializes
+    serde_json::to_string(messages).unwrap_or_default()
+}
+
+/// Reads the messages of a text frame, skipping any this implementation doesn't handle, like
+/// property changes.
+pub(super) fn decode_text(text: &str) -> Vec<ControlMessage> {
+    let messages = match serde_json::from_str::<Vec<serde_json::Value>>(text) {
+        Ok(messages) => messages,
+        Err(err) => {
+            tracing::warn!("Ignoring a malformed NetworkTables text frame: {err}");
+            return Vec::new();
+        }
+    };
+    messages
+        .into_iter()
+        .filter_map(|message| serde_json::from_value(message).ok())
+        .collect()
+}
+
+/// A message of a binary frame, which holds a run of msgpack arrays of these fields.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct BinaryMessage {
+    /// The topic id from the server, the publisher id from a client, or [`TIME_SYNC_ID`].
+    pub(super) id: i64,
+    pub(super) timestamp: i64,
+    pub(super) type_index: u8,
+    pub(super) value: Value,
+}
+
+/// Appends `message` to a binary frame.
+pub(super) fn encode_binary(frame: &mut Vec<u8>, message: BinaryMessage) {
+    let value = Value::Array(vec![
+        Value::from(message.id),
+        Value::from(message.timestamp),
+        Value::from(message.type_index),
+        message.value,
+    ]);
+    // writing to a vector can't fail
+    let _ = rmpv::encode::write_value(frame, &value);
+}
+
+/// Reads the messages of a binary frame, up to the first malformed one.
+pub(super) fn decode_binary(mut frame: &[u8]) -> Vec<BinaryMessage> {
+    let mut messages = Vec::new();
+    while !frame.is_empty() {
+        let message = match rmpv::decode::read_value(&mut frame) {
+            Ok(Value::Array(mut fields)) if fields.len() == 4 => {
+                let value = fields.pop().unwrap_or(Value::Nil);
+                let type_index = fields[2]
+                    .as_u64()
+                    .and_then(|index| u8::try_from(index).ok());
+                match (fields[0].as_i64(), fields[1].as_i64(), type_index) {
+                    (Some(id), Some(timestamp), Some(type_index)) => Some(BinaryMessage {
+                        id,
+                        timestamp,
+                        type_index,
+                        value,
+                    }),
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+        let Some(message) = message else {
+            tracing::warn!("Ignoring the rest of a malformed NetworkTables binary frame");
+            break;
+        };
+        messages.push(message);
+    }
+    messages
+}
+
+/// Returns the index binary messages give the type of a topic with `type_string`. Structs and
+/// every other type without its own index, like `structschema`, are raw bytes.
+pub(super) fn topic_type(type_string: &str) -> u8 {
+    match type_string {
+        "boolean" => 0,
+        "double" => 1,
+        "int" => 2,
+        "float" => 3,
+        "string" | "json" => 4,
+        "boolean[]" => 16,
+        "double[]" => 17,
+        "int[]" => 18,
+        "float[]" => 19,
+        "string[]" => 20,
+        _ => 5,
+    }
+}
+
+pub(super) fn into_msgpack(value: NetworkTableValue) -> Value {
+    match value {
+        NetworkTableValue::Boolean(value) => Value::from(value),
+        NetworkTableValue::Double(value) => Value::from(value),
+        NetworkTableValue::Int(value) => Value::from(value),
+        NetworkTableValue::String(value) => Value::from(value),
+        NetworkTableValue::Raw(value) => Value::Binary(value),
+        NetworkTableValue::BooleanArray(values) => {
+            Value::Array(values.into_iter().map(Value::from).collect())
+        }
+        NetworkTableValue::DoubleArray(values) => {
+            Value::Array(values.into_iter().map(Value::from).collect())
+        }
+        NetworkTableValue::IntArray(values) => {
+            Value::Array(values.into_iter().map(Value::from).collect())
+        }
+        NetworkTableValue::StringArray(values) => {
+            Value::Array(values.into_iter().map(Value::from).collect())
+        }
+    }
+}
+
+/// Reads a value of a topic announced with `type_string`, or `None` if it doesn't hold that type.
+///
+/// Floats are widened to doubles, as topics only hold doubles here.
+pub(super) fn from_msgpack(value: Value, type_string: &str) -> Option<NetworkTableValue> {
+    fn array<T>(value: Value, element: impl Fn(Value) -> Option<T>) -> Option<Vec<T>> {
+        match value {
+            Value::Array(values) => values.into_iter().map(element).collect(),
+            _ => None,
+        }
+    }
+    let string = |value: Value| match value {
+        Value::String(value) => value.into_str(),
+        _ => None,
+    };
+    Some(match topic_type(type_string) {
+        0 => NetworkTableValue::Boolean(value.as_bool()?),
+        1 | 3 => NetworkTableValue::Double(value.as_f64()?),
+        2 => NetworkTableValue::Int(value.as_i64()?),
+        4 => NetworkTableValue::String(string(value)?),
+        16 => NetworkTableValue::BooleanArray(array(value, |value| value.as_bool())?),
+        17 | 19 => NetworkTableValue::DoubleArray(array(value, |value| value.as_f64())?),
+        18 => NetworkTableValue::IntArray(array(value, |value| value.as_i64())?),
+        20 => NetworkTableValue::StringArray(array(value, string)?),
+        _ => match value {
+            Value::Binary(value) => NetworkTableValue::Raw(value),
+            _ => return None,
+        },
+    })
+}
+
+pub(super) fn connection_error(err: impl std::fmt::Display) -> NetworkTablesError {
+    NetworkTablesError::Connection(err.to_string())
+}
+
+/// Runs `task` on a runtime of its own on a new thread named `name`, logging the error it ends
+/// with, if any.
+///
+/// # Errors
+/// Returns an error if the thread couldn't be started.
+pub(super) fn spawn_runtime<F>(name: &str, task: F) -> Result<(), NetworkTablesError>
+where
+    F: Future<Output = Result<(), NetworkTablesError>> + Send + 'static,
+{
+    let thread_name = name.to_owned();
+    std::thread::Builder::new()
+        .name(thread_name.clone())
+        .spawn(move || {
+            let runtime = match tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+            {
+                Ok(runtime) => runtime,
+                Err(err) => {
+                    tracing::error!("Failed to start the {thread_name} runtime: {err}");
+                    return;
+                }
+            };
+            if let Err(err) = runtime.block_on(task) {
+                tracing::error!("{thread_name} stopped: {err}");
+            }
+        })
+        .map(|_| ())
+        .map_err(connection_error)
+}
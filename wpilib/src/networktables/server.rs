@@ -0,0 +1,314 @@
+use std::collections::HashMap;
+use std::net::SocketAddr;
+
+use futures_util::{SinkExt, StreamExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_tungstenite::tungstenite::handshake::server::{ErrorResponse, Request, Response};
+use tokio_tungstenite::tungstenite::http::HeaderValue;
+use tokio_tungstenite::tungstenite::Message;
+
+use super::instance::{NetworkTableInstance, NetworkTablesError, TopicUpdate};
+use super::protocol::{
+    connection_error, decode_binary, decode_text, encode_binary, encode_text, from_msgpack,
+    into_msgpack, now_micros, spawn_runtime, topic_type, BinaryMessage, ControlMessage,
+    SubscriptionOptions, SUBPROTOCOLS, TIME_SYNC_ID,
+};
+use super::value::NetworkTableValue;
+
+impl NetworkTableInstance {
+    /// Serves this instance's topics to NT4 clients, like dashboards, on a background thread,
+    /// taking in whatever they publish.
+    ///
+    /// Returns the address the server listens on, which has the port the system picked if
+    /// `address`'s was 0.
+    ///
+    /// # Errors
+    /// Returns an error if `address` couldn't be bound or the background thread couldn't be
+    /// started. Clients failing to connect are logged by the thread instead.
+    pub fn start_server(&self, address: SocketAddr) -> Result<SocketAddr, NetworkTablesError> {
+        let listener = std::net::TcpListener::bind(address).map_err(connection_error)?;
+        listener.set_nonblocking(true).map_err(connection_error)?;
+        let address = listener.local_addr().map_err(connection_error)?;
+        let instance = self.clone();
+        spawn_runtime("NetworkTables server", async move {
+            let listener = TcpListener::from_std(listener).map_err(connection_error)?;
+            loop {
+                let (stream, client) = listener.accept().await.map_err(connection_error)?;
+                let instance = instance.clone();
+                tokio::spawn(async move {
+                    if let Err(err) = serve_client(stream, &instance).await {
+                        tracing::info!("NetworkTables client {client} disconnected: {err}");
+                    }
+                });
+            }
+        })?;
+        Ok(address)
+    }
+}
+
+/// Picks the newest subprotocol the client offers, which NT4 clients refuse to go without.
+#[allow(clippy::unnecessary_wraps, clippy::result_large_err)]
+fn negotiate(request: &Request, mut response: Response) -> Result<Response, ErrorResponse> {
+    let offered = request
+        .headers()
+        .get_all("Sec-WebSocket-Protocol")
+        .iter()
+        .filter_map(|header| header.to_str().ok())
+        .flat_map(|header| header.split(',').map(str::trim))
+        .collect::<Vec<_>>();
+    if let Some(protocol) = SUBPROTOCOLS
+        .iter()
+        .find(|protocol| offered.contains(protocol))
+    {
+        response
+            .headers_mut()
+            .insert("Sec-WebSocket-Protocol", HeaderValue::from_static(protocol));
+    }
+    Ok(response)
+}
+
+/// A topic of the instance, as this connection knows it.
+struct ClientTopic {
+    id: i64,
+    type_string: String,
+    value: Option<NetworkTableValue>,
+    /// Whether the client has been told about the topic, which it only is once subscribed.
+    announced: bool,
+}
+
+/// The state of one client's connection, which mirrors the instance's topics to announce to
+/// the client once it subscribes to them.
+struct Connection<'a> {
+    instance: &'a NetworkTableInstance,
+    link: usize,
+    topics: HashMap<String, ClientTopic>,
+    /// The topic names the client publishes to, by its publisher ids.
+    publishers: HashMap<i64, String>,
+    subscriptions: HashMap<i64, (Vec<String>, SubscriptionOptions)>,
+    /// Messages waiting to be sent to the client.
+    outgoing: Vec<Message>,
+}
+
+async fn serve_client(
+    stream: TcpStream,
+    instance: &NetworkTableInstance,
+) -> Result<(), NetworkTablesError> {
+    let mut socket = tokio_tungstenite::accept_hdr_async(stream, negotiate)
+        .await
+        .map_err(connection_error)?;
+    let mut link = instance.add_link();
+    let mut connection = Connection {
+        instance,
+        link: link.id,
+        topics: HashMap::new(),
+        publishers: HashMap::new(),
+        subscriptions: HashMap::new(),
+        outgoing: Vec::new(),
+    };
+
+    loop {
+        tokio::select! {
+            message = socket.next() => {
+                let Some(message) = message else {
+                    return Ok(());
+                };
+                match message.map_err(connection_error)? {
+                    Message::Text(text) => {
+                        for message in decode_text(&text) {
+                            connection.handle_control(message);
+                        }
+                    }
+                    Message::Binary(frame) => {
+                        for message in decode_binary(&frame) {
+                            connection.handle_value(message);
+                        }
+                    }
+                    Message::Close(_) => return Ok(()),
+                    _ => {}
+                }
+            }
+            update = link.updates.recv() => {
+                let Some(update) = update else {
+                    return Ok(());
+                };
+                connection.handle_update(update);
+            }
+        }
+        for message in connection.outgoing.drain(..) {
+            socket.send(message).await.map_err(connection_error)?;
+        }
+    }
+}
+
+impl Connection<'_> {
+    fn is_subscribed(&self, name: &str) -> bool {
+        self.subscriptions.values().any(|(topics, options)| {
+            topics.iter().any(|topic| {
+                if options.prefix {
+                    name.starts_with(topic.as_str())
+                } else {
+                    name == topic
+                }
+            })
+        })
+    }
+
+    /// Returns the topic, adding it with the next id if the connection didn't know it yet.
+    fn topic(&mut self, name: &str, type_string: &str) -> &mut ClientTopic {
+        let id = i64::try_from(self.topics.len()).unwrap_or(i64::MAX);
+        self.topics
+            .entry(name.to_owned())
+            .or_insert_with(|| ClientTopic {
+                id,
+                type_string: type_string.to_owned(),
+                value: None,
+                announced: false,
+            })
+    }
+
+    fn announce(&mut self, name: &str, pubuid: Option<i64>) {
+        let Some(topic) = self.topics.get_mut(name) else {
+            return;
+        };
+        topic.announced = true;
+        self.outgoing
+            .push(Message::Text(encode_text(&[ControlMessage::Announce {
+                name: name.to_owned(),
+                id: topic.id,
+                type_string: topic.type_string.clone(),
+                pubuid,
+                properties: serde_json::Map::new(),
+            }])));
+    }
+
+    fn send_value(&mut self, name: &str) {
+        if !self.is_subscribed(name) {
+            return;
+        }
+        let Some(ClientTopic {
+            id,
+            type_string,
+            value: Some(value),
+            announced: true,
+        }) = self.topics.get(name)
+        else {
+            return;
+        };
+        let mut frame = Vec::new();
+        encode_binary(
+            &mut frame,
+            BinaryMessage {
+                id: *id,
+                timestamp: now_micros(),
+                type_index: topic_type(type_string),
+                value: into_msgpack(value.clone()),
+            },
+        );
+        self.outgoing.push(Message::Binary(frame));
+    }
+
+    /// Passes a change made to the instance on to the client, if it's subscribed to the topic.
+    fn handle_update(&mut self, update: TopicUpdate) {
+        match update {
+            TopicUpdate::Announce { topic, type_string } => {
+                self.topic(&topic, &type_string);
+                if self.is_subscribed(&topic) {
+                    self.announce(&topic, None);
+                }
+            }
+            TopicUpdate::Value { topic, value } => {
+                if let Some(state) = self.topics.get_mut(&topic) {
+                    state.value = Some(value);
+                    self.send_value(&topic);
+                }
+            }
+        }
+    }
+
+    fn handle_control(&mut self, message: ControlMessage) {
+        match message {
+            ControlMessage::Publish {
+                name,
+                pubuid,
+                type_string,
+                ..
+            } => {
+                self.instance.receive(
+                    &TopicUpdate::Announce {
+                        topic: name.clone(),
+                        type_string: type_string.clone(),
+                    },
+                    self.link,
+                );
+                // a topic that already had another type keeps it, which the client is told
+                let type_string = self.instance.get_topic_type(&name).unwrap_or(type_string);
+                self.topic(&name, &type_string);
+                self.publishers.insert(pubuid, name.clone());
+                self.announce(&name, Some(pubuid));
+                self.send_value(&name);
+            }
+            ControlMessage::Unpublish { pubuid } => {
+                self.publishers.remove(&pubuid);
+            }
+            ControlMessage::Subscribe {
+                topics,
+                subuid,
+                options,
+            } => {
+                self.subscriptions.insert(subuid, (topics, options));
+                let newly_subscribed = self
+                    .topics
+                    .iter()
+                    .filter(|(name, topic)| !topic.announced && self.is_subscribed(name))
+                    .map(|(name, _)| name.clone())
+                    .collect::<Vec<_>>();
+                for name in newly_subscribed {
+                    self.announce(&name, None);
+                    self.send_value(&name);
+                }
+            }
+            ControlMessage::Unsubscribe { subuid } => {
+                self.subscriptions.remove(&subuid);
+            }
+            // only servers send these
+            ControlMessage::Announce { .. } | ControlMessage::Unannounce { .. } => {}
+        }
+    }
+
+    /// Takes in a value the client published, or answers its clock synchronization.
+    fn handle_value(&mut self, message: BinaryMessage) {
+        if message.id == TIME_SYNC_ID {
+            let mut frame = Vec::new();
+            encode_binary(
+                &mut frame,
+                BinaryMessage {
+                    timestamp: now_micros(),
+                    ..message
+                },
+            );
+            self.outgoing.push(Message::Binary(frame));
+            return;
+        }
+        let Some(name) = self.publishers.get(&message.id) else {
+            return;
+        };
+        let Some(topic) = self.topics.get_mut(name) else {
+            return;
+        };
+        let value = Some(message.type_index)
+            .filter(|index| *index == topic_type(&topic.type_string))
+            .and_then(|_| from_msgpack(message.value, &topic.type_string));
+        let Some(value) = value else {
+            tracing::warn!("Ignoring a value of the wrong type for {name}");
+            return;
+        };
+        topic.value = Some(value.clone());
+        self.instance.receive(
+            &TopicUpdate::Value {
+                topic: name.clone(),
+                value,
+            },
+            self.link,
+        );
+    }
+}
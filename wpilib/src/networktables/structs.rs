@@ -0,0 +1,89 @@
+use crate::math::geometry::{Pose2d, Rotation2d, Translation2d};
+use crate::math::units::angle::Radian;
+use crate::math::units::distance::Meter;
+
+/// A type packed into a fixed size little endian buffer by the struct format dashboards read, so
+/// they can unpack it from the schema published alongside it.
+pub trait WpiStruct: Sized {
+    /// The name the schema is published under, `Pose2d` for `struct:Pose2d`.
+    const TYPE_NAME: &'static str;
+    /// The fields in order, like `double x;double y`.
+    const SCHEMA: &'static str;
+    /// The packed size in bytes.
+    const SIZE: usize;
+
+    fn pack(&self, buffer: &mut Vec<u8>);
+
+    /// Unpacks a buffer of exactly [`SIZE`](Self::SIZE) bytes.
+    fn unpack(buffer: &[u8]) -> Self;
+
+    /// Returns the type names and schemas of this struct and every struct nested in it, nested
+    /// ones first.
+    #[must_use]
+    fn schemas() -> Vec<(&'static str, &'static str)> {
+        vec![(Self::TYPE_NAME, Self::SCHEMA)]
+    }
+}
+
+fn read_f64(buffer: &[u8], offset: usize) -> f64 {
+    let mut bytes = [0; 8];
+    bytes.copy_from_slice(&buffer[offset..offset + 8]);
+    f64::from_le_bytes(bytes)
+}
+
+impl WpiStruct for Translation2d {
+    const TYPE_NAME: &'static str = "Translation2d";
+    const SCHEMA: &'static str = "double x;double y";
+    const SIZE: usize = 16;
+
+    fn pack(&self, buffer: &mut Vec<u8>) {
+        buffer.extend_from_slice(&self.x.value().to_le_bytes());
+        buffer.extend_from_slice(&self.y.value().to_le_bytes());
+    }
+
+    fn unpack(buffer: &[u8]) -> Self {
+        Self::new(
+            Meter::new(read_f64(buffer, 0)),
+            Meter::new(read_f64(buffer, 8)),
+        )
+    }
+}
+
+impl WpiStruct for Rotation2d {
+    const TYPE_NAME: &'static str = "Rotation2d";
+    const SCHEMA: &'static str = "double value";
+    const SIZE: usize = 8;
+
+    fn pack(&self, buffer: &mut Vec<u8>) {
+        buffer.extend_from_slice(&self.value.value().to_le_bytes());
+    }
+
+    fn unpack(buffer: &[u8]) -> Self {
+        Self::new(Radian::new(read_f64(buffer, 0)))
+    }
+}
+
+impl WpiStruct for Pose2d {
+    const TYPE_NAME: &'static str = "Pose2d";
+    const SCHEMA: &'static str = "Translation2d translation;Rotation2d rotation";
+    const SIZE: usize = Translation2d::SIZE + Rotation2d::SIZE;
+
+    fn pack(&self, buffer: &mut Vec<u8>) {
+        self.translation.pack(buffer);
+        self.rotation.pack(buffer);
+    }
+
+    fn unpack(buffer: &[u8]) -> Self {
+        Self::new(
+            Translation2d::unpack(&buffer[..Translation2d::SIZE]),
+            Rotation2d::unpack(&buffer[Translation2d::SIZE..]),
+        )
+    }
+
+    fn schemas() -> Vec<(&'static str, &'static str)> {
+        let mut schemas = Translation2d::schemas();
+        schemas.extend(Rotation2d::schemas());
+        schemas.push((Self::TYPE_NAME, Self::SCHEMA));
+        schemas
+    }
+}
@@ -0,0 +1,73 @@
+#[cfg(feature = "command")]
+use crate::command::CommandManager;
+use crate::math::units::energy::Volt;
+use crate::robots::{get_alliance, RobotMode};
+
+#[cfg(feature = "command")]
+use super::instance::NetworkTable;
+use super::instance::{NetworkTableInstance, NetworkTablesError, Publisher};
+
+/// Publishes the robot's mode, alliance and power state under `/Robot`.
+#[derive(Debug)]
+pub struct RobotTelemetry {
+    mode: Publisher<String>,
+    enabled: Publisher<bool>,
+    alliance: Publisher<String>,
+    battery_voltage: Publisher<f64>,
+    browned_out: Publisher<bool>,
+}
+
+impl RobotTelemetry {
+    /// # Errors
+    /// Returns an error if one of the topics was already published with another type.
+    pub fn new(instance: &NetworkTableInstance) -> Result<Self, NetworkTablesError> {
+        let table = instance.get_table("/Robot");
+        Ok(Self {
+            mode: table.get_topic("Mode").publish()?,
+            enabled: table.get_topic("Enabled").publish()?,
+            alliance: table.get_topic("Alliance").publish()?,
+            battery_voltage: table.get_topic("BatteryVoltage").publish()?,
+            browned_out: table.get_topic("BrownedOut").publish()?,
+        })
+    }
+
+    pub fn update(&self, mode: RobotMode) {
+        self.mode.set(format!("{mode:?}"));
+        self.enabled.set(!mode.is_disabled());
+        self.alliance
+            .set(get_alliance().map_or_else(String::new, |alliance| format!("{alliance:?}")));
+    }
+
+    /// Publishes the battery's state, which is only known in simulation.
+    pub fn update_battery(&self, voltage: Volt, browned_out: bool) {
+        self.battery_voltage.set(voltage.value());
+        self.browned_out.set(browned_out);
+    }
+}
+
+/// Publishes the names of the running commands under `/Scheduler`, and each subsystem's command
+/// and telemetry under `/Scheduler/Subsystems/<name>`.
+#[cfg(feature = "command")]
+#[derive(Debug)]
+pub struct SchedulerTelemetry {
+    names: Publisher<Vec<String>>,
+    subsystems: NetworkTable,
+}
+
+#[cfg(feature = "command")]
+impl SchedulerTelemetry {
+    /// # Errors
+    /// Returns an error if the names topic was already published with another type.
+    pub fn new(instance: &NetworkTableInstance) -> Result<Self, NetworkTablesError> {
+        let table = instance.get_table("/Scheduler");
+        Ok(Self {
+            names: table.get_topic("Names").publish()?,
+            subsystems: table.get_sub_table("Subsystems"),
+        })
+    }
+
+    pub fn update(&self) {
+        self.names.set(CommandManager::get_running_names());
+        CommandManager::publish_subsystems(&self.subsystems);
+    }
+}
@@ -0,0 +1,658 @@
+use std::fmt::Debug;
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::time::{Duration, Instant};
+
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::handshake::server::{ErrorResponse, Request, Response};
+use tokio_tungstenite::tungstenite::http::HeaderValue;
+use tokio_tungstenite::tungstenite::{Message, WebSocket};
+
+use crate::math::geometry::{Pose2d, Rotation2d, Translation2d};
+use crate::math::units::angle::Degree;
+use crate::math::units::distance::Meter;
+use crate::math::units::energy::Volt;
+use crate::robots::RobotMode;
+
+use super::protocol::{
+    decode_binary, decode_text, encode_binary, encode_text, from_msgpack, BinaryMessage,
+    ControlMessage, SubscriptionOptions,
+};
+#[cfg(feature = "command")]
+use super::SchedulerTelemetry;
+use super::{
+    NetworkTableInstance, NetworkTableValue, NetworkTablesError, RobotTelemetry, TopicType,
+};
+
+/// Starts a server on a free port of 127.0.0.1, returning it with the address it listens on.
+fn server() -> (NetworkTableInstance, SocketAddr) {
+    let server = NetworkTableInstance::new();
+    let address = server
+        .start_server(SocketAddr::from((Ipv4Addr::LOCALHOST, 0)))
+        .expect("binds");
+    (server, address)
+}
+
+fn client(address: SocketAddr) -> NetworkTableInstance {
+    let client = NetworkTableInstance::new();
+    client.start_client(address).expect("starts");
+    client
+}
+
+/// Answers a websocket handshake as an NT 4.1 server.
+#[allow(clippy::unnecessary_wraps, clippy::result_large_err)]
+fn nt4_handshake(_: &Request, mut response: Response) -> Result<Response, ErrorResponse> {
+    response.headers_mut().insert(
+        "Sec-WebSocket-Protocol",
+        HeaderValue::from_static("v4.1.networktables.first.wpi.edu"),
+    );
+    Ok(response)
+}
+
+/// Accepts the next client on `listener` as a bare NT 4.1 websocket, in place of a server from
+/// this module.
+fn accept(listener: &TcpListener) -> WebSocket<TcpStream> {
+    let (stream, _) = listener.accept().expect("accepts");
+    tokio_tungstenite::tungstenite::accept_hdr(stream, nt4_handshake).expect("handshakes")
+}
+
+/// Connects to `address` as a bare NT 4.1 websocket, in place of a client from this module.
+fn connect(address: SocketAddr) -> WebSocket<TcpStream> {
+    let mut request = format!("ws://{address}/nt/raw")
+        .into_client_request()
+        .expect("is a request");
+    request.headers_mut().insert(
+        "Sec-WebSocket-Protocol",
+        HeaderValue::from_static("v4.1.networktables.first.wpi.edu"),
+    );
+    let stream = TcpStream::connect(address).expect("connects");
+    let (socket, _) = tokio_tungstenite::tungstenite::client(request, stream).expect("handshakes");
+    socket
+}
+
+/// Reads messages from `socket` until `find` picks something out of one.
+fn read_until<T>(
+    socket: &mut WebSocket<TcpStream>,
+    mut find: impl FnMut(Message) -> Option<T>,
+) -> T {
+    socket
+        .get_ref()
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .expect("sets the timeout");
+    loop {
+        if let Some(found) = find(socket.read().expect("reads before the timeout")) {
+            return found;
+        }
+    }
+}
+
+/// Returns the JSON messages of a text frame, or none for another kind of frame.
+fn json_messages(message: &Message) -> Vec<serde_json::Value> {
+    match message {
+        Message::Text(text) => serde_json::from_str(text).expect("is a JSON array"),
+        _ => Vec::new(),
+    }
+}
+
+/// Returns the fields of each msgpack array in a binary frame, or none for another kind of frame.
+fn msgpack_messages(message: &Message) -> Vec<Vec<rmpv::Value>> {
+    let Message::Binary(frame) = message else {
+        return Vec::new();
+    };
+    let mut frame = frame.as_slice();
+    let mut messages = Vec::new();
+    while !frame.is_empty() {
+        match rmpv::decode::read_value(&mut frame).expect("is msgpack") {
+            rmpv::Value::Array(fields) => messages.push(fields),
+            value => panic!("{value} isn't an array"),
+        }
+    }
+    messages
+}
+
+/// Waits for `condition` to hold, as updates cross the connection on other threads.
+fn wait_until(condition: impl Fn() -> bool) {
+    let start = Instant::now();
+    while !condition() {
+        assert!(
+            start.elapsed() < Duration::from_secs(5),
+            "timed out waiting on the connection"
+        );
+        std::thread::sleep(Duration::from_millis(5));
+    }
+}
+
+/// Publishes `value` to `name` on `from` and checks it arrives on `to` with the same type.
+fn round_trip<T: TopicType + Clone + PartialEq + Debug>(
+    from: &NetworkTableInstance,
+    to: &NetworkTableInstance,
+    name: &str,
+    value: T,
+) {
+    from.get_topic::<T>(name)
+        .publish()
+        .expect("publishes")
+        .set(value.clone());
+    let subscriber = to.get_topic::<T>(name).subscribe();
+    wait_until(|| subscriber.get().is_some());
+    assert_eq!(subscriber.get(), Some(value));
+    assert_eq!(to.get_topic_type(name), Some(T::type_string()));
+}
+
+#[test]
+fn typed_topics() {
+    let (server, address) = server();
+    let client = client(address);
+    wait_until(|| server.is_connected() && client.is_connected());
+
+    // every type both ways, as each end decodes by the type it was announced with
+    for (from, to, prefix) in [
+        (&client, &server, "/from_client"),
+        (&server, &client, "/from_server"),
+    ] {
+        let name = |topic: &str| format!("{prefix}/{topic}");
+        round_trip(from, to, &name("boolean"), true);
+        round_trip(from, to, &name("double"), 2.5);
+        round_trip(from, to, &name("int"), -4_i64);
+        round_trip(from, to, &name("string"), String::from("hello"));
+        round_trip(from, to, &name("raw"), vec![1_u8, 2, 3]);
+        round_trip(from, to, &name("booleans"), vec![true, false]);
+        round_trip(from, to, &name("doubles"), vec![0.5, 1.5]);
+        round_trip(from, to, &name("ints"), vec![7_i64, -8]);
+        round_trip(
+            from,
+            to,
+            &name("strings"),
+            vec![String::from("a"), String::from("b")],
+        );
+        round_trip(from, to, &name("empty"), Vec::<i64>::new());
+    }
+
+    // every value is queued on the other end, in order
+    let publisher = client
+        .get_topic::<f64>("/queued")
+        .publish()
+        .expect("publishes");
+    let subscriber = server.get_topic::<f64>("/queued").subscribe();
+    assert_eq!(subscriber.get(), None);
+    assert!((subscriber.get_or(1.5) - 1.5).abs() < f64::EPSILON);
+    publisher.set(2.0);
+    publisher.set(3.0);
+    wait_until(|| subscriber.get() == Some(3.0));
+    assert_eq!(subscriber.read_queue(), vec![2.0, 3.0]);
+    assert!(subscriber.read_queue().is_empty());
+    // reading a topic as another type gives nothing rather than a wrong value
+    assert_eq!(server.get_table("/from_client").get::<f64>("boolean"), None);
+}
+
+#[test]
+fn client_reconnects() {
+    // a free port with nothing listening on it yet
+    let address = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+        .and_then(|listener| listener.local_addr())
+        .expect("binds");
+    let client = client(address);
+    client.get_table("/").put("early", 1.0).expect("puts");
+    std::thread::sleep(Duration::from_millis(100));
+    assert!(!client.is_connected());
+
+    // the client keeps trying until the server comes up, then sends what it published meanwhile
+    let server = NetworkTableInstance::new();
+    server.start_server(address).expect("binds");
+    let table = server.get_table("/");
+    wait_until(|| client.is_connected() && table.get::<f64>("early") == Some(1.0));
+}
+
+#[test]
+fn connection_state() {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).expect("binds");
+    let client = client(listener.local_addr().expect("has an address"));
+    let mut socket = accept(&listener);
+    wait_until(|| client.is_connected());
+
+    // the client is disconnected as soon as the server closes the connection
+    socket.close(None).expect("closes");
+    drop(socket);
+    wait_until(|| !client.is_connected());
+
+    // and connects again on its own
+    let _socket = accept(&listener);
+    wait_until(|| client.is_connected());
+
+    // and a server is only connected while a client is
+    let (server, address) = server();
+    let mut socket = connect(address);
+    wait_until(|| server.is_connected());
+    socket.close(None).expect("closes");
+    drop(socket);
+    wait_until(|| !server.is_connected());
+}
+
+// frames written out by hand from the NT 4.1 spec, as no other NT4 implementation is at hand to
+// test against
+
+#[test]
+fn spec_frames() {
+    // a publish as clients send it, then a property change, which is skipped
+    let messages = decode_text(
+        r#"[{"method":"publish","params":{"name":"/x","pubuid":3,"type":"double","properties":{"persistent":true}}},
+            {"method":"setproperties","params":{"name":"/x","update":{"persistent":false}}}]"#,
+    );
+    assert_eq!(
+        messages,
+        vec![ControlMessage::Publish {
+            name: String::from("/x"),
+            pubuid: 3,
+            type_string: String::from("double"),
+            properties: serde_json::json!({ "persistent": true })
+                .as_object()
+                .cloned()
+                .expect("is an object"),
+        }]
+    );
+    // a subscription without options gets the defaults
+    assert_eq!(
+        decode_text(r#"[{"method":"subscribe","params":{"topics":["/x"],"subuid":1}}]"#),
+        vec![ControlMessage::Subscribe {
+            topics: vec![String::from("/x")],
+            subuid: 1,
+            options: SubscriptionOptions::default(),
+        }]
+    );
+    let text = encode_text(&[ControlMessage::Announce {
+        name: String::from("/x"),
+        id: 7,
+        type_string: String::from("double"),
+        pubuid: None,
+        properties: serde_json::Map::new(),
+    }]);
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&text).expect("is JSON"),
+        serde_json::json!([{
+            "method": "announce",
+            "params": { "name": "/x", "id": 7, "type": "double", "properties": {} },
+        }])
+    );
+
+    // [7, 1000, 1, 2.5]: a fixarray of a positive fixint, a uint16, a positive fixint and a
+    // float64
+    let mut frame = Vec::new();
+    encode_binary(
+        &mut frame,
+        BinaryMessage {
+            id: 7,
+            timestamp: 1000,
+            type_index: 1,
+            value: rmpv::Value::from(2.5),
+        },
+    );
+    let mut expected = vec![0x94, 0x07, 0xcd, 0x03, 0xe8, 0x01, 0xcb];
+    expected.extend(2.5_f64.to_be_bytes());
+    assert_eq!(frame, expected);
+
+    // a time sync [-1, 0, 2, 12345] then a float [2, 0, 3, 1.5] in one frame, the float as a
+    // float32 like other implementations send it
+    let mut frame = vec![0x94, 0xff, 0x00, 0x02, 0xcd, 0x30, 0x39];
+    frame.extend([0x94, 0x02, 0x00, 0x03, 0xca]);
+    frame.extend(1.5_f32.to_be_bytes());
+    let messages = decode_binary(&frame);
+    assert_eq!(messages.len(), 2);
+    assert_eq!(
+        (
+            messages[0].id,
+            messages[0].timestamp,
+            messages[0].type_index
+        ),
+        (-1, 0, 2)
+    );
+    assert_eq!(messages[0].value.as_i64(), Some(12345));
+    assert_eq!(
+        from_msgpack(messages[1].value.clone(), "float"),
+        Some(NetworkTableValue::Double(1.5))
+    );
+}
+
+#[test]
+fn raw_client() {
+    let (server, address) = server();
+    server.get_table("/").put("served", 1.5).expect("puts");
+    let mut socket = connect(address);
+    socket
+        .send(Message::Text(String::from(
+            r#"[{"method":"subscribe","params":{"topics":["/served"],"subuid":1,"options":{}}},
+                {"method":"publish","params":{"name":"/sent","pubuid":2,"type":"int","properties":{}}}]"#,
+        )))
+        .expect("sends");
+
+    // the subscribed topic is announced then sent, and the published one is announced with the
+    // publisher's id
+    let mut served_id = None;
+    let mut served_value = None;
+    let mut sent_announced = false;
+    read_until(&mut socket, |message| {
+        for message in json_messages(&message) {
+            assert_eq!(message["method"], "announce");
+            let params = &message["params"];
+            if params["name"] == "/served" {
+                assert_eq!(params["type"], "double");
+                served_id = params["id"].as_i64();
+            } else {
+                assert_eq!(params["name"], "/sent");
+                assert_eq!(params["pubuid"], 2);
+                sent_announced = true;
+            }
+        }
+        for fields in msgpack_messages(&message) {
+            assert_eq!(fields[0].as_i64(), served_id);
+            assert_eq!(fields[2].as_u64(), Some(1));
+            served_value = fields[3].as_f64();
+        }
+        (served_value.is_some() && sent_announced).then_some(())
+    });
+    assert_eq!(served_value, Some(1.5));
+
+    // [2, 0, 2, 42] sets the published int
+    socket
+        .send(Message::Binary(vec![0x94, 0x02, 0x00, 0x02, 0x2a]))
+        .expect("sends");
+    let table = server.get_table("/");
+    wait_until(|| table.get::<i64>("sent") == Some(42));
+
+    // [-1, 0, 2, 12345] is answered with the server's time and the client's sent back
+    socket
+        .send(Message::Binary(vec![
+            0x94, 0xff, 0x00, 0x02, 0xcd, 0x30, 0x39,
+        ]))
+        .expect("sends");
+    let fields = read_until(&mut socket, |message| {
+        msgpack_messages(&message)
+            .into_iter()
+            .find(|fields| fields[0].as_i64() == Some(-1))
+    });
+    assert!(fields[1].as_i64().is_some_and(|time| time >= 0));
+    assert_eq!(fields[3].as_i64(), Some(12345));
+}
+
+#[test]
+fn raw_server() {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).expect("binds");
+    let client = client(listener.local_addr().expect("has an address"));
+    client.get_table("/").put("sent", true).expect("puts");
+    let mut socket = accept(&listener);
+
+    // the client subscribes to every topic, syncs its clock, then publishes its topic and sets it
+    let mut subscribed = false;
+    let mut synced = false;
+    let mut pubuid = None;
+    read_until(&mut socket, |message| {
+        for message in json_messages(&message) {
+            let params = &message["params"];
+            match message["method"].as_str() {
+                Some("subscribe") => {
+                    assert_eq!(params["topics"], serde_json::json!([""]));
+                    assert_eq!(params["options"]["prefix"], true);
+                    subscribed = true;
+                }
+                Some("publish") => {
+                    assert_eq!(params["name"], "/sent");
+                    assert_eq!(params["type"], "boolean");
+                    pubuid = params["pubuid"].as_i64();
+                }
+                method => panic!("unexpected {method:?}"),
+            }
+        }
+        let mut set = false;
+        for fields in msgpack_messages(&message) {
+            if fields[0].as_i64() == Some(-1) {
+                assert_eq!(fields[2].as_u64(), Some(2));
+                synced = true;
+            } else {
+                assert_eq!(fields[0].as_i64(), pubuid);
+                assert_eq!(
+                    (fields[2].as_u64(), fields[3].as_bool()),
+                    (Some(0), Some(true))
+                );
+                set = true;
+            }
+        }
+        (subscribed && synced && set).then_some(())
+    });
+
+    // a topic announced by the server takes the values sent to its id, here [5, 0, 17, [0.5, 1.5]]
+    socket
+        .send(Message::Text(String::from(
+            r#"[{"method":"announce","params":{"name":"/served","id":5,"type":"double[]","properties":{}}}]"#,
+        )))
+        .expect("sends");
+    let mut frame = vec![0x94, 0x05, 0x00, 0x11, 0x92, 0xcb];
+    frame.extend(0.5_f64.to_be_bytes());
+    frame.push(0xcb);
+    frame.extend(1.5_f64.to_be_bytes());
+    socket.send(Message::Binary(frame)).expect("sends");
+    let table = client.get_table("/");
+    wait_until(|| table.get::<Vec<f64>>("served") == Some(vec![0.5, 1.5]));
+    assert_eq!(
+        client.get_topic_type("/served").as_deref(),
+        Some("double[]")
+    );
+}
+
+#[test]
+fn values_flow_both_ways() {
+    let (server, address) = server();
+    server
+        .get_table("/SmartDashboard")
+        .put("before", 1.0)
+        .expect("puts");
+    // topics published before connecting are sent to the new client
+    let client = client(address);
+    let table = client.get_table("/SmartDashboard");
+    wait_until(|| table.get::<f64>("before") == Some(1.0));
+
+    // and a second client sees what the first publishes through the server
+    let other = self::client(address);
+    let subscriber = other.get_topic::<String>("/from_client").subscribe();
+    client
+        .get_topic::<String>("/from_client")
+        .publish()
+        .expect("publishes")
+        .set(String::from("hi"));
+    wait_until(|| subscriber.get().is_some());
+    assert_eq!(subscriber.read_queue(), vec![String::from("hi")]);
+
+    server
+        .get_table("/SmartDashboard")
+        .put("before", 2.0)
+        .expect("puts");
+    let table = other.get_table("SmartDashboard");
+    wait_until(|| table.get::<f64>("before") == Some(2.0));
+}
+
+#[test]
+fn server_updates_published_topics() {
+    let (server, address) = server();
+    let client = client(address);
+    let publisher = client
+        .get_topic::<f64>("/shared")
+        .publish()
+        .expect("publishes");
+    publisher.set(1.0);
+    let table = server.get_table("/");
+    wait_until(|| table.get::<f64>("shared") == Some(1.0));
+
+    // a topic published from the client still takes values set elsewhere
+    table.put("shared", 2.0).expect("puts");
+    let subscriber = client.get_topic::<f64>("/shared").subscribe();
+    wait_until(|| subscriber.get() == Some(2.0));
+
+    // including one the client set before
+    publisher.set(3.0);
+    wait_until(|| table.get::<f64>("shared") == Some(3.0));
+    table.put("shared", 1.0).expect("puts");
+    wait_until(|| subscriber.get() == Some(1.0));
+    // and the client's own values still reach the server
+    publisher.set(4.0);
+    wait_until(|| table.get::<f64>("shared") == Some(4.0));
+}
+
+#[test]
+fn type_mismatch() {
+    let (server, address) = server();
+    server.get_table("/").put("value", 1.0).expect("puts");
+    let client = client(address);
+    wait_until(|| client.get_table("/").get::<f64>("value") == Some(1.0));
+    let err = client
+        .get_topic::<bool>("/value")
+        .publish()
+        .expect_err("types differ");
+    assert!(matches!(
+        err,
+        NetworkTablesError::TypeMismatch { ref published, ref requested, .. }
+            if published == "double" && requested == "boolean"
+    ));
+    assert_eq!(client.get_table("/").get::<f64>("value"), Some(1.0));
+}
+
+#[test]
+fn struct_topics() {
+    let (server, address) = server();
+    let client = client(address);
+    let pose = Pose2d::new(
+        Translation2d::new(Meter::new(1.5), Meter::new(-2.0)),
+        Rotation2d::new(Degree::new(90.0)),
+    );
+    client
+        .get_topic::<Pose2d>("/pose")
+        .publish()
+        .expect("publishes")
+        .set(pose);
+
+    let subscriber = server.get_topic::<Pose2d>("/pose").subscribe();
+    wait_until(|| subscriber.get().is_some());
+    // the type goes over the wire as is rather than as raw bytes
+    assert_eq!(
+        server.get_topic_type("/pose").as_deref(),
+        Some("struct:Pose2d")
+    );
+    let received = subscriber.get().expect("has a value");
+    assert_eq!(received.translation, pose.translation);
+    assert!((received.rotation.value.value() - pose.rotation.value.value()).abs() < 1e-12);
+    // the raw bytes are two doubles then one more, little endian
+    let raw = server
+        .get_table("/")
+        .get::<Vec<u8>>("pose")
+        .expect("is raw");
+    assert_eq!(raw.len(), 24);
+    assert_eq!(raw[..8], 1.5_f64.to_le_bytes());
+
+    // schemas for the struct and the ones nested in it are published too
+    for (name, schema) in [
+        ("Pose2d", "Translation2d translation;Rotation2d rotation"),
+        ("Translation2d", "double x;double y"),
+        ("Rotation2d", "double value"),
+    ] {
+        let topic = format!("/.schema/struct:{name}");
+        wait_until(|| server.get_topic_type(&topic).is_some());
+        assert_eq!(
+            server.get_topic_type(&topic).as_deref(),
+            Some("structschema")
+        );
+        assert_eq!(
+            server
+                .get_table("/.schema")
+                .get::<Vec<u8>>(&format!("struct:{name}")),
+            Some(schema.as_bytes().to_vec())
+        );
+    }
+
+    // another client gets the struct type too, so it can publish the topic itself
+    let other = self::client(address);
+    wait_until(|| other.get_topic_type("/pose").is_some());
+    assert_eq!(
+        other.get_topic_type("/pose").as_deref(),
+        Some("struct:Pose2d")
+    );
+    let moved = Pose2d::new(
+        Translation2d::new(Meter::new(3.0), Meter::new(0.0)),
+        Rotation2d::new(Degree::new(0.0)),
+    );
+    other
+        .get_topic::<Pose2d>("/pose")
+        .publish()
+        .expect("types match")
+        .set(moved);
+    wait_until(|| {
+        subscriber
+            .get()
+            .is_some_and(|pose| pose.translation == moved.translation)
+    });
+}
+
+#[test]
+fn tables() {
+    let instance = NetworkTableInstance::new();
+    let table = instance.get_table("/Shooter/");
+    assert_eq!(table.get_path(), "/Shooter");
+    table.put("Speed", 4000.0).expect("puts");
+    table.put("Ready", false).expect("puts");
+    let wheel = table.get_sub_table("Wheel");
+    assert_eq!(wheel.get_path(), "/Shooter/Wheel");
+    wheel.put("Current", 30.0).expect("puts");
+
+    assert!(table.contains_key("Speed"));
+    assert!(!table.contains_key("Current"));
+    assert_eq!(
+        table.get_keys(),
+        vec![String::from("Ready"), String::from("Speed")]
+    );
+    assert_eq!(
+        instance.get_topic_names(),
+        vec![
+            String::from("/Shooter/Ready"),
+            String::from("/Shooter/Speed"),
+            String::from("/Shooter/Wheel/Current"),
+        ]
+    );
+    assert_eq!(
+        instance
+            .get_topic::<f64>("Shooter/Wheel/Current")
+            .get_name(),
+        "/Shooter/Wheel/Current"
+    );
+}
+
+#[test]
+fn robot_telemetry() {
+    let (server, address) = server();
+    let client = client(address);
+    let telemetry = RobotTelemetry::new(&client).expect("publishes");
+    telemetry.update(RobotMode::Teleop);
+    telemetry.update_battery(Volt::new(12.0), false);
+
+    let table = server.get_table("/Robot");
+    wait_until(|| table.get::<bool>("BrownedOut").is_some());
+    assert_eq!(table.get::<String>("Mode").as_deref(), Some("Teleop"));
+    assert_eq!(table.get::<bool>("Enabled"), Some(true));
+    assert!(table.get::<f64>("BatteryVoltage").is_some());
+    assert_eq!(
+        server.get_topic_type("/Robot/Alliance").as_deref(),
+        Some("string")
+    );
+
+    telemetry.update(RobotMode::Disabled);
+    wait_until(|| table.get::<bool>("Enabled") == Some(false));
+}
+
+#[cfg(feature = "command")]
+#[test]
+fn scheduler_telemetry() {
+    let (server, address) = server();
+    let client = client(address);
+    let telemetry = SchedulerTelemetry::new(&client).expect("publishes");
+    telemetry.update();
+    let table = server.get_table("/Scheduler");
+    wait_until(|| table.get::<Vec<String>>("Names").is_some());
+    assert_eq!(
+        server.get_topic_type("/Scheduler/Names").as_deref(),
+        Some("string[]")
+    );
+}
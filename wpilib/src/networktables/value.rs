@@ -0,0 +1,89 @@
+use super::structs::WpiStruct;
+
+/// A value held by a topic, one variant for each data type of the protocol.
+#[derive(Clone, Debug, PartialEq)]
+pub enum NetworkTableValue {
+    Boolean(bool),
+    Double(f64),
+    Int(i64),
+    String(String),
+    Raw(Vec<u8>),
+    BooleanArray(Vec<bool>),
+    DoubleArray(Vec<f64>),
+    IntArray(Vec<i64>),
+    StringArray(Vec<String>),
+}
+
+/// A Rust type that can be published to and read from a topic.
+///
+/// The type string is what the topic is announced with, such as `double` or `struct:Pose2d`, and
+/// a topic only accepts values of the type it was first published with.
+pub trait TopicType: Sized {
+    fn type_string() -> String;
+
+    fn into_value(self) -> NetworkTableValue;
+
+    /// Reads a value back, or `None` if it holds a different type.
+    fn from_value(value: &NetworkTableValue) -> Option<Self>;
+
+    /// Returns the struct schemas that have to be published for the value to be read.
+    #[must_use]
+    fn schemas() -> Vec<(&'static str, &'static str)> {
+        Vec::new()
+    }
+}
+
+macro_rules! topic_type {
+    ($type:ty, $type_string:literal, $variant:ident) => {
+        impl TopicType for $type {
+            fn type_string() -> String {
+                String::from($type_string)
+            }
+
+            fn into_value(self) -> NetworkTableValue {
+                NetworkTableValue::$variant(self)
+            }
+
+            fn from_value(value: &NetworkTableValue) -> Option<Self> {
+                match value {
+                    NetworkTableValue::$variant(value) => Some(value.clone()),
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+topic_type!(bool, "boolean", Boolean);
+topic_type!(f64, "double", Double);
+topic_type!(i64, "int", Int);
+topic_type!(String, "string", String);
+topic_type!(Vec<u8>, "raw", Raw);
+topic_type!(Vec<bool>, "boolean[]", BooleanArray);
+topic_type!(Vec<f64>, "double[]", DoubleArray);
+topic_type!(Vec<i64>, "int[]", IntArray);
+topic_type!(Vec<String>, "string[]", StringArray);
+
+// structs travel as raw bytes under a type string naming their schema
+impl<T: WpiStruct> TopicType for T {
+    fn type_string() -> String {
+        format!("struct:{}", T::TYPE_NAME)
+    }
+
+    fn into_value(self) -> NetworkTableValue {
+        let mut buffer = Vec::with_capacity(T::SIZE);
+        self.pack(&mut buffer);
+        NetworkTableValue::Raw(buffer)
+    }
+
+    fn from_value(value: &NetworkTableValue) -> Option<Self> {
+        match value {
+            NetworkTableValue::Raw(buffer) if buffer.len() == T::SIZE => Some(T::unpack(buffer)),
+            _ => None,
+        }
+    }
+
+    fn schemas() -> Vec<(&'static str, &'static str)> {
+        <T as WpiStruct>::schemas()
+    }
+}
@@ -1,18 +1,56 @@
 use std::{
     fmt::Debug,
+    net::{Ipv4Addr, SocketAddr, SocketAddrV4},
     time::{Duration, Instant},
 };
 
 use parking_lot::Mutex;
 
+#[cfg(feature = "simulation")]
+use crate::math::simulation::BatterySim;
+#[cfg(feature = "command")]
+use crate::networktables::SchedulerTelemetry;
+use crate::networktables::{NetworkTableInstance, RobotTelemetry};
 use crate::{command::CommandManager, if_not_athena, if_sim};
 
 static PERIODIC_TIME: Mutex<f64> = Mutex::new(0.02);
+static ALLIANCE: Mutex<Option<Alliance>> = Mutex::new(None);
+static NETWORKTABLES_ADDRESS: Mutex<Option<SocketAddr>> = Mutex::new(Some(SocketAddr::V4(
+    SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, 5810),
+)));
 
 pub fn set_periodic_time(time: f64) {
     *PERIODIC_TIME.lock() = time;
 }
 
+/// Sets the alliance reported by the driver station, or `None` if it isn't known yet.
+///
+/// Nothing reads the alliance from the driver station yet, so until this is called, such as by
+/// the robot program once its alliance is known or by a simulation, the alliance is `None`.
+pub fn set_alliance(alliance: Option<Alliance>) {
+    *ALLIANCE.lock() = alliance;
+}
+
+/// Returns the alliance reported by the driver station, or `None` if it isn't known yet.
+#[must_use]
+pub fn get_alliance() -> Option<Alliance> {
+    *ALLIANCE.lock()
+}
+
+/// Sets the address the robot serves its network tables on once started, port 5810 on every
+/// interface by default.
+///
+/// `None` doesn't serve them, such as when running several robots in one process or connecting
+/// the default instance to another server as a client.
+pub fn set_networktables_address(address: Option<SocketAddr>) {
+    *NETWORKTABLES_ADDRESS.lock() = address;
+}
+
+#[must_use]
+pub fn get_networktables_address() -> Option<SocketAddr> {
+    *NETWORKTABLES_ADDRESS.lock()
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum RobotMode {
     Disabled = 0,
@@ -39,12 +77,33 @@ impl RobotMode {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Alliance {
+    Red,
+    Blue,
+}
+impl Alliance {
+    #[must_use]
+    pub const fn is_red(&self) -> bool {
+        matches!(self, Self::Red)
+    }
+    #[must_use]
+    pub const fn is_blue(&self) -> bool {
+        matches!(self, Self::Blue)
+    }
+}
+
 pub trait RobotCore {
     fn start(&mut self);
 
     fn end(&mut self);
 
     fn get_mode(&self) -> RobotMode;
+
+    /// Returns the alliance last [set](set_alliance), or `None` if it isn't known yet.
+    fn get_alliance(&self) -> Option<Alliance> {
+        get_alliance()
+    }
 }
 
 pub trait UserRobot: Send + Sync {
@@ -76,6 +135,14 @@ pub trait UserRobot: Send + Sync {
     //sim
     fn sim_init(&mut self) {}
     fn sim_periodic(&mut self) {}
+    //the simulated battery, whose voltage is published and whose brownouts are reported
+    #[cfg(feature = "simulation")]
+    fn sim_battery(&self) -> Option<&BatterySim> {
+        None
+    }
+
+    //brownout, called once each time the simulated battery drops below its brownout voltage
+    fn robot_brownout(&mut self) {}
 }
 
 pub struct RobotCoreImpl {
@@ -94,7 +161,23 @@ impl RobotCore for RobotCoreImpl {
             self.user_robot.sim_init();
         }
 
+        let instance = NetworkTableInstance::get_default();
+        if let Some(address) = get_networktables_address() {
+            if let Err(err) = instance.start_server(address) {
+                tracing::warn!("NetworkTables isn't being served on {address}: {err}");
+            }
+        }
+        let robot_telemetry = RobotTelemetry::new(&instance)
+            .map_err(|err| tracing::warn!("Robot telemetry is disabled: {err}"))
+            .ok();
+        #[cfg(feature = "command")]
+        let scheduler_telemetry = SchedulerTelemetry::new(&instance)
+            .map_err(|err| tracing::warn!("Scheduler telemetry is disabled: {err}"))
+            .ok();
+
         let mut last_mode = self.get_mode();
+        #[cfg(feature = "simulation")]
+        let mut was_browned_out = false;
         let mut start;
 
         loop {
@@ -154,10 +237,18 @@ impl RobotCore for RobotCoreImpl {
             #[cfg(feature = "command")]
             {
                 CommandManager::run();
+                if let Some(telemetry) = &scheduler_telemetry {
+                    telemetry.update();
+                }
             }
 
-            if_sim! {
-                self.user_robot.sim_periodic();
+            if let Some(telemetry) = &robot_telemetry {
+                telemetry.update(mode);
+            }
+
+            #[cfg(feature = "simulation")]
+            {
+                was_browned_out = self.sim_periodic(robot_telemetry.as_ref(), was_browned_out);
             }
 
             //todo, make this more reliable
@@ -173,12 +264,41 @@ impl RobotCore for RobotCoreImpl {
         RobotMode::Disabled
     }
 }
+impl RobotCoreImpl {
+    /// Steps the simulation, publishing the simulated battery and reporting when it browns out.
+    /// Returns whether the battery is browned out now.
+    #[cfg(feature = "simulation")]
+    fn sim_periodic(&mut self, telemetry: Option<&RobotTelemetry>, was_browned_out: bool) -> bool {
+        self.user_robot.sim_periodic();
+
+        let battery = self
+            .user_robot
+            .sim_battery()
+            .map(|battery| (battery.get_voltage(), battery.is_browned_out()));
+        let Some((voltage, browned_out)) = battery else {
+            return was_browned_out;
+        };
+        if let Some(telemetry) = telemetry {
+            telemetry.update_battery(voltage, browned_out);
+        }
+        if browned_out && !was_browned_out {
+            tracing::warn!("Simulated battery browned out, disabling simulated mechanisms");
+            self.user_robot.robot_brownout();
+        }
+        browned_out
+    }
+}
 impl Debug for RobotCoreImpl {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("RobotCoreImpl").finish()
     }
 }
 
+/// Runs the robot until it exits.
+///
+/// The default [`NetworkTableInstance`](NetworkTableInstance::get_default) is served to dashboards
+/// as an NT4 server on the [address set for them](set_networktables_address), unless that's been
+/// set to `None`.
 #[no_panic::no_panic]
 pub fn run_robot(user_robot: Box<dyn UserRobot>) {
     let mut robot = RobotCoreImpl { user_robot };
@@ -0,0 +1,143 @@
+use nalgebra::{DMatrix, DVector};
+
+use crate::math::controllers::feed_forward::{Arm, Elevator, Simple, VelocityUnit};
+use crate::math::units::angular_velocity::RadianPerSecond;
+
+use super::log::{SysIdLog, SysIdRecord};
+
+/// How gravity loads the mechanism being fitted.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Gravity {
+    None,
+    Constant,
+    Cosine,
+}
+
+/// Fits feedforward gains to a [`SysIdLog`] by ordinary least squares.
+///
+/// Each pair of consecutive records within a test is one sample of the discretized mechanism
+/// `x_k+1 = αx_k + βu_k + γsgn(x_k) + δg_k`, where `x` is the velocity, `u` the voltage and `g`
+/// is one for an elevator or the cosine of the angle for an arm. The gains follow as
+/// `k_v = (1 − α)/β`, `k_a = (α − 1)T/(β ln α)`, `k_s = −γ/β` and `k_g = −δ/β`, with `T` the
+/// loop period.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SysIdAnalyzer<'a, Velocity: VelocityUnit = RadianPerSecond> {
+    log: &'a SysIdLog<Velocity>,
+    motion_threshold: f64,
+}
+
+impl<'a, Velocity: VelocityUnit> SysIdAnalyzer<'a, Velocity> {
+    #[must_use]
+    pub const fn new(log: &'a SysIdLog<Velocity>) -> Self {
+        Self {
+            log,
+            motion_threshold: 0.0,
+        }
+    }
+
+    /// Ignores samples whose velocity magnitude is at or below `motion_threshold`, such as
+    /// while the mechanism is still held by static friction at the start of a quasistatic test.
+    #[must_use]
+    pub const fn with_motion_threshold(mut self, motion_threshold: f64) -> Self {
+        self.motion_threshold = motion_threshold;
+        self
+    }
+
+    /// Fits a mechanism with no gravity load, such as a flywheel or drivetrain.
+    ///
+    /// # Panics
+    /// Panics if the log has too few samples or doesn't describe a mechanism that slows down on
+    /// its own.
+    #[must_use]
+    pub fn simple(&self) -> Simple<Velocity> {
+        let (k_s, _, k_v, k_a) = self.fit(Gravity::None);
+        Simple::new(k_s, k_v, k_a)
+    }
+
+    /// Fits a mechanism under a constant gravity load, such as an elevator.
+    ///
+    /// # Panics
+    /// Panics if the log has too few samples or doesn't describe a mechanism that slows down on
+    /// its own.
+    #[must_use]
+    pub fn elevator(&self) -> Elevator<Velocity> {
+        let (k_s, k_g, k_v, k_a) = self.fit(Gravity::Constant);
+        Elevator::new(k_s, k_g, k_v, k_a)
+    }
+
+    /// Returns `k_s`, `k_g`, `k_v` and `k_a`.
+    fn fit(&self, gravity: Gravity) -> (f64, f64, f64, f64) {
+        // consecutive records from the same run of a test; a test restarting resets its time
+        let samples: Vec<(&SysIdRecord<Velocity>, &SysIdRecord<Velocity>)> = self
+            .log
+            .get_records()
+            .windows(2)
+            .map(|pair| (&pair[0], &pair[1]))
+            .filter(|(current, next)| {
+                current.state == next.state
+                    && next.time > current.time
+                    && Into::<f64>::into(current.velocity).abs() > self.motion_threshold
+            })
+            .collect();
+
+        let columns = if gravity == Gravity::None { 3 } else { 4 };
+        assert!(
+            samples.len() >= columns,
+            "not enough samples to fit the feedforward"
+        );
+
+        let mut x = DMatrix::zeros(samples.len(), columns);
+        let mut y = DVector::zeros(samples.len());
+        let mut total_time = 0.0;
+        for (row, (current, next)) in samples.iter().enumerate() {
+            let velocity: f64 = current.velocity.into();
+            x[(row, 0)] = velocity;
+            x[(row, 1)] = current.voltage.value();
+            x[(row, 2)] = num::signum(velocity);
+            match gravity {
+                Gravity::None => {}
+                Gravity::Constant => x[(row, 3)] = 1.0,
+                Gravity::Cosine => x[(row, 3)] = Into::<f64>::into(current.position).cos(),
+            }
+            y[row] = next.velocity.into();
+            total_time += (next.time - current.time).value();
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let period = total_time / samples.len() as f64;
+
+        let coefficients = x
+            .svd(true, true)
+            .solve(&y, 1e-12)
+            .expect("SVD was computed with U and Vᵀ");
+        let (alpha, beta, gamma) = (coefficients[0], coefficients[1], coefficients[2]);
+        let delta = if gravity == Gravity::None {
+            0.0
+        } else {
+            coefficients[3]
+        };
+        assert!(
+            alpha > 0.0 && alpha < 1.0,
+            "the log doesn't describe a mechanism that slows down on its own"
+        );
+
+        (
+            -gamma / beta,
+            -delta / beta,
+            (1.0 - alpha) / beta,
+            (alpha - 1.0) * period / (beta * alpha.ln()),
+        )
+    }
+}
+
+impl SysIdAnalyzer<'_, RadianPerSecond> {
+    /// Fits an arm, whose logged position must be its angle from horizontal.
+    ///
+    /// # Panics
+    /// Panics if the log has too few samples or doesn't describe a mechanism that slows down on
+    /// its own.
+    #[must_use]
+    pub fn arm(&self) -> Arm {
+        let (k_s, k_g, k_v, k_a) = self.fit(Gravity::Cosine);
+        Arm::new(k_s, k_g, k_v, k_a)
+    }
+}
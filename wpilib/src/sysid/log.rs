@@ -0,0 +1,96 @@
+use serde::{Deserialize, Serialize};
+
+use crate::math::controllers::feed_forward::VelocityUnit;
+use crate::math::units::angular_velocity::RadianPerSecond;
+use crate::math::units::energy::Volt;
+use crate::math::units::time::Second;
+
+/// Which way a test drives the mechanism.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SysIdDirection {
+    Forward,
+    Reverse,
+}
+
+/// The test a [`SysIdRecord`] was taken during.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SysIdState {
+    QuasistaticForward,
+    QuasistaticReverse,
+    DynamicForward,
+    DynamicReverse,
+}
+
+impl SysIdState {
+    #[must_use]
+    pub const fn quasistatic(direction: SysIdDirection) -> Self {
+        match direction {
+            SysIdDirection::Forward => Self::QuasistaticForward,
+            SysIdDirection::Reverse => Self::QuasistaticReverse,
+        }
+    }
+
+    #[must_use]
+    pub const fn dynamic(direction: SysIdDirection) -> Self {
+        match direction {
+            SysIdDirection::Forward => Self::DynamicForward,
+            SysIdDirection::Reverse => Self::DynamicReverse,
+        }
+    }
+}
+
+/// One loop of a test: the voltage applied for the loop and the state measured at its start.
+///
+/// Position and velocity are in the units the gains are identified in, such as meters for an
+/// elevator or radians for an arm.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SysIdRecord<Velocity: VelocityUnit = RadianPerSecond> {
+    pub state: SysIdState,
+    /// Time since the start of the test.
+    pub time: Second,
+    pub voltage: Volt,
+    pub position: Velocity::Position,
+    pub velocity: Velocity,
+}
+
+/// The records of any number of tests, in the order they were taken.
+///
+/// The log is analyzed on the robot by [`SysIdAnalyzer`](super::SysIdAnalyzer), so it's kept in
+/// memory rather than written to a data log for the desktop characterization tool. It can be
+/// serialized to analyze it elsewhere.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "SysIdRecord<Velocity>: Serialize",
+    deserialize = "SysIdRecord<Velocity>: Deserialize<'de>"
+))]
+pub struct SysIdLog<Velocity: VelocityUnit = RadianPerSecond> {
+    records: Vec<SysIdRecord<Velocity>>,
+}
+
+impl<Velocity: VelocityUnit> SysIdLog<Velocity> {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            records: Vec::new(),
+        }
+    }
+
+    pub fn record(&mut self, record: SysIdRecord<Velocity>) {
+        self.records.push(record);
+    }
+
+    #[must_use]
+    pub fn get_records(&self) -> &[SysIdRecord<Velocity>] {
+        &self.records
+    }
+
+    pub fn clear(&mut self) {
+        self.records.clear();
+    }
+}
+
+impl<Velocity: VelocityUnit> Default for SysIdLog<Velocity> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
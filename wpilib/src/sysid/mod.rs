@@ -0,0 +1,11 @@
+mod analysis;
+mod log;
+#[cfg(feature = "command")]
+mod routine;
+#[cfg(all(test, feature = "command"))]
+mod test;
+
+pub use analysis::*;
+pub use log::*;
+#[cfg(feature = "command")]
+pub use routine::*;
@@ -0,0 +1,191 @@
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+
+use crate::command::commands::CommandTrait;
+use crate::command::Command;
+use crate::math::controllers::feed_forward::VelocityUnit;
+use crate::math::units::angular_velocity::RadianPerSecond;
+use crate::math::units::energy::Volt;
+use crate::math::units::time::Second;
+
+use super::log::{SysIdDirection, SysIdLog, SysIdRecord, SysIdState};
+
+type DriveFn = Box<dyn FnMut(Volt) + Send>;
+type MeasureFn<T> = Box<dyn FnMut() -> T + Send>;
+
+/// How hard and how long a [`SysIdRoutine`] drives the mechanism.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SysIdRoutineConfig {
+    /// How quickly the quasistatic tests ramp the voltage, in volts per second.
+    pub ramp_rate: f64,
+    /// The voltage the dynamic tests apply.
+    pub step_voltage: Volt,
+    /// How long each test runs.
+    pub timeout: Second,
+    /// How often the tests' commands run; time in a test is counted in these periods.
+    pub period: Second,
+}
+
+impl Default for SysIdRoutineConfig {
+    fn default() -> Self {
+        Self {
+            ramp_rate: 1.0,
+            step_voltage: Volt::new(7.0),
+            timeout: Second::new(10.0),
+            period: Second::new(0.02),
+        }
+    }
+}
+
+struct SysIdMechanism<Velocity: VelocityUnit> {
+    drive: DriveFn,
+    position: MeasureFn<Velocity::Position>,
+    velocity: MeasureFn<Velocity>,
+}
+
+/// Generates the tests that characterize a mechanism, recording each loop to a [`SysIdLog`].
+///
+/// The quasistatic tests ramp the voltage slowly so acceleration is negligible, isolating
+/// `k_s`, `k_g` and `k_v`; the dynamic tests apply a voltage step to excite `k_a`. Run each
+/// test in both directions, then fit the log with [`SysIdAnalyzer`](super::SysIdAnalyzer).
+pub struct SysIdRoutine<Velocity: VelocityUnit = RadianPerSecond> {
+    config: SysIdRoutineConfig,
+    mechanism: Arc<Mutex<SysIdMechanism<Velocity>>>,
+    log: Arc<Mutex<SysIdLog<Velocity>>>,
+    requirements: Vec<u8>,
+}
+
+impl<Velocity: VelocityUnit + Send + 'static> SysIdRoutine<Velocity>
+where
+    Velocity::Position: Send,
+{
+    /// `drive` applies a voltage to the mechanism, and `position` and `velocity` measure it in
+    /// the units the gains should be in. An arm's position must be its angle from horizontal.
+    #[must_use]
+    pub fn new(
+        config: SysIdRoutineConfig,
+        drive: impl FnMut(Volt) + Send + 'static,
+        position: impl FnMut() -> Velocity::Position + Send + 'static,
+        velocity: impl FnMut() -> Velocity + Send + 'static,
+        requirements: Vec<u8>,
+    ) -> Self {
+        Self {
+            config,
+            mechanism: Arc::new(Mutex::new(SysIdMechanism {
+                drive: Box::new(drive),
+                position: Box::new(position),
+                velocity: Box::new(velocity),
+            })),
+            log: Arc::new(Mutex::new(SysIdLog::new())),
+            requirements,
+        }
+    }
+
+    #[must_use]
+    pub const fn get_config(&self) -> SysIdRoutineConfig {
+        self.config
+    }
+
+    /// Returns a command that ramps the voltage at the configured rate until the timeout.
+    #[must_use]
+    pub fn quasistatic(&self, direction: SysIdDirection) -> Command {
+        self.test(SysIdState::quasistatic(direction))
+    }
+
+    /// Returns a command that holds the step voltage until the timeout.
+    #[must_use]
+    pub fn dynamic(&self, direction: SysIdDirection) -> Command {
+        self.test(SysIdState::dynamic(direction))
+    }
+
+    /// Returns a copy of everything recorded so far.
+    #[must_use]
+    pub fn get_log(&self) -> SysIdLog<Velocity> {
+        self.log.lock().clone()
+    }
+
+    pub fn clear_log(&self) {
+        self.log.lock().clear();
+    }
+
+    fn test(&self, state: SysIdState) -> Command {
+        Command::custom(Box::new(SysIdCommand {
+            config: self.config,
+            state,
+            mechanism: self.mechanism.clone(),
+            log: self.log.clone(),
+            requirements: self.requirements.clone(),
+            loops: 0,
+        }))
+        .with_name(&format!("SysId({state:?})"))
+    }
+}
+
+impl<Velocity: VelocityUnit> std::fmt::Debug for SysIdRoutine<Velocity> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SysIdRoutine")
+            .field("config", &self.config)
+            .field("requirements", &self.requirements)
+            .finish_non_exhaustive()
+    }
+}
+
+struct SysIdCommand<Velocity: VelocityUnit> {
+    config: SysIdRoutineConfig,
+    state: SysIdState,
+    mechanism: Arc<Mutex<SysIdMechanism<Velocity>>>,
+    log: Arc<Mutex<SysIdLog<Velocity>>>,
+    requirements: Vec<u8>,
+    loops: u32,
+}
+
+impl<Velocity: VelocityUnit> SysIdCommand<Velocity> {
+    fn elapsed(&self) -> Second {
+        Second::new(self.config.period.value() * f64::from(self.loops))
+    }
+}
+
+impl<Velocity: VelocityUnit> CommandTrait for SysIdCommand<Velocity> {
+    fn init(&mut self) {
+        self.loops = 0;
+    }
+
+    fn periodic(&mut self) {
+        let time = self.elapsed();
+        let voltage = match self.state {
+            SysIdState::QuasistaticForward => self.config.ramp_rate * time.value(),
+            SysIdState::QuasistaticReverse => -self.config.ramp_rate * time.value(),
+            SysIdState::DynamicForward => self.config.step_voltage.value(),
+            SysIdState::DynamicReverse => -self.config.step_voltage.value(),
+        };
+        let voltage = Volt::new(voltage);
+
+        let mut mechanism = self.mechanism.lock();
+        let position = (mechanism.position)();
+        let velocity = (mechanism.velocity)();
+        (mechanism.drive)(voltage);
+        drop(mechanism);
+
+        self.log.lock().record(SysIdRecord {
+            state: self.state,
+            time,
+            voltage,
+            position,
+            velocity,
+        });
+        self.loops += 1;
+    }
+
+    fn end(&mut self, _interrupted: bool) {
+        (self.mechanism.lock().drive)(Volt::new(0.0));
+    }
+
+    fn is_finished(&mut self) -> bool {
+        self.elapsed() >= self.config.timeout
+    }
+
+    fn get_requirements(&self) -> Vec<u8> {
+        self.requirements.clone()
+    }
+}
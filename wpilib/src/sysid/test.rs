@@ -0,0 +1,237 @@
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+
+use crate::command::commands::CommandTrait;
+use crate::command::Command;
+use crate::math::controllers::feed_forward::{Arm, Elevator, Simple, VelocityUnit};
+#[cfg(feature = "simulation")]
+use crate::math::simulation::{BatterySim, FlywheelSim};
+use crate::math::system::Discretization;
+#[cfg(feature = "simulation")]
+use crate::math::system::{DCMotor, LinearSystemId};
+#[cfg(feature = "simulation")]
+use crate::math::units::angular_velocity::RadianPerSecond;
+use crate::math::units::energy::Volt;
+use crate::math::units::linear_velocity::MeterPerSecond;
+use crate::sysid::{
+    SysIdAnalyzer, SysIdDirection, SysIdLog, SysIdRecord, SysIdRoutine, SysIdRoutineConfig,
+    SysIdState,
+};
+use approx::assert_relative_eq;
+use nalgebra::Vector1;
+
+/// A velocity system with friction and gravity, stepped exactly with the voltage and gravity
+/// load held for each period.
+struct MechanismSim {
+    k_s: f64,
+    k_g: f64,
+    k_v: f64,
+    k_a: f64,
+    cosine: bool,
+    voltage: f64,
+    position: f64,
+    velocity: f64,
+}
+
+impl MechanismSim {
+    const fn new(k_s: f64, k_g: f64, k_v: f64, k_a: f64, cosine: bool) -> Self {
+        Self {
+            k_s,
+            k_g,
+            k_v,
+            k_a,
+            cosine,
+            voltage: 0.0,
+            position: 0.0,
+            velocity: 0.0,
+        }
+    }
+
+    fn update(&mut self, dt: f64) {
+        let gravity = if self.cosine {
+            self.position.cos()
+        } else {
+            1.0
+        };
+        let input = self.k_g.mul_add(
+            -gravity,
+            self.k_s.mul_add(-num::signum(self.velocity), self.voltage),
+        );
+        let (a, b) = Discretization::discretize_ab(
+            &Vector1::new(-self.k_v / self.k_a),
+            &Vector1::new(1.0 / self.k_a),
+            dt,
+        );
+        let next_velocity = a[0].mul_add(self.velocity, b[0] * input);
+        self.position += (self.velocity + next_velocity) / 2.0 * dt;
+        self.velocity = next_velocity;
+    }
+}
+
+fn run(command: &mut Command, mut update: impl FnMut()) {
+    command.init();
+    loop {
+        command.periodic();
+        update();
+        if command.is_finished() {
+            command.end(false);
+            break;
+        }
+    }
+}
+
+fn run_all<Velocity: VelocityUnit + Send + 'static>(
+    routine: &SysIdRoutine<Velocity>,
+    mut update: impl FnMut(),
+) where
+    Velocity::Position: Send,
+{
+    for direction in [SysIdDirection::Forward, SysIdDirection::Reverse] {
+        run(&mut routine.quasistatic(direction), &mut update);
+        run(&mut routine.dynamic(direction), &mut update);
+    }
+}
+
+fn mechanism_routine<Velocity: VelocityUnit + Send + 'static>(
+    sim: &Arc<Mutex<MechanismSim>>,
+    config: SysIdRoutineConfig,
+) -> SysIdRoutine<Velocity>
+where
+    Velocity::Position: Send,
+{
+    let drive = sim.clone();
+    let position = sim.clone();
+    let velocity = sim.clone();
+    SysIdRoutine::new(
+        config,
+        move |voltage: Volt| drive.lock().voltage = voltage.value(),
+        move || Velocity::Position::from(position.lock().position),
+        move || Velocity::from(velocity.lock().velocity),
+        vec![],
+    )
+}
+
+#[test]
+fn routine_records_tests() {
+    let sim = Arc::new(Mutex::new(MechanismSim::new(0.0, 0.0, 1.0, 0.5, false)));
+    let config = SysIdRoutineConfig {
+        timeout: 1.0.into(),
+        ..SysIdRoutineConfig::default()
+    };
+    let routine = mechanism_routine::<MeterPerSecond>(&sim, config);
+
+    let mut command = routine.quasistatic(SysIdDirection::Reverse);
+    assert_eq!(command.get_name(), "SysId(QuasistaticReverse)");
+    run(&mut command, || sim.lock().update(0.02));
+
+    let log = routine.get_log();
+    let records = log.get_records();
+    assert_eq!(records.len(), 50);
+    assert!(records
+        .iter()
+        .all(|record| record.state == SysIdState::QuasistaticReverse));
+    assert_relative_eq!(records[10].time.value(), 0.2, epsilon = 1e-9);
+    assert_relative_eq!(records[10].voltage.value(), -0.2, epsilon = 1e-9);
+    assert!(records[49].velocity.value() < 0.0);
+    assert!(records[49].position.value() < 0.0);
+    // the mechanism is stopped once the test ends
+    assert_relative_eq!(sim.lock().voltage, 0.0);
+
+    run(&mut routine.dynamic(SysIdDirection::Forward), || {
+        sim.lock().update(0.02);
+    });
+    let log = routine.get_log();
+    assert_eq!(log.get_records().len(), 100);
+    assert_relative_eq!(log.get_records()[50].voltage.value(), 7.0);
+
+    routine.clear_log();
+    assert!(routine.get_log().get_records().is_empty());
+}
+
+#[cfg(feature = "simulation")]
+#[test]
+fn analyze_flywheel() {
+    let plant = LinearSystemId::identify_velocity_system(0.5, 0.1);
+    let sim = Arc::new(Mutex::new(FlywheelSim::new_plant(
+        plant,
+        DCMotor::new_neo(1),
+        1.0,
+    )));
+    let drive = sim.clone();
+    let velocity = sim.clone();
+    let routine = SysIdRoutine::new(
+        SysIdRoutineConfig::default(),
+        move |voltage: Volt| {
+            drive
+                .lock()
+                .set_input_voltage(voltage, &BatterySim::default());
+        },
+        || 0.0.into(),
+        move || velocity.lock().get_angular_velocity(),
+        vec![],
+    );
+    run_all(&routine, || sim.lock().update(0.02));
+
+    let feedforward: Simple<RadianPerSecond> = SysIdAnalyzer::new(&routine.get_log()).simple();
+    assert_relative_eq!(feedforward.get_k_s(), 0.0, epsilon = 1e-6);
+    assert_relative_eq!(feedforward.get_k_v(), 0.5, epsilon = 1e-6);
+    assert_relative_eq!(feedforward.get_k_a(), 0.1, epsilon = 1e-6);
+}
+
+#[test]
+fn analyze_elevator() {
+    let sim = Arc::new(Mutex::new(MechanismSim::new(0.3, 0.9, 2.5, 0.4, false)));
+    let routine = mechanism_routine(&sim, SysIdRoutineConfig::default());
+    run_all(&routine, || sim.lock().update(0.02));
+
+    let feedforward: Elevator<MeterPerSecond> = SysIdAnalyzer::new(&routine.get_log()).elevator();
+    assert_relative_eq!(feedforward.get_k_s(), 0.3, epsilon = 1e-6);
+    assert_relative_eq!(feedforward.get_k_g(), 0.9, epsilon = 1e-6);
+    assert_relative_eq!(feedforward.get_k_v(), 2.5, epsilon = 1e-6);
+    assert_relative_eq!(feedforward.get_k_a(), 0.4, epsilon = 1e-6);
+}
+
+#[test]
+fn analyze_arm() {
+    let sim = Arc::new(Mutex::new(MechanismSim::new(0.2, 1.2, 1.5, 0.3, true)));
+    let routine = mechanism_routine(&sim, SysIdRoutineConfig::default());
+    run_all(&routine, || sim.lock().update(0.02));
+
+    let feedforward: Arm = SysIdAnalyzer::new(&routine.get_log()).arm();
+    assert_relative_eq!(feedforward.get_k_s(), 0.2, epsilon = 1e-6);
+    assert_relative_eq!(feedforward.get_k_g(), 1.2, epsilon = 1e-6);
+    assert_relative_eq!(feedforward.get_k_v(), 1.5, epsilon = 1e-6);
+    assert_relative_eq!(feedforward.get_k_a(), 0.3, epsilon = 1e-6);
+}
+
+#[test]
+fn analyze_skips_test_boundaries() {
+    // two runs of the same test with a jump between them that no mechanism could make
+    let mut log = SysIdLog::new();
+    let mut sim = MechanismSim::new(0.0, 0.0, 1.0, 0.5, false);
+    for run in 0..2 {
+        sim.velocity = f64::from(run) * 100.0;
+        for i in 0..50 {
+            sim.voltage = f64::from(i % 7);
+            log.record(SysIdRecord {
+                state: SysIdState::DynamicForward,
+                time: (f64::from(i) * 0.02).into(),
+                voltage: sim.voltage.into(),
+                position: sim.position.into(),
+                velocity: sim.velocity.into(),
+            });
+            sim.update(0.02);
+        }
+    }
+
+    let feedforward: Simple = SysIdAnalyzer::new(&log).with_motion_threshold(0.1).simple();
+    assert_relative_eq!(feedforward.get_k_v(), 1.0, epsilon = 1e-6);
+    assert_relative_eq!(feedforward.get_k_a(), 0.5, epsilon = 1e-6);
+}
+
+#[test]
+#[should_panic = "not enough samples to fit the feedforward"]
+fn analyze_empty_log() {
+    let _: Simple = SysIdAnalyzer::new(&SysIdLog::new()).simple();
+}